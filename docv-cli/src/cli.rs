@@ -1,13 +1,34 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     pub filename: Option<PathBuf>,
 }
 
+#[derive(Subcommand)]
+pub enum Command {
+    /// Check a PDF for structural problems and print them.
+    ///
+    /// Exits with 1 if an error-level issue is found and with 2 if the file
+    /// can't be read at all.
+    Validate { filename: PathBuf },
+    /// Print the text of a PDF, pages separated by form feeds.
+    ///
+    /// Exits with 2 if the file can't be read or `--pages` is invalid.
+    Text {
+        filename: PathBuf,
+        /// Pages to print, such as `1-3,5,7-`. All pages if omitted.
+        #[arg(long)]
+        pages: Option<String>,
+    },
+}
+
 pub fn parse() -> Cli {
     Cli::parse()
 }
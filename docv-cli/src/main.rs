@@ -1,11 +1,26 @@
+use std::process::ExitCode;
+
+use crate::cli::Command;
+
 mod cli;
+mod page_range;
+mod text;
+mod validate;
 
-fn main() -> Result<(), Box<docv_ui::Error>> {
+fn main() -> Result<ExitCode, Box<docv_ui::Error>> {
     let cli = cli::parse();
 
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::ERROR)
         .init();
 
-    docv_ui::run(cli.filename).map_err(|err| err.into())
+    match cli.command {
+        Some(Command::Validate { filename }) => Ok(validate::run(&filename)),
+        Some(Command::Text { filename, pages }) => Ok(text::run(&filename, pages.as_deref())),
+        None => {
+            docv_ui::run(cli.filename)?;
+
+            Ok(ExitCode::SUCCESS)
+        }
+    }
 }
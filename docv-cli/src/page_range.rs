@@ -0,0 +1,183 @@
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Parses a page selection such as `1-3,5,7-` into sorted page numbers.
+///
+/// Each comma separated part is either a single page (`5`), an inclusive
+/// range (`1-3`) or a range open on one side (`-3`, `7-`). Pages are
+/// numbered from 1, duplicates are removed.
+///
+/// # Arguments
+/// * `input` - Page selection as typed by the user
+/// * `total` - Number of pages in the document
+///
+/// # Returns
+/// `Result` containing sorted unique page numbers, or `Error` if a part is
+/// malformed, inverted or points outside of `1..=total`
+pub fn parse_page_range(input: &str, total: usize) -> Result<Vec<usize>> {
+    let mut pages = Vec::new();
+
+    for part in input.split(',').map(str::trim) {
+        ensure!(!part.is_empty(), error::EmptyPart);
+
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (
+                parse_bound(start)?.unwrap_or(1),
+                parse_bound(end)?.unwrap_or(total),
+            ),
+            None => {
+                let page = parse_bound(part)?.context(error::EmptyPart)?;
+                (page, page)
+            }
+        };
+
+        ensure!(start <= end, error::Inverted { start, end });
+        ensure!(
+            start >= 1 && end <= total,
+            error::OutOfBounds {
+                page: if start < 1 { start } else { end },
+                total
+            }
+        );
+
+        pages.extend(start..=end);
+    }
+
+    pages.sort_unstable();
+    pages.dedup();
+
+    Ok(pages)
+}
+
+/// Parses one side of a range, an empty side means the range is open.
+fn parse_bound(input: &str) -> Result<Option<usize>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+
+    let page = input.parse().context(error::InvalidNumber { input })?;
+
+    Ok(Some(page))
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Empty page selection"))]
+        EmptyPart,
+
+        #[snafu(display("Invalid page number `{input}`"))]
+        InvalidNumber {
+            input: String,
+            source: std::num::ParseIntError,
+        },
+
+        #[snafu(display("Page range {start}-{end} is inverted"))]
+        Inverted { start: usize, end: usize },
+
+        #[snafu(display("Page {page} is out of bounds, document has {total} pages"))]
+        OutOfBounds { page: usize, total: usize },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_page_range() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static str,
+            total: usize,
+            expected: Option<Vec<usize>>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "pages and range",
+                input: "1-3,5",
+                total: 10,
+                expected: Some(vec![1, 2, 3, 5]),
+            },
+            TestCase {
+                name: "open end",
+                input: "7-",
+                total: 9,
+                expected: Some(vec![7, 8, 9]),
+            },
+            TestCase {
+                name: "open start",
+                input: "-3",
+                total: 9,
+                expected: Some(vec![1, 2, 3]),
+            },
+            TestCase {
+                name: "overlapping and unsorted",
+                input: "5, 2-4 ,3",
+                total: 9,
+                expected: Some(vec![2, 3, 4, 5]),
+            },
+            TestCase {
+                name: "whole document",
+                input: "-",
+                total: 3,
+                expected: Some(vec![1, 2, 3]),
+            },
+            TestCase {
+                name: "inverted range",
+                input: "5-3",
+                total: 9,
+                expected: None,
+            },
+            TestCase {
+                name: "out of bounds",
+                input: "8-10",
+                total: 9,
+                expected: None,
+            },
+            TestCase {
+                name: "page zero",
+                input: "0",
+                total: 9,
+                expected: None,
+            },
+            TestCase {
+                name: "not a number",
+                input: "1,a",
+                total: 9,
+                expected: None,
+            },
+            TestCase {
+                name: "empty part",
+                input: "1,,2",
+                total: 9,
+                expected: None,
+            },
+            TestCase {
+                name: "empty input",
+                input: "",
+                total: 9,
+                expected: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let result = parse_page_range(case.input, case.total);
+            assert_eq!(
+                result.as_ref().ok(),
+                case.expected.as_ref(),
+                "Test '{}' failed: got {:?}",
+                case.name,
+                result
+            );
+        }
+    }
+}
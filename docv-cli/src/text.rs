@@ -0,0 +1,73 @@
+use std::{io::Write, path::Path, process::ExitCode};
+
+use docv_pdf::Document;
+use snafu::{ResultExt, Snafu};
+
+use crate::page_range::parse_page_range;
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Runs `docv text`, printing the text of the selected pages to stdout.
+///
+/// # Returns
+/// - `ExitCode::SUCCESS` if the text of every selected page was printed
+/// - 2 if the document can't be read or the selection is invalid
+pub fn run(path: &Path, pages: Option<&str>) -> ExitCode {
+    match print(path, pages, &mut std::io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}: {}", path.display(), snafu::Report::from_error(err));
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Writes the text of the pages selected by `selection`, all of them if
+/// `None`, each page followed by a form feed.
+fn print(path: &Path, selection: Option<&str>, output: &mut impl Write) -> Result<()> {
+    let mut document = Document::from_path(path).context(error::Document)?;
+    let pages = document
+        .pages()
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context(error::Document)?;
+
+    let selected = match selection {
+        Some(selection) => parse_page_range(selection, pages.len()).context(error::Pages)?,
+        None => (1..=pages.len()).collect(),
+    };
+
+    for number in selected {
+        let text = pages[number - 1]
+            .extract_text()
+            .context(error::Page { number })?;
+
+        write!(output, "{text}\x0c").context(error::Output)?;
+    }
+
+    Ok(())
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Failed to read the document"))]
+        Document { source: docv_pdf::Error },
+
+        #[snafu(display("Invalid page selection"))]
+        Pages { source: crate::page_range::Error },
+
+        #[snafu(display("Failed to extract the text of page {number}"))]
+        Page {
+            number: usize,
+            source: docv_pdf::Error,
+        },
+
+        #[snafu(display("Failed to write the text"))]
+        Output { source: std::io::Error },
+    }
+}
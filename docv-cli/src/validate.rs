@@ -0,0 +1,61 @@
+use std::{io::Write, path::Path, process::ExitCode};
+
+use docv_pdf::{Document, IssueSeverity};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Runs `docv validate`, printing issues to stdout and failures to stderr.
+///
+/// # Returns
+/// - `ExitCode::SUCCESS` if there is no error-level issue
+/// - 1 if there is at least one
+/// - 2 if the document can't be opened or checked
+pub fn run(path: &Path) -> ExitCode {
+    match report(path, &mut std::io::stdout().lock()) {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("{}: {}", path.display(), snafu::Report::from_error(err));
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Writes one `severity: issue` line per issue of the document at `path`.
+///
+/// # Returns
+/// Whether an error-level issue was found
+fn report(path: &Path, output: &mut impl Write) -> Result<bool> {
+    let mut document = Document::from_path(path).context(error::Document)?;
+    let issues = document.validate().context(error::Document)?;
+
+    for issue in &issues {
+        writeln!(output, "{}: {issue}", issue.severity()).context(error::Output)?;
+    }
+    if issues.is_empty() {
+        writeln!(output, "No issues found").context(error::Output)?;
+    }
+
+    let failed = issues
+        .iter()
+        .any(|issue| issue.severity() == IssueSeverity::Error);
+
+    Ok(failed)
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Failed to read the document"))]
+        Document { source: docv_pdf::Error },
+
+        #[snafu(display("Failed to write the report"))]
+        Output { source: std::io::Error },
+    }
+}
@@ -0,0 +1,132 @@
+use std::{path::PathBuf, process::Command};
+
+/// Writes a PDF made of `objects`, numbered from 1, with a matching xref table.
+fn write_pdf(name: &str, objects: &[&str], trailer: &str) -> PathBuf {
+    let mut data = b"%PDF-1.7\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(data.len());
+        data.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", index + 1).as_bytes());
+    }
+
+    let xref = data.len();
+    data.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        data.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    data.extend_from_slice(
+        format!("trailer\n<< {trailer} >>\nstartxref\n{xref}\n%%EOF\n").as_bytes(),
+    );
+
+    let path = std::env::temp_dir().join(format!("docv-cli-{}-{name}.pdf", std::process::id()));
+    std::fs::write(&path, data).unwrap();
+    path
+}
+
+fn validate(path: &PathBuf) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_docv"))
+        .arg("validate")
+        .arg(path)
+        .output()
+        .unwrap();
+
+    (
+        output.status.code(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn test_validate_command() {
+    let page = "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] /Resources << >> >>";
+
+    struct TestCase {
+        name: &'static str,
+        path: PathBuf,
+        expected_code: Option<i32>,
+        expected_output: &'static str,
+    }
+
+    let test_cases = [
+        TestCase {
+            name: "consistent document",
+            path: write_pdf(
+                "valid",
+                &[
+                    "<< /Type /Catalog /Pages 2 0 R >>",
+                    "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+                    page,
+                ],
+                "/Root 1 0 R /Size 4",
+            ),
+            expected_code: Some(0),
+            expected_output: "No issues found\n",
+        },
+        TestCase {
+            name: "warnings only",
+            path: write_pdf(
+                "warnings",
+                &[
+                    "<< /Type /Catalog /Pages 2 0 R >>",
+                    "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+                    page,
+                ],
+                "/Root 1 0 R /Size 10",
+            ),
+            expected_code: Some(0),
+            expected_output: "warning: Trailer /Size is 10, but the highest object number is 3\n",
+        },
+        TestCase {
+            name: "errors",
+            path: write_pdf(
+                "errors",
+                &[
+                    "<< /Type /Catalog /Pages 2 0 R >>",
+                    "<< /Type /Pages /Kids [3 0 R] /Count 2 >>",
+                    page,
+                ],
+                "/Root 1 0 R /Size 10",
+            ),
+            expected_code: Some(1),
+            expected_output: "warning: Trailer /Size is 10, but the highest object number is 3\n\
+                              error: Page tree declares 2 pages, but 1 were found\n",
+        },
+        TestCase {
+            name: "cyclic page tree",
+            path: write_pdf(
+                "cycle",
+                &[
+                    "<< /Type /Catalog /Pages 2 0 R >>",
+                    "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+                    "<< /Type /Pages /Parent 2 0 R /Kids [3 0 R] /Count 1 >>",
+                ],
+                "/Root 1 0 R /Size 4",
+            ),
+            expected_code: Some(1),
+            expected_output: "error: Page tree declares 1 pages, but 0 were found\n\
+                              error: Page tree node 3 0 R is listed in its own /Kids subtree\n",
+        },
+        TestCase {
+            name: "missing file",
+            path: std::env::temp_dir().join("docv-cli-missing.pdf"),
+            expected_code: Some(2),
+            expected_output: "",
+        },
+    ];
+
+    for case in test_cases {
+        let (code, output) = validate(&case.path);
+        assert_eq!(
+            code, case.expected_code,
+            "Test '{}' failed: wrong exit code",
+            case.name
+        );
+        assert_eq!(
+            output, case.expected_output,
+            "Test '{}' failed: wrong output",
+            case.name
+        );
+    }
+}
@@ -1,34 +1,104 @@
-use std::{fs::File, path::Path};
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::Write,
+    ops::{ControlFlow, Range},
+    path::Path,
+};
 
-use snafu::{ResultExt, Snafu};
+use snafu::{ResultExt, Snafu, ensure};
 
 use crate::{
-    objects::Objects,
+    objects::{DocumentLimits, LoadPhase, LoadProgress, Objects, ParseOptions},
     pages::Pages,
+    snapshot::DocumentSnapshot,
     structures::{
+        acroform::AcroFormInfo,
+        destination::Destination,
+        embedded_file::EmbeddedFile,
+        encryption::EncryptionInfo,
         hash::Hash,
+        image::ImageRef,
         info::Info,
-        root::{Root, version::Version},
+        name_tree::read_name_tree,
+        page::Page,
+        root::{
+            Root,
+            optional_content::Layer,
+            pages_tree::{PageTreeKid, PageTreeStats, read_kids},
+            version::Version,
+            viewer_preferences::ViewerPreferences,
+        },
+        signature::{SignatureField, read_signature_fields},
+        validation::ValidationIssue,
+        xmp,
+        xref::{XrefKind, XrefMetadata},
     },
+    types::{Dictionary, IndirectReference, Object},
+    writer::PdfWriter,
 };
 
 #[derive(Debug, Snafu)]
-pub struct Error(error::Error);
+#[snafu(source(from(error::Error, Box::new)))]
+pub struct Error(Box<error::Error>);
 type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug)]
 pub struct Document {
     root: Root,
-    info: Info,
+    info: Option<Info>,
     objects: Objects,
 
     size: u64,
-    version: Version,
-    hash: Option<Hash>,
+    xref_metadata: XrefMetadata,
 }
 
 impl Document {
     pub fn from_path(path: &Path) -> crate::Result<Self> {
+        Self::from_path_with_limits(path, DocumentLimits::default())
+    }
+
+    /// Opens a document, aborting as soon as any of `limits` is exceeded.
+    ///
+    /// Cross-reference sections are read lazily, so a limit may also be hit
+    /// later, by any method that has to load more of the file.
+    pub fn from_path_with_limits(path: &Path, limits: DocumentLimits) -> crate::Result<Self> {
+        Self::open(path, limits, ParseOptions::default(), |_| {})
+    }
+
+    /// Opens a document with `limits`, reading it as chosen by `options`.
+    ///
+    /// See [`Document::from_path_with_limits`] for how the limits apply.
+    pub fn from_path_with_options(
+        path: &Path,
+        limits: DocumentLimits,
+        options: ParseOptions,
+    ) -> crate::Result<Self> {
+        Self::open(path, limits, options, |_| {})
+    }
+
+    /// Opens a document, calling `progress` after each phase of loading.
+    ///
+    /// Meant for showing feedback while large files are opened. The phases
+    /// are reported in order: `Header`, `Xref`, `Trailer` and `PageIndex`.
+    pub fn from_path_with_progress(
+        path: &Path,
+        progress: impl FnMut(LoadProgress),
+    ) -> crate::Result<Self> {
+        Self::open(
+            path,
+            DocumentLimits::default(),
+            ParseOptions::default(),
+            progress,
+        )
+    }
+
+    fn open(
+        path: &Path,
+        limits: DocumentLimits,
+        options: ParseOptions,
+        mut progress: impl FnMut(LoadProgress),
+    ) -> crate::Result<Self> {
         let file = File::open(path)
             .with_context(|_| error::OpenFile { path })
             .map_err(|err| err.into())
@@ -40,10 +110,11 @@ impl Document {
             .map_err(|err| err.into())
             .context(crate::error::Document)?;
 
-        let (mut objects, metadata) = Objects::from_file(file)
-            .context(error::Objects)
-            .map_err(|err| err.into())
-            .context(crate::error::Document)?;
+        let (mut objects, metadata) =
+            Objects::from_file_with_progress(file, limits, options, &mut progress)
+                .context(error::Objects)
+                .map_err(|err| err.into())
+                .context(crate::error::Document)?;
 
         let root = objects
             .get_object(&metadata.root_id)
@@ -53,40 +124,114 @@ impl Document {
             .map_err(|err| err.into())
             .context(crate::error::Document)?;
 
-        let root = Root::from_object(root, &mut objects)
-            .context(error::Root)
-            .map_err(|err| err.into())
-            .context(crate::error::Document)?;
-
+        // A dangling `/Info` is treated as absent, the document is readable without it.
         let info = metadata
             .info_id
-            .map(|object| -> Result<Info> {
-                let object = objects
-                    .get_object(&object)
-                    .context(error::Object { object })?;
-
-                Ok(Info::from_object(object).context(error::Info)?)
+            .and_then(|reference| match objects.get_object(&reference) {
+                Ok(object) => Some(object),
+                Err(err) => {
+                    tracing::warn!("Ignoring unreadable document information {reference}: {err}");
+                    None
+                }
             })
+            .map(|object| -> Result<Info> { Ok(Info::from_object(object).context(error::Info)?) })
             .transpose()
             .context(crate::error::Document)?;
 
+        progress(LoadProgress {
+            phase: LoadPhase::Trailer,
+            objects_resolved: objects.resolved_count(),
+        });
+
+        let root = Root::from_object(root, &mut objects)
+            .context(error::Root)
+            .map_err(|err| err.into())
+            .context(crate::error::Document)?;
+
+        progress(LoadProgress {
+            phase: LoadPhase::PageIndex,
+            objects_resolved: objects.resolved_count(),
+        });
+
         Ok(Document {
             root,
-            info: info.unwrap_or_default(),
+            info,
             objects,
 
             size: file_metadata.len(),
-            version: metadata.version,
-            hash: metadata.hash,
+            xref_metadata: metadata,
         })
     }
 
-    pub fn info(&self) -> &Info {
-        &self.info
+    /// Document information dictionary (`/Info` of the trailer).
+    ///
+    /// # Returns
+    /// `None` if the trailer has no `/Info` or it points to a missing object
+    pub fn info(&self) -> Option<&Info> {
+        self.info.as_ref()
+    }
+
+    /// Document title (`/Title` of the Info dictionary).
+    pub fn title(&self) -> Option<String> {
+        self.info.as_ref()?.title.clone()
+    }
+
+    /// Name of the person who created the document (`/Author`).
+    pub fn author(&self) -> Option<String> {
+        self.info.as_ref()?.author.clone()
+    }
+
+    /// Subject of the document (`/Subject`).
+    pub fn subject(&self) -> Option<String> {
+        self.info.as_ref()?.subject.clone()
+    }
+
+    /// Keywords associated with the document (`/Keywords`).
+    pub fn keywords(&self) -> Option<String> {
+        self.info.as_ref()?.keywords.clone()
+    }
+
+    /// Application that created the original document (`/Creator`).
+    pub fn creator(&self) -> Option<String> {
+        self.info.as_ref()?.creator.clone()
+    }
+
+    /// Application that converted the document to PDF (`/Producer`).
+    pub fn producer(&self) -> Option<String> {
+        self.info.as_ref()?.producer.clone()
+    }
+
+    /// Document title, taken from `dc:title` of the XMP metadata if the Info dictionary has none.
+    pub fn title_or_xmp(&mut self) -> crate::Result<Option<String>> {
+        self.info_or_xmp(Self::title, "dc:title")
+    }
+
+    /// Author of the document, taken from `dc:creator` of the XMP metadata if the Info dictionary has none.
+    pub fn author_or_xmp(&mut self) -> crate::Result<Option<String>> {
+        self.info_or_xmp(Self::author, "dc:creator")
+    }
+
+    fn info_or_xmp(
+        &mut self,
+        field: fn(&Self) -> Option<String>,
+        property: &str,
+    ) -> crate::Result<Option<String>> {
+        if let Some(value) = field(self) {
+            return Ok(Some(value));
+        }
+
+        let xml = self.read_metadata_xml().context(crate::error::Document)?;
+
+        Ok(xml
+            .as_deref()
+            .and_then(|xml| xmp::first_item(xml, property)))
     }
 
     pub fn version(&self) -> &Version {
-        self.root.version.as_ref().unwrap_or(&self.version)
+        self.root
+            .version
+            .as_ref()
+            .unwrap_or(&self.xref_metadata.version)
     }
 
     pub fn filesize(&self) -> u64 {
@@ -94,123 +239,4111 @@ impl Document {
     }
 
     pub fn hash(&self) -> Option<&Hash> {
-        self.hash.as_ref()
+        self.xref_metadata.hash.as_ref()
     }
 
-    /// Iterator over pages in a PDF document's page tree.
+    /// Summary of the newest cross-reference section and its trailer.
     ///
-    /// The `Pages` struct provides an iterator that traverses the PDF page tree
-    /// structure, resolving indirect references and flattening the hierarchy
-    /// into a sequence of individual page objects.
+    /// Sections reached through `/Prev` are not reflected here, use
+    /// [`XrefMetadata::prev`] to find out whether the document has any.
+    pub fn xref_metadata(&self) -> &XrefMetadata {
+        &self.xref_metadata
+    }
+
+    /// Source bytes of the newest trailer, see [`XrefMetadata::trailer_range`].
+    pub fn raw_trailer_bytes(&self) -> &[u8] {
+        self.objects
+            .file_bytes(self.xref_metadata.trailer_range.clone())
+            .unwrap_or_default()
+    }
+
+    /// Source bytes of the newest cross-reference section, from the offset
+    /// given after `startxref` to the end of its trailer.
     ///
-    /// # Page Tree Structure
-    /// PDF documents organize pages in a tree structure where:
-    /// - The root node is a `/Pages` object
-    /// - Intermediate nodes are also `/Pages` objects (containing `/Kids`)
-    /// - Leaf nodes are `/Page` objects
-    /// - Attributes can be inherited from parent pages nodes
+    /// For an xref table these start with `xref` and end with the trailer
+    /// dictionary, for an xref stream they are the whole `N G obj` to
+    /// `endobj`. Empty if the file had no usable cross-reference section.
+    pub fn xref_section_bytes(&self) -> Vec<u8> {
+        self.objects
+            .startxref_position()
+            .and_then(|start| {
+                self.objects
+                    .file_bytes(start..self.xref_metadata.trailer_range.end)
+            })
+            .unwrap_or_default()
+            .to_vec()
+    }
+
+    /// Offset of the newest cross-reference section, as given after `startxref`.
     ///
-    /// This iterator performs a depth-first traversal of this tree structure.
+    /// 0 if the file had no usable cross-reference section, see [`XrefKind::Scanned`].
+    pub fn startxref_offset(&self) -> u64 {
+        self.objects.startxref_offset()
+    }
+
+    /// Offsets of the cross-reference sections of every revision, newest first.
     ///
-    /// # Usage
-    /// ```
-    /// use std::path::PathBuf;
-    /// use docv_pdf::Document;
+    /// The first offset is [`Document::startxref_offset`], the following ones
+    /// come from the `/Prev` chain. Offsets are given as stored in the file.
+    pub fn revision_offsets(&mut self) -> crate::Result<Vec<u64>> {
+        let offsets = self
+            .objects
+            .revision_offsets()
+            .context(error::Xref)
+            .map_err(|err| err.into())
+            .context(crate::error::Document)?;
+
+        Ok(offsets)
+    }
+
+    /// Bytes of the file taken by an indirect object, e.g. to check a `/ByteRange`.
     ///
-    /// let mut document = Document::from_path(&PathBuf::from("../example_files/report1.pdf")).unwrap();
-    /// for page in document.pages() {
-    ///     let page = page.unwrap();
+    /// # Returns
+    /// - `Ok(Some(Range))` from `N G obj` up to and including `endobj`
+    /// - `Ok(None)` if the object is unknown or stored in an object stream
+    pub fn byte_range(
+        &mut self,
+        object: &IndirectReference,
+    ) -> crate::Result<Option<Range<usize>>> {
+        self.objects
+            .read_all_tables()
+            .context(error::Xref)
+            .map_err(|err| err.into())
+            .context(crate::error::Document)?;
+
+        Ok(self.objects.byte_range(object))
+    }
+
+    /// Describes the document encryption without decrypting anything.
     ///
-    ///     // Process page...
-    /// }
-    /// ```
+    /// # Returns
+    /// - `Ok(None)` if the trailer has no `/Encrypt` entry
+    /// - `Ok(Some(EncryptionInfo))` with the encryption parameters otherwise
+    pub fn encryption_info(&mut self) -> crate::Result<Option<EncryptionInfo>> {
+        let info = self
+            .read_encryption_info()
+            .context(crate::error::Document)?;
+
+        Ok(info)
+    }
+
+    fn read_encryption_info(&mut self) -> Result<Option<EncryptionInfo>> {
+        let Some(encrypt_id) = self.xref_metadata.encrypt_id else {
+            return Ok(None);
+        };
+
+        let object = self
+            .objects
+            .get_object(&encrypt_id)
+            .context(error::Object { object: encrypt_id })?;
+        let dictionary = object.as_dictionary().context(error::InvalidEncrypt)?;
+
+        let file_id = self.xref_metadata.hash.as_ref().map(Hash::initial);
+        let info =
+            EncryptionInfo::from_dictionary(dictionary, file_id).context(error::Encryption)?;
+
+        Ok(Some(info))
+    }
+
+    /// Optional content groups (layers) declared in the catalog's `/OCProperties`.
     ///
-    /// # Note
-    /// This iterator consumes and mutates the `Objects` store to resolve
-    /// indirect references. It should not be used concurrently with other
-    /// operations that modify the same objects store.
-    pub fn pages<'a>(&'a mut self) -> Pages<'a> {
-        Pages::new(&self.root.pages, &mut self.objects)
+    /// # Returns
+    /// Layers in `/OCGs` order with their visibility in the default
+    /// configuration, or an empty list if the document has no layers
+    pub fn layers(&mut self) -> crate::Result<Vec<Layer>> {
+        let layers = self.read_layers().context(crate::error::Document)?;
+
+        Ok(layers)
     }
-}
 
-mod error {
-    use std::path::PathBuf;
+    fn read_layers(&mut self) -> Result<Vec<Layer>> {
+        let Some(properties) = &self.root.oc_properties else {
+            return Ok(Vec::new());
+        };
 
-    use snafu::Snafu;
+        let layers =
+            Layer::from_properties(properties, &mut self.objects).context(error::Layers)?;
 
-    use crate::types::IndirectReference;
+        Ok(layers)
+    }
 
-    #[derive(Debug, Snafu)]
-    #[snafu(visibility(pub(super)), context(suffix(false)))]
-    pub(super) enum Error {
-        #[snafu(display("Failed to open file: {}", path.display()))]
-        OpenFile {
-            path: PathBuf,
-            source: std::io::Error,
-        },
+    /// Viewer preferences of the catalog (`/ViewerPreferences`).
+    ///
+    /// # Returns
+    /// - `Ok(None)` if the catalog has no viewer preferences
+    /// - `Ok(Some(ViewerPreferences))` with spec defaults for missing entries otherwise
+    pub fn viewer_preferences(&mut self) -> crate::Result<Option<ViewerPreferences>> {
+        let preferences = self
+            .read_viewer_preferences()
+            .context(crate::error::Document)?;
 
-        #[snafu(display("Failed to get metadata"))]
-        Metadata { source: std::io::Error },
+        Ok(preferences)
+    }
 
-        #[snafu(display("Failed to get objects"))]
-        Objects { source: crate::objects::Error },
+    fn read_viewer_preferences(&mut self) -> Result<Option<ViewerPreferences>> {
+        let Some(preferences) = &self.root.viewer_preferences else {
+            return Ok(None);
+        };
 
-        #[snafu(display("Failed to get object {object}"))]
-        Object {
-            object: IndirectReference,
-            source: crate::objects::Error,
-        },
+        let preferences = ViewerPreferences::from_object(preferences, &mut self.objects)
+            .context(error::ViewerPreferences)?;
 
-        #[snafu(display("Failed to read root dictionary"))]
-        Root {
-            source: crate::structures::root::Error,
-        },
+        Ok(Some(preferences))
+    }
 
-        #[snafu(display("Failed to read info dictionary"))]
-        Info {
-            source: crate::structures::info::Error,
-        },
+    /// Whether the document is a tagged PDF.
+    ///
+    /// Requires `/MarkInfo /Marked true` in the catalog together with a
+    /// `/StructTreeRoot`, so the structure tree can actually be read.
+    pub fn is_tagged(&mut self) -> crate::Result<bool> {
+        let tagged = self.read_is_tagged().context(crate::error::Document)?;
+
+        Ok(tagged)
     }
-}
 
-#[cfg(test)]
-mod test {
-    use snafu::Whatever;
+    fn read_is_tagged(&mut self) -> Result<bool> {
+        let has_structure = self
+            .root
+            .struct_tree_root
+            .as_ref()
+            .is_some_and(|object| !object.direct(&mut self.objects).is_null());
+        let Some(mark_info) = &self.root.mark_info else {
+            return Ok(false);
+        };
 
-    use super::*;
-    use std::{fs, path::PathBuf, sync::LazyLock};
+        let mark_info = mark_info.direct(&mut self.objects).into_owned();
+        let marked = mark_info
+            .as_dictionary()
+            .context(error::MarkInfo)?
+            .get("Marked")
+            .map(|object| object.as_bool())
+            .transpose()
+            .context(error::MarkInfo)?
+            .unwrap_or(false);
 
-    static EXAMPLES: LazyLock<PathBuf> = LazyLock::new(|| {
-        let mut dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        dir.pop();
-        dir.push("example_files");
-        dir
-    });
+        Ok(marked && has_structure)
+    }
 
-    #[snafu::report]
-    #[test]
-    fn read_example_files() -> std::result::Result<(), Whatever> {
-        for example in
-            fs::read_dir(EXAMPLES.clone()).whatever_context("Failed to read directory")?
-        {
-            let entry = example.whatever_context("Failed to directory entry")?;
-            let path = entry.path();
+    /// XMP metadata of the document, the decoded `/Metadata` stream of the catalog.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if the catalog has no metadata stream
+    /// - `Ok(Some(String))` otherwise, invalid UTF-8 replaced
+    pub fn metadata_xml(&mut self) -> crate::Result<Option<String>> {
+        let xml = self.read_metadata_xml().context(crate::error::Document)?;
 
-            let mut document = Document::from_path(&path)
-                .with_whatever_context(|_| format!("Failed to open file {}", path.display()))?;
+        Ok(xml)
+    }
 
-            let count = document.pages().count();
+    fn read_metadata_xml(&mut self) -> Result<Option<String>> {
+        let Some(reference) = self.root.metadata else {
+            return Ok(None);
+        };
 
-            let result = document
-                .pages()
-                .collect::<std::result::Result<Vec<_>, _>>()
-                .with_whatever_context(|_| {
-                    format!("Failed to iterate over pages for file {}", path.display())
-                })?;
+        let object = self
+            .objects
+            .get_object(&reference)
+            .context(error::Object { object: reference })?;
+        let mut stream = object.as_stream().context(error::InvalidMetadata)?.clone();
+        self.objects
+            .decode_budget()
+            .process_filters(&mut stream)
+            .context(error::DecodeMetadata)?;
 
-            assert_eq!(result.len(), count);
+        Ok(Some(String::from_utf8_lossy(&stream.data).into_owned()))
+    }
+
+    /// PDF/A part and conformance level claimed by the XMP metadata, e.g. `2B`.
+    ///
+    /// Only the `pdfaid` identification is read, the file isn't checked
+    /// against the PDF/A requirements.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if there is no metadata or it has no PDF/A identification
+    /// - `Ok(Some(String))` otherwise
+    pub fn pdfa_conformance(&mut self) -> crate::Result<Option<String>> {
+        let xml = self.read_metadata_xml().context(crate::error::Document)?;
+
+        Ok(xml.as_deref().and_then(xmp::pdfa_conformance))
+    }
+
+    /// Default natural language of the document's text (`/Lang`).
+    ///
+    /// A language tag such as `en-US`, pages and structure elements may
+    /// override it, see [`Page::language`](crate::Page::language).
+    pub fn language(&mut self) -> crate::Result<Option<String>> {
+        let language = self.read_language().context(crate::error::Document)?;
+
+        Ok(language)
+    }
+
+    fn read_language(&mut self) -> Result<Option<String>> {
+        let Some(lang) = &self.root.lang else {
+            return Ok(None);
+        };
+
+        let lang = match lang.direct(&mut self.objects).into_owned() {
+            Object::IndirectDefinition(definition) => (*definition).clone(),
+            object => object,
+        };
+        let language = lang
+            .as_string()
+            .context(error::InvalidLanguage)?
+            .to_text_string();
+
+        Ok(Some(language))
+    }
+
+    /// Files attached to the document through the `/EmbeddedFiles` name tree.
+    ///
+    /// # Returns
+    /// Attachments in name tree order, or an empty list if there are none
+    pub fn embedded_files(&mut self) -> crate::Result<Vec<EmbeddedFile>> {
+        let files = self.read_embedded_files().context(crate::error::Document)?;
+
+        Ok(files)
+    }
+
+    fn read_embedded_files(&mut self) -> Result<Vec<EmbeddedFile>> {
+        let Some(tree) = self
+            .root
+            .names
+            .as_ref()
+            .and_then(|names| names.embedded_files())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let entries = read_name_tree(tree, &mut self.objects).context(error::NameTree)?;
+
+        let mut files = Vec::with_capacity(entries.len());
+        for (key, file_spec) in entries {
+            let file = EmbeddedFile::from_file_spec(key, &file_spec, &mut self.objects)
+                .context(error::EmbeddedFile)?;
+            files.push(file);
+        }
+
+        Ok(files)
+    }
+
+    /// Image XObjects referenced from the `/XObject` resources of any page.
+    ///
+    /// Images used by several pages are reported once, in order of first use.
+    /// Images nested inside form XObjects are not included.
+    pub fn images(&mut self) -> crate::Result<Vec<ImageRef>> {
+        let pages = self.pages().collect::<crate::Result<Vec<_>>>()?;
+        let images = self.read_images(&pages).context(crate::error::Document)?;
+
+        Ok(images)
+    }
+
+    fn read_images(&mut self, pages: &[Page]) -> Result<Vec<ImageRef>> {
+        let mut seen = BTreeSet::new();
+        let mut images = Vec::new();
+
+        let references = pages
+            .iter()
+            .flat_map(|page| page.x_objects())
+            .filter_map(|object| object.as_indirect_ref().ok());
+        for reference in references {
+            if !seen.insert(*reference) {
+                continue;
+            }
+
+            if let Some(image) =
+                ImageRef::from_reference(*reference, &mut self.objects).context(error::Image)?
+            {
+                images.push(image);
+            }
+        }
+
+        Ok(images)
+    }
+
+    /// Signature fields of the interactive form (`/AcroForm`).
+    ///
+    /// Signature values are reported as stored, nothing is verified.
+    ///
+    /// # Returns
+    /// Signed and unsigned signature fields, or an empty list if the
+    /// document has no interactive form
+    pub fn signatures(&mut self) -> crate::Result<Vec<SignatureField>> {
+        let signatures = self.read_signatures().context(crate::error::Document)?;
+
+        Ok(signatures)
+    }
+
+    fn read_signatures(&mut self) -> Result<Vec<SignatureField>> {
+        let Some(acro_form) = &self.root.acro_form else {
+            return Ok(Vec::new());
+        };
+
+        let signatures =
+            read_signature_fields(acro_form, &mut self.objects).context(error::Signatures)?;
+
+        Ok(signatures)
+    }
+
+    /// Document-wide settings of the interactive form (`/AcroForm`).
+    ///
+    /// # Returns
+    /// - `Ok(None)` if the document has no interactive form
+    /// - `Ok(Some(AcroFormInfo))` otherwise
+    pub fn acroform_info(&mut self) -> crate::Result<Option<AcroFormInfo>> {
+        let info = self.read_acroform_info().context(crate::error::Document)?;
+
+        Ok(info)
+    }
+
+    fn read_acroform_info(&mut self) -> Result<Option<AcroFormInfo>> {
+        let Some(acro_form) = &self.root.acro_form else {
+            return Ok(None);
+        };
+
+        let info =
+            AcroFormInfo::from_object(acro_form, &mut self.objects).context(error::AcroForm)?;
+
+        Ok(Some(info))
+    }
+
+    /// Whether the catalog has a `/Perms` dictionary.
+    ///
+    /// It holds DocMDP or usage rights signatures that restrict which
+    /// changes may be made to the document.
+    pub fn has_perms(&self) -> bool {
+        self.root.perms.is_some()
+    }
+
+    /// Destination shown when the document is opened (`/OpenAction`).
+    ///
+    /// Both an explicit destination and a `/GoTo` action are resolved, named
+    /// destinations are looked up with [`Document::named_destination`].
+    ///
+    /// # Returns
+    /// - `Ok(None)` if there is no open action or it is not a `/GoTo` action
+    /// - `Ok(Some(Destination))` otherwise
+    pub fn open_action(&mut self) -> crate::Result<Option<Destination>> {
+        let destination = self.read_open_action().context(crate::error::Document)?;
+
+        Ok(destination)
+    }
+
+    fn read_open_action(&mut self) -> Result<Option<Destination>> {
+        let Some(action) = &self.root.open_action else {
+            return Ok(None);
+        };
+
+        let action = action.direct(&mut self.objects).into_owned();
+        let destination = match &action {
+            Object::Array(_) => action,
+            Object::Dictionary(dictionary) => {
+                let is_goto = dictionary
+                    .get("S")
+                    .and_then(|object| object.as_name().ok())
+                    .is_some_and(|name| name.as_str() == "GoTo");
+                let Some(destination) = dictionary.get("D").filter(|_| is_goto) else {
+                    return Ok(None);
+                };
+
+                destination.direct(&mut self.objects).into_owned()
+            }
+            _ => return Ok(None),
+        };
+
+        match &destination {
+            Object::Name(name) => self.read_named_destination(name.as_str()),
+            Object::String(string) => {
+                self.read_named_destination(&String::from_utf8_lossy(string.as_bytes()))
+            }
+            _ => {
+                let destination = Destination::from_object(&destination, &mut self.objects)
+                    .context(error::Destination)?;
+
+                Ok(Some(destination))
+            }
+        }
+    }
+
+    /// Looks up a named destination.
+    ///
+    /// The `/Dests` name tree of the `/Names` dictionary is searched first,
+    /// then the `/Dests` dictionary of the catalog that PDF 1.1 files use.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if there is no destination called `name`
+    /// - `Ok(Some(Destination))` otherwise
+    pub fn named_destination(&mut self, name: &str) -> crate::Result<Option<Destination>> {
+        let destination = self
+            .read_named_destination(name)
+            .context(crate::error::Document)?;
+
+        Ok(destination)
+    }
+
+    fn read_named_destination(&mut self, name: &str) -> Result<Option<Destination>> {
+        let mut destination = None;
+
+        if let Some(tree) = self.root.names.as_ref().and_then(|names| names.dests()) {
+            let entries = read_name_tree(tree, &mut self.objects).context(error::NameTree)?;
+            destination = entries
+                .into_iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, destination)| destination);
+        }
+
+        if destination.is_none()
+            && let Some(dests) = &self.root.dests
+        {
+            let dests = dests.direct(&mut self.objects).into_owned();
+            destination = dests
+                .as_dictionary()
+                .context(error::InvalidDests)?
+                .get(name)
+                .cloned();
+        }
+
+        let Some(destination) = destination else {
+            return Ok(None);
+        };
+
+        let destination = Destination::from_object(&destination, &mut self.objects)
+            .context(error::Destination)?;
+
+        Ok(Some(destination))
+    }
+
+    /// Zero-based index of the page object `reference` in the page tree.
+    ///
+    /// Useful to turn a [`crate::DestinationPage::Reference`] into a page number.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if `reference` is not a page of this document
+    /// - `Ok(Some(index))` otherwise
+    pub fn page_index(&mut self, reference: IndirectReference) -> crate::Result<Option<usize>> {
+        let index = self
+            .read_page_index(reference)
+            .context(crate::error::Document)?;
+
+        Ok(index)
+    }
+
+    fn read_page_index(&mut self, reference: IndirectReference) -> Result<Option<usize>> {
+        let mut index = 0;
+        let mut found = None;
+        self.walk_page_tree(|node| {
+            if node.kids.is_none() {
+                if node.reference == Some(reference) {
+                    found = Some(index);
+                    return ControlFlow::Break(());
+                }
+                index += 1;
+            }
+
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(found)
+    }
+
+    /// Depth, size and balance of the page tree.
+    ///
+    /// Uses the same traversal as [`Document::page_index`]: repeated nodes
+    /// are visited once and kids that aren't dictionaries are skipped.
+    pub fn page_tree_stats(&mut self) -> crate::Result<PageTreeStats> {
+        let stats = self
+            .read_page_tree_stats()
+            .context(crate::error::Document)?;
+
+        Ok(stats)
+    }
+
+    fn read_page_tree_stats(&mut self) -> Result<PageTreeStats> {
+        let mut stats = PageTreeStats {
+            node_count: 1,
+            max_kids: self.root.pages.kids.len(),
+            ..Default::default()
+        };
+
+        self.walk_page_tree(|node| {
+            match node.kids {
+                Some(kids) => {
+                    stats.node_count += 1;
+                    stats.max_kids = stats.max_kids.max(kids);
+                }
+                None => {
+                    stats.leaf_count += 1;
+                    stats.max_depth = stats.max_depth.max(node.depth);
+                }
+            }
+
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(stats)
+    }
+
+    /// Depth-first walk over the page tree below the root, in page order.
+    ///
+    /// `visit` gets every node, see [`PageTreeVisit`]. Returning
+    /// `ControlFlow::Break` stops the walk.
+    ///
+    /// # Returns
+    /// Nodes listed again in `/Kids` below themselves. They are skipped, as
+    /// the walk would never end otherwise.
+    fn walk_page_tree(
+        &mut self,
+        mut visit: impl FnMut(PageTreeVisit) -> ControlFlow<()>,
+    ) -> Result<Vec<IndirectReference>> {
+        let mut visited = BTreeSet::new();
+        let mut cycles = Vec::new();
+        let mut stack = vec![(None, self.root.pages.kids.clone().into_iter())];
+
+        while let Some((listed_by, kids)) = stack.last_mut() {
+            let listed_by = *listed_by;
+            let Some(kid) = kids.next() else {
+                stack.pop();
+                continue;
+            };
+            let (reference, object) = match kid {
+                PageTreeKid::Reference(reference) => {
+                    // Cyclic `/Kids` would otherwise be walked forever.
+                    if !visited.insert(reference) {
+                        if stack.iter().any(|(node, _)| *node == Some(reference)) {
+                            cycles.push(reference);
+                        }
+                        continue;
+                    }
+
+                    let object = self
+                        .objects
+                        .get_object(&reference)
+                        .context(error::Object { object: reference })?;
+                    (Some(reference), object)
+                }
+                PageTreeKid::Inline(dictionary) => (None, Object::Dictionary(*dictionary)),
+            };
+            let Ok(dictionary) = object.as_dictionary() else {
+                continue;
+            };
+
+            let mut node = PageTreeVisit {
+                reference,
+                depth: stack.len(),
+                kids: None,
+                listed_by,
+                parent: dictionary
+                    .get("Parent")
+                    .and_then(|object| object.as_indirect_ref().ok())
+                    .copied(),
+            };
+            match dictionary.get("Kids") {
+                Some(kids) => {
+                    let kids = read_kids(kids, self.objects.options().strictness)
+                        .context(error::InvalidKids)?;
+                    node.kids = Some(kids.len());
+                    if visit(node).is_break() {
+                        break;
+                    }
+                    stack.push((reference, kids.into_iter()));
+                }
+                None => {
+                    if visit(node).is_break() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(cycles)
+    }
+
+    /// Writes a copy of the document without object streams or cross-reference streams.
+    ///
+    /// Every object in use is written as a plain indirect object, objects
+    /// stored in object streams included, followed by a classic xref table.
+    /// Stream data is copied as is, still encoded. Encrypted documents are
+    /// rejected, as objects taken out of object streams would stay unencrypted.
+    ///
+    /// # Arguments
+    /// * `output` - Destination of the rewritten file
+    pub fn rewrite_uncompressed<W: Write>(&mut self, output: W) -> crate::Result<()> {
+        self.write_uncompressed(output, false)
+            .context(crate::error::Document)?;
+
+        Ok(())
+    }
+
+    /// Same as [`Document::rewrite_uncompressed`], but keeps the original bytes where possible.
+    ///
+    /// Objects that are stored directly in the file and weren't staged
+    /// through [`Document::objects_mut`] are copied verbatim, whitespace and
+    /// comments included. Only staged objects and objects taken out of object
+    /// streams are serialized again, so the output stays close to the
+    /// original and diffs between versions show the actual edits.
+    ///
+    /// # Arguments
+    /// * `output` - Destination of the rewritten file
+    pub fn rewrite_preserving<W: Write>(&mut self, output: W) -> crate::Result<()> {
+        self.write_uncompressed(output, true)
+            .context(crate::error::Document)?;
+
+        Ok(())
+    }
+
+    fn write_uncompressed<W: Write>(&mut self, output: W, preserve_bytes: bool) -> Result<()> {
+        ensure!(
+            self.xref_metadata.encrypt_id.is_none(),
+            error::RewriteEncrypted
+        );
+
+        let references = self.rewritten_references()?;
+
+        let mut writer =
+            PdfWriter::new(output, &self.xref_metadata.version).context(error::Write)?;
+        for reference in references {
+            let object = self
+                .objects
+                .get_object(&reference)
+                .context(error::Object { object: reference })?;
+            if is_cross_reference_data(&object) {
+                continue;
+            }
+
+            let raw = preserve_bytes
+                .then(|| self.objects.raw_bytes(&reference))
+                .flatten();
+            match raw {
+                Some(data) => writer.write_raw(reference, data),
+                None => writer.write_object(reference, &object),
+            }
+            .context(error::Write)?;
+        }
+
+        writer
+            .finish(self.rewritten_trailer())
+            .context(error::Write)?;
+
+        Ok(())
+    }
+
+    /// Writes a compact copy of the document using object streams and a cross-reference stream.
+    ///
+    /// Objects that aren't streams are packed into Flate-compressed object
+    /// streams, the rest is written as plain indirect objects, followed by a
+    /// cross-reference stream. Documents older than PDF 1.5 are written as
+    /// 1.5, which introduced both. Stream data is copied as is. Encrypted
+    /// documents are rejected, as packed objects would stay unencrypted.
+    ///
+    /// # Arguments
+    /// * `output` - Destination of the optimized file
+    pub fn save_optimized<W: Write>(&mut self, output: W) -> crate::Result<()> {
+        self.write_optimized(output)
+            .context(crate::error::Document)?;
+
+        Ok(())
+    }
+
+    fn write_optimized<W: Write>(&mut self, output: W) -> Result<()> {
+        /// Number of objects packed into each object stream.
+        const OBJECTS_PER_STREAM: usize = 100;
+
+        ensure!(
+            self.xref_metadata.encrypt_id.is_none(),
+            error::RewriteEncrypted
+        );
+
+        let references = self.rewritten_references()?;
+        let mut next_id = references.last().map_or(1, |reference| reference.id + 1);
+
+        let version = if self.xref_metadata.version < Version::Pdf1_5 {
+            Version::Pdf1_5
+        } else {
+            self.xref_metadata.version.clone()
+        };
+        let mut writer = PdfWriter::new(output, &version).context(error::Write)?;
+
+        let mut packed = Vec::new();
+        for reference in references {
+            let object = self
+                .objects
+                .get_object(&reference)
+                .context(error::Object { object: reference })?;
+            if is_cross_reference_data(&object) {
+                continue;
+            }
+
+            // Object streams can only hold objects of generation 0.
+            if object.is_stream() || reference.gen_id != 0 {
+                writer
+                    .write_object(reference, &object)
+                    .context(error::Write)?;
+            } else {
+                packed.push((reference, object));
+            }
+        }
+
+        for objects in packed.chunks(OBJECTS_PER_STREAM) {
+            let reference = IndirectReference {
+                id: next_id,
+                gen_id: 0,
+            };
+            next_id += 1;
+
+            writer
+                .write_object_stream(reference, objects)
+                .context(error::Write)?;
+        }
+
+        writer
+            .finish_with_xref_stream(self.rewritten_trailer())
+            .context(error::Write)?;
+
+        Ok(())
+    }
+
+    /// Objects in use or staged, in object number order.
+    fn rewritten_references(&mut self) -> Result<Vec<IndirectReference>> {
+        let mut references = self.objects.in_use_references().context(error::Xref)?;
+        references.extend(self.objects.staged().map(|(reference, _)| *reference));
+        references.sort();
+        references.dedup();
+
+        Ok(references)
+    }
+
+    /// Trailer of a complete rewrite of the document.
+    fn rewritten_trailer(&self) -> Dictionary {
+        let mut trailer = Dictionary::from([(
+            "Root",
+            Object::IndirectReference(self.xref_metadata.root_id),
+        )]);
+        if let Some(info_id) = self.xref_metadata.info_id {
+            trailer.insert("Info", Object::IndirectReference(info_id));
+        }
+        if let Some(hash) = &self.xref_metadata.hash {
+            trailer.insert("ID", hash.to_object());
+        }
+
+        trailer
+    }
+
+    /// Object store of the document, for resolving and staging many edits at once.
+    ///
+    /// Edits staged with [`Objects::stage`] are seen by every later read
+    /// through this document and are written by [`Document::save_incremental`].
+    /// The catalog and `/Info` read when the document was opened aren't
+    /// updated.
+    ///
+    /// The returned reference borrows the whole document mutably, the same
+    /// way the [`Pages`] iterator does, so it has to be dropped before pages
+    /// are iterated or other methods are called.
+    pub fn objects_mut(&mut self) -> &mut Objects {
+        &mut self.objects
+    }
+
+    /// Writes the original file followed by an incremental update with the staged objects.
+    ///
+    /// The new cross-reference table lists the staged objects only and
+    /// links to the previous one through `/Prev`, so signatures over the
+    /// original bytes stay valid. Encrypted documents are rejected, as staged
+    /// objects would be written unencrypted.
+    ///
+    /// # Arguments
+    /// * `output` - Destination of the updated file
+    pub fn save_incremental<W: Write>(&mut self, output: W) -> crate::Result<()> {
+        self.write_incremental(output)
+            .context(crate::error::Document)?;
+
+        Ok(())
+    }
+
+    fn write_incremental<W: Write>(&mut self, mut output: W) -> Result<()> {
+        ensure!(
+            self.xref_metadata.encrypt_id.is_none(),
+            error::RewriteEncrypted
+        );
+        ensure!(
+            self.xref_metadata.kind != XrefKind::Scanned,
+            error::IncrementalWithoutXref
+        );
+        // The newest trailer may declare a smaller `/Size` than older ones.
+        let size = self.objects.size().context(error::Xref)?;
+
+        let original = self
+            .objects
+            .file_bytes(0..self.objects.file_len())
+            .unwrap_or_default();
+        output.write_all(original).context(error::Write)?;
+        let mut offset = original.len();
+        if !original.ends_with(b"\n") {
+            output.write_all(b"\n").context(error::Write)?;
+            offset += 1;
+        }
+
+        // Offsets in the file count from the header, garbage before it aside.
+        let mut writer = PdfWriter::append(output, offset - self.objects.first_byte());
+        for (reference, object) in self.objects.staged() {
+            writer
+                .write_object(*reference, object)
+                .context(error::Write)?;
+        }
+
+        let mut trailer = Dictionary::from([
+            (
+                "Root",
+                Object::IndirectReference(self.xref_metadata.root_id),
+            ),
+            ("Size", Object::integer(size as i64)),
+            (
+                "Prev",
+                Object::integer(self.objects.startxref_offset() as i64),
+            ),
+        ]);
+        if let Some(info_id) = self.xref_metadata.info_id {
+            trailer.insert("Info", Object::IndirectReference(info_id));
+        }
+        if let Some(hash) = &self.xref_metadata.hash {
+            trailer.insert("ID", hash.to_object());
+        }
+
+        writer.finish(trailer).context(error::Write)?;
+
+        Ok(())
+    }
+
+    /// Objects in use according to the cross-reference table but unreachable from the trailer.
+    ///
+    /// Reachability starts at `/Root`, `/Info` and `/Encrypt` and follows
+    /// every reference. Object streams and cross-reference streams are
+    /// never reported, as they are referenced by the xref itself. Objects
+    /// that can't be loaded are skipped, whether they are referenced or not.
+    pub fn orphan_objects(&mut self) -> crate::Result<Vec<IndirectReference>> {
+        let orphans = self.read_orphan_objects().context(crate::error::Document)?;
+
+        Ok(orphans)
+    }
+
+    fn read_orphan_objects(&mut self) -> Result<Vec<IndirectReference>> {
+        let references = self.objects.in_use_references().context(error::Xref)?;
+
+        let mut reachable = BTreeSet::new();
+        let mut stack = [
+            Some(self.xref_metadata.root_id),
+            self.xref_metadata.info_id,
+            self.xref_metadata.encrypt_id,
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        while let Some(reference) = stack.pop() {
+            if !reachable.insert(reference) {
+                continue;
+            }
+
+            if let Ok(object) = self.objects.get_object(&reference) {
+                stack.extend(object.references());
+            }
+        }
+
+        let mut orphans = Vec::new();
+        for reference in references {
+            if reachable.contains(&reference) {
+                continue;
+            }
+
+            let object = match self.objects.get_object(&reference) {
+                Ok(object) => object,
+                Err(err) => {
+                    tracing::warn!("Skipping orphan candidate {reference}: {err}");
+                    continue;
+                }
+            };
+            let structural = object
+                .as_stream()
+                .ok()
+                .and_then(|stream| stream.dictionary.get_name("Type"))
+                .and_then(|name| name.ok())
+                .is_some_and(|name| name == "ObjStm" || name == "XRef");
+            if !structural {
+                orphans.push(reference);
+            }
+        }
+
+        Ok(orphans)
+    }
+
+    /// Names of every filter applied to a stream of the document.
+    ///
+    /// Tells upfront which decoders a document needs, including filters
+    /// this crate can't decode. Abbreviated names are reported in their
+    /// long form, e.g. `FlateDecode` for `/Fl`.
+    pub fn filters_used(&mut self) -> crate::Result<BTreeSet<String>> {
+        let filters = self.read_filters_used().context(crate::error::Document)?;
+
+        Ok(filters)
+    }
+
+    fn read_filters_used(&mut self) -> Result<BTreeSet<String>> {
+        let references = self.objects.in_use_references().context(error::Xref)?;
+
+        let mut filters = BTreeSet::new();
+        for reference in references {
+            let object = self
+                .objects
+                .get_object(&reference)
+                .context(error::Object { object: reference })?;
+            let Ok(stream) = object.as_stream() else {
+                continue;
+            };
+
+            let names = stream
+                .filter_names()
+                .context(error::StreamFilters { object: reference })?;
+            filters.extend(names);
+        }
+
+        Ok(filters)
+    }
+
+    /// Number of in-use objects in the merged cross-reference table.
+    ///
+    /// All sections reachable through `/Prev` are read first, so the
+    /// count reflects incremental updates as well.
+    pub fn object_count(&mut self) -> crate::Result<usize> {
+        let value = self
+            .objects
+            .object_count()
+            .context(error::Xref)
+            .map_err(|err| err.into())
+            .context(crate::error::Document)?;
+
+        Ok(value)
+    }
+
+    /// Number of free objects in the merged cross-reference table.
+    pub fn free_count(&mut self) -> crate::Result<usize> {
+        let value = self
+            .objects
+            .free_count()
+            .context(error::Xref)
+            .map_err(|err| err.into())
+            .context(crate::error::Document)?;
+
+        Ok(value)
+    }
+
+    /// Whether every object number up to `/Size - 1` has a cross-reference entry.
+    ///
+    /// A `false` result usually points at a truncated or hand-edited xref.
+    pub fn is_xref_complete(&mut self) -> crate::Result<bool> {
+        let value = self
+            .objects
+            .is_xref_complete()
+            .context(error::Xref)
+            .map_err(|err| err.into())
+            .context(crate::error::Document)?;
+
+        Ok(value)
+    }
+
+    /// Checks the document for structural inconsistencies.
+    ///
+    /// Verifies that the trailer `/Size` matches the highest object number,
+    /// that `/Root` is a `/Catalog`, that the page tree `/Count` matches
+    /// the number of reachable pages and that every `/Parent` points back
+    /// to the node listing it in `/Kids`.
+    ///
+    /// # Returns
+    /// Found issues, an empty list for a consistent document
+    pub fn validate(&mut self) -> crate::Result<Vec<ValidationIssue>> {
+        let issues = self
+            .read_validation_issues()
+            .context(crate::error::Document)?;
+
+        Ok(issues)
+    }
+
+    fn read_validation_issues(&mut self) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+
+        let size = self.objects.size().context(error::Xref)?;
+        if let Some(highest_object) = self.objects.highest_object().context(error::Xref)?
+            && size != highest_object + 1
+        {
+            issues.push(ValidationIssue::SizeMismatch {
+                size,
+                highest_object,
+            });
+        }
+
+        let sizes = self.objects.section_sizes().context(error::Xref)?;
+        if sizes.iter().any(|size| *size != sizes[0]) {
+            issues.push(ValidationIssue::SectionSizesDiffer { sizes });
+        }
+
+        let root_id = self.xref_metadata.root_id;
+        let root = self
+            .objects
+            .get_object(&root_id)
+            .context(error::Object { object: root_id })?;
+        let root_type = root
+            .as_dictionary()
+            .ok()
+            .and_then(|dictionary| dictionary.get("Type"))
+            .and_then(|object| object.as_name().ok());
+        if root_type.is_none_or(|name| name.as_str() != "Catalog") {
+            issues.push(ValidationIssue::RootNotCatalog {
+                found: root_type.map(|name| name.to_string()),
+            });
+        }
+
+        // Kids of the root can only be checked if the root is an indirect object.
+        let root_pages = root
+            .as_dictionary()
+            .ok()
+            .and_then(|dictionary| dictionary.get("Pages"))
+            .and_then(|object| object.as_indirect_ref().ok())
+            .copied();
+        // Direct nodes can't be pointed to, neither by a `/Parent` nor from one.
+        let count_position = issues.len();
+        let mut actual = 0;
+        let cycles = self.walk_page_tree(|node| {
+            if node.kids.is_none() {
+                actual += 1;
+            }
+
+            let expected = match node.depth {
+                1 => root_pages,
+                _ => node.listed_by,
+            };
+            if let Some(reference) = node.reference
+                && let Some(expected) = expected
+                && node.parent != Some(expected)
+            {
+                issues.push(ValidationIssue::ParentMismatch {
+                    node: reference,
+                    expected,
+                    found: node.parent,
+                });
+            }
+
+            ControlFlow::Continue(())
+        })?;
+
+        let declared = self.root.pages.leaf_count;
+        if declared != actual {
+            issues.insert(
+                count_position,
+                ValidationIssue::PageCountMismatch { declared, actual },
+            );
+        }
+        issues.extend(
+            cycles
+                .into_iter()
+                .map(|node| ValidationIssue::PageTreeCycle { node }),
+        );
+
+        Ok(issues)
+    }
+
+    /// Iterator over pages in a PDF document's page tree.
+    ///
+    /// The `Pages` struct provides an iterator that traverses the PDF page tree
+    /// structure, resolving indirect references and flattening the hierarchy
+    /// into a sequence of individual page objects.
+    ///
+    /// # Page Tree Structure
+    /// PDF documents organize pages in a tree structure where:
+    /// - The root node is a `/Pages` object
+    /// - Intermediate nodes are also `/Pages` objects (containing `/Kids`)
+    /// - Leaf nodes are `/Page` objects
+    /// - Attributes can be inherited from parent pages nodes
+    ///
+    /// This iterator performs a depth-first traversal of this tree structure.
+    ///
+    /// # Usage
+    /// ```
+    /// use std::path::PathBuf;
+    /// use docv_pdf::Document;
+    ///
+    /// let mut document = Document::from_path(&PathBuf::from("../example_files/report1.pdf")).unwrap();
+    /// for page in document.pages() {
+    ///     let page = page.unwrap();
+    ///
+    ///     // Process page...
+    /// }
+    /// ```
+    ///
+    /// # Note
+    /// This iterator consumes and mutates the `Objects` store to resolve
+    /// indirect references. It should not be used concurrently with other
+    /// operations that modify the same objects store.
+    pub fn pages<'a>(&'a mut self) -> Pages<'a> {
+        Pages::new(&self.root.pages, &mut self.objects)
+    }
+
+    /// First page of the document, for previews of large files.
+    ///
+    /// Only the leftmost branch of the page tree is loaded, down to its
+    /// first leaf, so the time doesn't depend on the number of pages.
+    /// `/Pages` nodes without kids are skipped like [`Document::pages`] does.
+    ///
+    /// # Errors
+    /// Fails if the page tree has no pages or the first page can't be read.
+    pub fn first_page(&mut self) -> crate::Result<Page> {
+        match self.pages().next() {
+            Some(page) => page,
+            None => Err(Error::from(error::Error::NoPages))
+                .context(crate::error::Document)
+                .map_err(Into::into),
+        }
+    }
+
+    /// Loads every page into an owned snapshot that can be shared between threads.
+    ///
+    /// [`Document::pages`] needs `&mut self` to load objects on demand, so
+    /// pages can't be read in parallel directly. Pages of the snapshot
+    /// don't refer back to the document.
+    pub fn snapshot(&mut self) -> crate::Result<DocumentSnapshot> {
+        let pages = self.pages().collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(DocumentSnapshot::new(pages))
+    }
+
+    /// Iterator over pages that skips pages which fail to parse.
+    ///
+    /// Errors are logged as warnings and iteration continues with the next
+    /// leaf of the page tree, so one malformed page doesn't hide the rest.
+    /// Use [`Document::pages`] to see the errors.
+    pub fn pages_lenient<'a>(&'a mut self) -> impl Iterator<Item = Page> + 'a {
+        self.pages()
+            .enumerate()
+            .filter_map(|(index, page)| match page {
+                Ok(page) => Some(page),
+                Err(err) => {
+                    tracing::warn!("Skipping page {}: {}", index + 1, err.full_message());
+                    None
+                }
+            })
+    }
+}
+
+/// Page tree node reached by [`Document::walk_page_tree`].
+struct PageTreeVisit {
+    /// `None` for a node written directly into `/Kids`
+    reference: Option<IndirectReference>,
+    /// 1 for kids of the root
+    depth: usize,
+    /// Number of kids, `None` for pages
+    kids: Option<usize>,
+    /// Node whose `/Kids` lists this one, `None` for kids of the root or of a direct node
+    listed_by: Option<IndirectReference>,
+    /// `/Parent` of the node, `None` if absent or not a reference
+    parent: Option<IndirectReference>,
+}
+
+/// Whether `object` is an object stream or a cross-reference stream, which rewrites rebuild.
+fn is_cross_reference_data(object: &Object) -> bool {
+    object.as_stream().is_ok_and(|stream| {
+        stream
+            .dictionary
+            .get("Type")
+            .and_then(|object| object.as_name().ok())
+            .is_some_and(|name| name.as_str() == "ObjStm" || name.as_str() == "XRef")
+    })
+}
+
+mod error {
+    use std::path::PathBuf;
+
+    use snafu::Snafu;
+
+    use crate::types::IndirectReference;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Failed to open file: {}", path.display()))]
+        OpenFile {
+            path: PathBuf,
+            source: std::io::Error,
+        },
+
+        #[snafu(display("Failed to get metadata"))]
+        Metadata { source: std::io::Error },
+
+        #[snafu(display("Page tree has no pages"))]
+        NoPages,
+
+        #[snafu(display("Failed to get objects"))]
+        Objects { source: crate::objects::Error },
+
+        #[snafu(display("Failed to read cross-reference sections"))]
+        Xref { source: crate::objects::Error },
+
+        #[snafu(display("Failed to get object {object}"))]
+        Object {
+            object: IndirectReference,
+            source: crate::objects::Error,
+        },
+
+        #[snafu(display("Invalid filters of stream {object}"))]
+        StreamFilters {
+            object: IndirectReference,
+            source: crate::types::stream::Error,
+        },
+
+        #[snafu(display("Failed to read root dictionary"))]
+        Root {
+            source: crate::structures::root::Error,
+        },
+
+        #[snafu(display("Encryption dictionary is not a dictionary"))]
+        InvalidEncrypt { source: crate::types::object::Error },
+
+        #[snafu(display("Failed to read encryption dictionary"))]
+        Encryption {
+            source: crate::structures::encryption::Error,
+        },
+
+        #[snafu(display("Failed to read optional content properties"))]
+        Layers {
+            source: crate::structures::root::optional_content::Error,
+        },
+
+        #[snafu(display("Failed to read name tree"))]
+        NameTree {
+            source: crate::structures::name_tree::Error,
+        },
+
+        #[snafu(display("Failed to read embedded file"))]
+        EmbeddedFile {
+            source: crate::structures::embedded_file::Error,
+        },
+
+        #[snafu(display("Metadata is not a stream"))]
+        InvalidMetadata { source: crate::types::object::Error },
+
+        #[snafu(display("Catalog `Lang` entry is not a string"))]
+        InvalidLanguage { source: crate::types::object::Error },
+
+        #[snafu(display("Failed to decode metadata stream"))]
+        DecodeMetadata { source: crate::types::stream::Error },
+
+        #[snafu(display("Invalid `MarkInfo` dictionary"))]
+        MarkInfo { source: crate::types::object::Error },
+
+        #[snafu(display("Failed to read viewer preferences"))]
+        ViewerPreferences {
+            source: crate::structures::root::viewer_preferences::Error,
+        },
+
+        #[snafu(display("Encrypted documents can't be rewritten or updated"))]
+        RewriteEncrypted,
+
+        #[snafu(display(
+            "Can't append an update to a file without cross-reference sections, rewrite it instead"
+        ))]
+        IncrementalWithoutXref,
+
+        #[snafu(display("Failed to write document"))]
+        Write { source: std::io::Error },
+
+        #[snafu(display("Failed to read interactive form"))]
+        AcroForm {
+            source: crate::structures::acroform::Error,
+        },
+
+        #[snafu(display("Failed to read signature fields"))]
+        Signatures {
+            source: crate::structures::signature::Error,
+        },
+
+        #[snafu(display("Failed to read image"))]
+        Image {
+            source: crate::structures::image::Error,
+        },
+
+        #[snafu(display("Failed to read destination"))]
+        Destination {
+            source: crate::structures::destination::Error,
+        },
+
+        #[snafu(display("Catalog `Dests` entry is not a dictionary"))]
+        InvalidDests { source: crate::types::object::Error },
+
+        #[snafu(display("Invalid `Kids` array in page tree"))]
+        InvalidKids {
+            source: crate::structures::root::pages_tree::Error,
+        },
+
+        #[snafu(display("Failed to read info dictionary"))]
+        Info {
+            source: crate::structures::info::Error,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use flate2::{Compression, write::ZlibEncoder};
+    use snafu::Whatever;
+
+    use super::*;
+    use crate::{
+        objects::Strictness,
+        structures::{
+            color_space::ColorSpace,
+            destination::{DestinationPage, DestinationView},
+            encryption::crypt::{CryptFilters, CryptMethod},
+            image::ImageMask,
+            info::Trap,
+            root::{
+                pages_tree::PagesTreeNode,
+                viewer_preferences::{Direction, NonFullScreenPageMode},
+            },
+        },
+        test_utils::{CATALOG, EMPTY_PAGES, PdfBuilder, minimal_document},
+        types::{ContentOp, DuplicateKeyPolicy, IndirectReference, Numeric},
+    };
+    use std::{fs, io::Write, path::PathBuf, sync::LazyLock};
+
+    static EXAMPLES: LazyLock<PathBuf> = LazyLock::new(|| {
+        let mut dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dir.pop();
+        dir.push("example_files");
+        dir
+    });
+
+    #[snafu::report]
+    #[test]
+    fn read_example_files() -> std::result::Result<(), Whatever> {
+        for example in
+            fs::read_dir(EXAMPLES.clone()).whatever_context("Failed to read directory")?
+        {
+            let entry = example.whatever_context("Failed to directory entry")?;
+            let path = entry.path();
+
+            let mut document = Document::from_path(&path)
+                .with_whatever_context(|_| format!("Failed to open file {}", path.display()))?;
+
+            let count = document.pages().count();
+
+            let result = document
+                .pages()
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .with_whatever_context(|_| {
+                    format!("Failed to iterate over pages for file {}", path.display())
+                })?;
+
+            assert_eq!(result.len(), count);
+        }
+        Ok(())
+    }
+
+    /// Stable textual summary of a document, compared against `snapshots/`.
+    fn snapshot(document: &mut Document) -> std::result::Result<String, Whatever> {
+        let mut snapshot = format!("version: {}\n", document.version());
+
+        let fields = [
+            ("title", document.title()),
+            ("author", document.author()),
+            ("subject", document.subject()),
+            ("keywords", document.keywords()),
+            ("creator", document.creator()),
+            ("producer", document.producer()),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                snapshot.push_str(&format!("{name}: {value:?}\n"));
+            }
+        }
+        if let Some(info) = document.info() {
+            if let Some(date) = &info.creation_date {
+                snapshot.push_str(&format!("creation_date: {date}\n"));
+            }
+            if let Some(date) = &info.mod_date {
+                snapshot.push_str(&format!("mod_date: {date}\n"));
+            }
+        }
+
+        let pages = document
+            .pages()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .whatever_context("Failed to iterate over pages")?;
+        snapshot.push_str(&format!("pages: {}\n", pages.len()));
+        for (index, page) in pages.iter().enumerate() {
+            let media_box = page.media_box();
+            snapshot.push_str(&format!(
+                "page {}: {} x {}\n",
+                index + 1,
+                media_box.width(),
+                media_box.height()
+            ));
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Path of the committed snapshot for `name`.
+    fn snapshot_path(name: &str) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("snapshots")
+            .join(name);
+        path.set_extension("txt");
+        path
+    }
+
+    fn updating_snapshots() -> bool {
+        std::env::var_os("DOCV_UPDATE_SNAPSHOTS").is_some()
+    }
+
+    /// Compares `actual` with the committed snapshot `snapshots/<name>.txt`.
+    ///
+    /// A missing or different snapshot fails the test. Set
+    /// `DOCV_UPDATE_SNAPSHOTS=1` to write snapshots after an intended
+    /// change in parsing.
+    fn check_snapshot(name: &str, actual: &str) -> std::result::Result<(), Whatever> {
+        let path = snapshot_path(name);
+
+        if updating_snapshots() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .whatever_context("Failed to create snapshot directory")?;
+            }
+            return fs::write(&path, actual)
+                .with_whatever_context(|_| format!("Failed to write snapshot {}", path.display()));
+        }
+
+        let expected = fs::read_to_string(&path).with_whatever_context(|_| {
+            format!(
+                "Missing snapshot {}, run with DOCV_UPDATE_SNAPSHOTS=1 to create it",
+                path.display()
+            )
+        })?;
+        assert_eq!(
+            actual, expected,
+            "Snapshot {name} changed, rerun with DOCV_UPDATE_SNAPSHOTS=1 if intended"
+        );
+        Ok(())
+    }
+
+    /// Compares every example file with its committed snapshot.
+    ///
+    /// Example files without a snapshot are skipped and reported, since the
+    /// examples are not part of the repository.
+    #[snafu::report]
+    #[test]
+    fn snapshot_example_files() -> std::result::Result<(), Whatever> {
+        let mut missing = Vec::new();
+
+        for example in
+            fs::read_dir(EXAMPLES.clone()).whatever_context("Failed to read directory")?
+        {
+            let entry = example.whatever_context("Failed to directory entry")?;
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+
+            if !updating_snapshots() && !snapshot_path(&name).exists() {
+                missing.push(name);
+                continue;
+            }
+
+            let mut document = Document::from_path(&path)
+                .with_whatever_context(|_| format!("Failed to open file {}", path.display()))?;
+            let actual = snapshot(&mut document)
+                .with_whatever_context(|_| format!("Failed to snapshot {}", path.display()))?;
+
+            check_snapshot(&name, &actual)?;
+        }
+
+        if !missing.is_empty() {
+            eprintln!(
+                "Skipped example files without a snapshot: {}. \
+                 Run with DOCV_UPDATE_SNAPSHOTS=1 to create them.",
+                missing.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    #[snafu::report]
+    #[test]
+    fn snapshot_fixture() -> std::result::Result<(), Whatever> {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(
+                2,
+                "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 /MediaBox [0 0 612 792] >>",
+            )
+            .object(3, "<< /Type /Page /Parent 2 0 R /Resources << >> >>")
+            .object(
+                4,
+                "<< /Type /Page /Parent 2 0 R /Resources << >> /MediaBox [0 0 595 842] >>",
+            )
+            .object(
+                5,
+                "<< /Title (Snapshot fixture) /Author <FEFF004A006F007300E9> \
+                 /Producer (docv) /CreationDate (D:20240102030405Z) \
+                 /ModDate (D:20240203040506+02'00) >>",
+            )
+            .section("/Root 1 0 R /Info 5 0 R")
+            .write("snapshot-fixture");
+
+        let mut document = Document::from_path(&path)
+            .with_whatever_context(|_| format!("Failed to open file {}", path.display()))?;
+        let actual = snapshot(&mut document)?;
+
+        check_snapshot("fixture", &actual)
+    }
+
+    #[test]
+    fn test_object_counts() {
+        struct TestCase {
+            name: &'static str,
+            file: PdfBuilder,
+            expected_objects: usize,
+            expected_free: usize,
+            expected_complete: bool,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "single section",
+                file: minimal_document()
+                    .object(3, "(unused)")
+                    .free(4)
+                    .section("/Root 1 0 R"),
+                expected_objects: 3,
+                expected_free: 2,
+                expected_complete: true,
+            },
+            TestCase {
+                name: "incremental update frees and adds objects",
+                file: minimal_document()
+                    .object(3, "(unused)")
+                    .section("/Root 1 0 R")
+                    .free(3)
+                    .object(4, "(added)")
+                    .section("/Root 1 0 R"),
+                expected_objects: 3,
+                expected_free: 2,
+                expected_complete: true,
+            },
+            TestCase {
+                name: "gap in object numbers",
+                file: minimal_document()
+                    .object(5, "(orphan)")
+                    .section("/Root 1 0 R"),
+                expected_objects: 3,
+                expected_free: 1,
+                expected_complete: false,
+            },
+        ];
+
+        for case in test_cases {
+            let path = case.file.write("object-counts");
+            let mut document = Document::from_path(&path).unwrap();
+
+            assert_eq!(
+                document.object_count().unwrap(),
+                case.expected_objects,
+                "Test '{}' failed: wrong object count",
+                case.name
+            );
+            assert_eq!(
+                document.free_count().unwrap(),
+                case.expected_free,
+                "Test '{}' failed: wrong free count",
+                case.name
+            );
+            assert_eq!(
+                document.is_xref_complete().unwrap(),
+                case.expected_complete,
+                "Test '{}' failed: wrong completeness",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_xref_metadata() {
+        struct TestCase {
+            name: &'static str,
+            file: PdfBuilder,
+            expected_kind: XrefKind,
+            expected_size: usize,
+            expected_prev: bool,
+            expected_info: Option<IndirectReference>,
+            expected_encrypt: Option<IndirectReference>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "single table",
+                file: minimal_document()
+                    .object(3, "<< /Title (Test) >>")
+                    .section("/Root 1 0 R /Info 3 0 R"),
+                expected_kind: XrefKind::Table,
+                expected_size: 4,
+                expected_prev: false,
+                expected_info: Some(IndirectReference { id: 3, gen_id: 0 }),
+                expected_encrypt: None,
+            },
+            TestCase {
+                name: "incremental update",
+                file: minimal_document()
+                    .section("/Root 1 0 R")
+                    .object(3, "<< /Filter /Standard >>")
+                    .section("/Root 1 0 R /Encrypt 3 0 R"),
+                expected_kind: XrefKind::Table,
+                expected_size: 4,
+                expected_prev: true,
+                expected_info: None,
+                expected_encrypt: Some(IndirectReference { id: 3, gen_id: 0 }),
+            },
+            TestCase {
+                name: "xref stream",
+                file: minimal_document().stream_section(3, "/Root 1 0 R"),
+                expected_kind: XrefKind::Stream,
+                expected_size: 4,
+                expected_prev: false,
+                expected_info: None,
+                expected_encrypt: None,
+            },
+        ];
+
+        for case in test_cases {
+            let path = case.file.write("xref-metadata");
+            let document = Document::from_path(&path).unwrap();
+            let metadata = document.xref_metadata();
+
+            assert_eq!(
+                metadata.kind, case.expected_kind,
+                "Test '{}' failed: wrong kind",
+                case.name
+            );
+            assert_eq!(
+                metadata.size, case.expected_size,
+                "Test '{}' failed: wrong size",
+                case.name
+            );
+            assert_eq!(
+                metadata.prev.is_some(),
+                case.expected_prev,
+                "Test '{}' failed: wrong prev",
+                case.name
+            );
+            assert_eq!(
+                metadata.root_id,
+                IndirectReference { id: 1, gen_id: 0 },
+                "Test '{}' failed: wrong root",
+                case.name
+            );
+            assert_eq!(
+                metadata.info_id, case.expected_info,
+                "Test '{}' failed: wrong info",
+                case.name
+            );
+            assert_eq!(
+                metadata.encrypt_id, case.expected_encrypt,
+                "Test '{}' failed: wrong encrypt",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_catalog_in_object_stream() {
+        let path = PdfBuilder::new()
+            .object_stream(
+                3,
+                &[
+                    (1, CATALOG),
+                    (
+                        2,
+                        "<< /Type /Pages /Kids [4 0 R 5 0 R] /Count 2 /MediaBox [0 0 10 10] >>",
+                    ),
+                    (4, "<< /Type /Page /Parent 2 0 R /Resources << >> >>"),
+                ],
+            )
+            .object(5, "<< /Type /Page /Parent 2 0 R /Resources << >> >>")
+            .stream_section(6, "/Root 1 0 R")
+            .write("compressed-catalog");
+
+        let mut document = Document::from_path(&path).unwrap();
+        let pages = document
+            .pages()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(document.object_count().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_document_limits() {
+        struct TestCase {
+            name: &'static str,
+            file: PdfBuilder,
+            limits: DocumentLimits,
+            expected_error: Option<&'static str>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "no limits",
+                file: minimal_document().section("/Root 1 0 R"),
+                limits: DocumentLimits::default(),
+                expected_error: None,
+            },
+            TestCase {
+                name: "object count within limit",
+                file: minimal_document().section("/Root 1 0 R"),
+                limits: DocumentLimits {
+                    max_objects: Some(3),
+                    ..Default::default()
+                },
+                expected_error: None,
+            },
+            TestCase {
+                name: "too many objects",
+                file: minimal_document().section("/Root 1 0 R"),
+                limits: DocumentLimits {
+                    max_objects: Some(2),
+                    ..Default::default()
+                },
+                expected_error: Some("ObjectLimit"),
+            },
+            TestCase {
+                name: "too many xref sections",
+                file: minimal_document()
+                    .section("/Root 1 0 R")
+                    .object(3, "(update)")
+                    .section("/Root 1 0 R"),
+                limits: DocumentLimits {
+                    max_xref_sections: Some(1),
+                    ..Default::default()
+                },
+                expected_error: Some("XrefChainTooLong"),
+            },
+            TestCase {
+                name: "too much decompressed data",
+                file: minimal_document().stream_section(3, "/Root 1 0 R"),
+                limits: DocumentLimits {
+                    max_total_decompressed: Some(16),
+                    ..Default::default()
+                },
+                expected_error: Some("DecompressedLimit"),
+            },
+        ];
+
+        for case in test_cases {
+            let path = case.file.write("limits");
+            let result = Document::from_path_with_limits(&path, case.limits);
+
+            match case.expected_error {
+                None => assert!(
+                    result.is_ok(),
+                    "Test '{}' failed: unexpected error {:?}",
+                    case.name,
+                    result.err()
+                ),
+                Some(expected) => {
+                    let error = format!("{:?}", result.expect_err(case.name));
+                    assert!(
+                        error.contains(expected),
+                        "Test '{}' failed: expected {expected}, got {error}",
+                        case.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let path = PdfBuilder::new()
+            .object(1, "<< /Type /Catalog /Pages 2 0 R /Pages 2 0 R >>")
+            .object(2, EMPTY_PAGES)
+            .section("/Root 1 0 R")
+            .write("parse-options");
+
+        let test_cases = [
+            ("duplicate keys kept", DuplicateKeyPolicy::KeepLast, true),
+            ("duplicate keys rejected", DuplicateKeyPolicy::Error, false),
+        ];
+
+        for (name, duplicate_keys, expected) in test_cases {
+            let options = ParseOptions {
+                duplicate_keys,
+                ..Default::default()
+            };
+            let result =
+                Document::from_path_with_options(&path, DocumentLimits::default(), options);
+
+            assert_eq!(result.is_ok(), expected, "Test '{name}' failed");
+            if let Err(error) = result {
+                let error = format!("{error:?}");
+                assert!(error.contains("Root"), "Test '{name}' failed: got {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_content_stream_limit() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&[b' '; 64 * 1024]).unwrap();
+        let contents = encoder.finish().unwrap();
+
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /Resources << >> /MediaBox [0 0 612 792] \
+                 /Contents 4 0 R >>",
+            )
+            .stream(4, "/Filter /FlateDecode", &contents)
+            .section("/Root 1 0 R")
+            .write("content-stream-limit");
+
+        let test_cases = [
+            ("within limit", 64 * 1024, true),
+            ("over limit", 4096, false),
+        ];
+
+        for (name, limit, expected) in test_cases {
+            let limits = DocumentLimits {
+                max_total_decompressed: Some(limit),
+                ..Default::default()
+            };
+            let mut document = Document::from_path_with_limits(&path, limits).unwrap();
+            let page = document.first_page().unwrap();
+            let result = page.content_bytes();
+
+            assert_eq!(result.is_ok(), expected, "Test '{name}' failed: {result:?}");
+            if let Err(error) = result {
+                let error = format!("{error:?}");
+                assert!(
+                    error.contains("DecompressedLimit"),
+                    "Test '{name}' failed: got {error}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_content_malformed_numbers() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /Resources << >> /MediaBox [0 0 612 792] \
+                 /Contents 4 0 R >>",
+            )
+            .stream(4, "", b"--10 1.5.0 m")
+            .section("/Root 1 0 R")
+            .write("content-malformed-numbers");
+
+        let expected = vec![ContentOp::new(
+            "m",
+            vec![
+                Object::Numeric(Numeric::Integer(-10)),
+                Object::Numeric(Numeric::Real(1.5)),
+            ],
+        )];
+        let test_cases = [
+            ("lenient", Strictness::Lenient, Some(expected)),
+            ("strict", Strictness::Strict, None),
+        ];
+
+        for (name, strictness, expected) in test_cases {
+            let options = ParseOptions {
+                strictness,
+                ..Default::default()
+            };
+            let mut document =
+                Document::from_path_with_options(&path, DocumentLimits::default(), options)
+                    .unwrap();
+            let page = document.first_page().unwrap();
+            let result = page.content_ops();
+
+            assert_eq!(result.ok(), expected, "Test '{name}' failed");
+        }
+    }
+
+    #[test]
+    fn test_load_progress() {
+        let path = minimal_document()
+            .object(3, "<< /Title (Progress) >>")
+            .section("/Root 1 0 R /Info 3 0 R")
+            .write("load-progress");
+
+        let mut reported = Vec::new();
+        let document =
+            Document::from_path_with_progress(&path, |progress| reported.push(progress)).unwrap();
+
+        let expected = [
+            (LoadPhase::Header, 0),
+            (LoadPhase::Xref, 0),
+            (LoadPhase::Trailer, 2),
+            (LoadPhase::PageIndex, 3),
+        ]
+        .map(|(phase, objects_resolved)| LoadProgress {
+            phase,
+            objects_resolved,
+        });
+        assert_eq!(reported, expected);
+        assert_eq!(document.title().as_deref(), Some("Progress"));
+
+        let path = minimal_document()
+            .section("/Root 5 0 R")
+            .write("load-progress-missing-root");
+        let mut phases = Vec::new();
+        let result =
+            Document::from_path_with_progress(&path, |progress| phases.push(progress.phase));
+        assert!(result.is_err());
+        assert_eq!(phases, [LoadPhase::Header, LoadPhase::Xref]);
+    }
+
+    #[test]
+    fn test_encryption_info() {
+        const ID: &str =
+            "/ID [<0123456789abcdef0123456789abcdef> <0123456789abcdef0123456789abcdef>]";
+        const OWNER: &str = "<000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f>";
+
+        struct TestCase {
+            name: &'static str,
+            encrypt: String,
+            expected: Option<EncryptionInfo>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "not encrypted",
+                encrypt: String::new(),
+                expected: None,
+            },
+            TestCase {
+                name: "RC4 128 without user password",
+                encrypt: format!(
+                    "<< /Filter /Standard /V 2 /R 3 /Length 128 /P -1028 /O {OWNER} /U <52a8c5201ee8d2366e06203c9567139bb99c0c84f62fa20bb346ac9e39262e1f> >>"
+                ),
+                expected: Some(EncryptionInfo {
+                    filter: "Standard".to_string(),
+                    v: 2,
+                    r: 3,
+                    key_length: 128,
+                    has_user_password: false,
+                    permissions: -1028,
+                }),
+            },
+            TestCase {
+                name: "RC4 128 with user password",
+                encrypt: format!(
+                    "<< /Filter /Standard /V 2 /R 3 /Length 128 /P -1028 /O {OWNER} /U <e8a1bddec3c21242a8400e1c72c8ca654659e25363b4b1692d8ae7f3e913eab4> >>"
+                ),
+                expected: Some(EncryptionInfo {
+                    filter: "Standard".to_string(),
+                    v: 2,
+                    r: 3,
+                    key_length: 128,
+                    has_user_password: true,
+                    permissions: -1028,
+                }),
+            },
+            TestCase {
+                name: "RC4 128 with binary literal strings",
+                encrypt: format!(
+                    "<< /Filter /Standard /V 2 /R 3 /Length 128 /P -1028 /O {} /U {} >>",
+                    r"(\000\001\002\003\004\005\006\007\010\011\012\013\014\015\016\017\020\021\022\023\024\025\026\027\030\031\032\033\034\035\036\037)",
+                    r"(\122\250\305\040\036\350\322\066\156\006\040\074\225\147\023\233\271\234\014\204\366\057\242\013\263\106\254\236\071\046\056\037)"
+                ),
+                expected: Some(EncryptionInfo {
+                    filter: "Standard".to_string(),
+                    v: 2,
+                    r: 3,
+                    key_length: 128,
+                    has_user_password: false,
+                    permissions: -1028,
+                }),
+            },
+            TestCase {
+                name: "AES-256 without user password",
+                encrypt: format!(
+                    "<< /Filter /Standard /V 5 /R 6 /Length 256 /P -4 /O {OWNER} /U <1403c04eb647d2e60452dfc4eb0a5e0cf322e8a83a759eabbd17d498a93ba04100010203040506070000000000000000> /CF << /StdCF << /CFM /AESV3 /Length 32 >> >> >>"
+                ),
+                expected: Some(EncryptionInfo {
+                    filter: "Standard".to_string(),
+                    v: 5,
+                    r: 6,
+                    key_length: 256,
+                    has_user_password: false,
+                    permissions: -4,
+                }),
+            },
+            TestCase {
+                name: "AES-256 with user password",
+                encrypt: format!(
+                    "<< /Filter /Standard /V 5 /R 6 /Length 256 /P -4 /O {OWNER} /U <952a028e406d92accedad37501d7f8ffe7e3d9582c35336d4e434b580de7de7600010203040506070000000000000000> /CF << /StdCF << /CFM /AESV3 /Length 32 >> >> >>"
+                ),
+                expected: Some(EncryptionInfo {
+                    filter: "Standard".to_string(),
+                    v: 5,
+                    r: 6,
+                    key_length: 256,
+                    has_user_password: true,
+                    permissions: -4,
+                }),
+            },
+            TestCase {
+                name: "custom security handler",
+                encrypt: "<< /Filter /Vendor /V 4 /CF << /StdCF << /Length 16 >> >> >>".to_string(),
+                expected: Some(EncryptionInfo {
+                    filter: "Vendor".to_string(),
+                    v: 4,
+                    r: 0,
+                    key_length: 128,
+                    has_user_password: false,
+                    permissions: 0,
+                }),
+            },
+            TestCase {
+                name: "crypt filter named by StmF",
+                encrypt: "<< /Filter /Vendor /V 4 /CF << /StdCF << /Length 16 >> /Vendor40 << /Length 5 >> >> /StmF /Vendor40 >>"
+                    .to_string(),
+                expected: Some(EncryptionInfo {
+                    filter: "Vendor".to_string(),
+                    v: 4,
+                    r: 0,
+                    key_length: 40,
+                    has_user_password: false,
+                    permissions: 0,
+                }),
+            },
+        ];
+
+        for case in test_cases {
+            let file = if case.encrypt.is_empty() {
+                minimal_document().section(&format!("/Root 1 0 R {ID}"))
+            } else {
+                minimal_document()
+                    .object(3, &case.encrypt)
+                    .section(&format!("/Root 1 0 R /Encrypt 3 0 R {ID}"))
+            };
+            let path = file.write("encryption");
+            let mut document = Document::from_path(&path).unwrap();
+
+            assert_eq!(
+                document.encryption_info().unwrap(),
+                case.expected,
+                "Test '{}' failed",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_identity_crypt_filter() {
+        let path = minimal_document()
+            .object(
+                3,
+                "<< /Filter /Standard /V 4 /R 4 /Length 128 /P -4 \
+                 /CF << /StdCF << /CFM /AESV2 /Length 16 >> >> /StmF /StdCF /StrF /StdCF \
+                 /O <000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f> \
+                 /U <000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f> >>",
+            )
+            .stream(
+                4,
+                "/Type /Metadata /Subtype /XML /Filter /Crypt /DecodeParms << /Name /Identity >>",
+                b"<x:xmpmeta/>",
+            )
+            .section(
+                "/Root 1 0 R /Encrypt 3 0 R \
+                 /ID [<0123456789abcdef0123456789abcdef> <0123456789abcdef0123456789abcdef>]",
+            )
+            .write("identity-crypt");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert!(document.encryption_info().unwrap().is_some());
+
+        let encrypt = document
+            .objects_mut()
+            .get_object(&IndirectReference { id: 3, gen_id: 0 })
+            .unwrap();
+        let filters = CryptFilters::from_dictionary(encrypt.as_dictionary().unwrap()).unwrap();
+
+        let reference = IndirectReference { id: 4, gen_id: 0 };
+        let object = document.objects_mut().get_object(&reference).unwrap();
+        let mut stream = object.as_stream().unwrap().clone();
+
+        assert_eq!(stream.crypt_filter().unwrap().as_deref(), Some("Identity"));
+        assert_eq!(filters.stream_method(&stream), CryptMethod::Identity);
+
+        filters
+            .decrypt_stream(&[0; 16], reference, &mut stream)
+            .unwrap();
+        stream.process_filters().unwrap();
+        assert_eq!(stream.data, b"<x:xmpmeta/>");
+    }
+
+    #[test]
+    fn test_info_shortcuts() {
+        let path = minimal_document()
+            .object(
+                3,
+                "<< /Title (Quarterly report) /Author <FEFF004A006F007300E9> \
+                 /Subject <8456> /Keywords (pdf, test) /Creator (Writer) >>",
+            )
+            .section("/Root 1 0 R /Info 3 0 R")
+            .write("info-shortcuts");
+        let document = Document::from_path(&path).unwrap();
+
+        assert_eq!(document.title().as_deref(), Some("Quarterly report"));
+        assert_eq!(document.author().as_deref(), Some("José"));
+        assert_eq!(document.subject().as_deref(), Some("\u{2014}V"));
+        assert_eq!(document.keywords().as_deref(), Some("pdf, test"));
+        assert_eq!(document.creator().as_deref(), Some("Writer"));
+        assert_eq!(document.producer(), None);
+
+        let path = minimal_document()
+            .section("/Root 1 0 R")
+            .write("info-missing");
+        let document = Document::from_path(&path).unwrap();
+
+        assert_eq!(document.title(), None);
+        assert_eq!(document.author(), None);
+    }
+
+    #[test]
+    fn test_info_binary_literals() {
+        let mut info = b"<< /Producer (Caf\xE9 Writer) /Title (\xFE\xFF\x00J\x00o\x00s\x00\xE9) \
+                         /Company (M\xFCller) /Trapped (\xFE\xFF\x00T\x00r\x00u\x00e) \
+                         /CreationDate (\xFE\xFF"
+            .to_vec();
+        for byte in b"D:20240102030405Z" {
+            info.extend([0, *byte]);
+        }
+        info.extend(b") >>");
+
+        let path = minimal_document()
+            .object_bytes(3, &info)
+            .section("/Root 1 0 R /Info 3 0 R")
+            .write("info-binary-literals");
+        let document = Document::from_path(&path).unwrap();
+        let info = document.info().unwrap();
+
+        assert_eq!(document.producer().as_deref(), Some("Café Writer"));
+        assert_eq!(document.title().as_deref(), Some("José"));
+        assert_eq!(
+            info.other,
+            vec![("Company".to_string(), "Müller".to_string())]
+        );
+        assert!(matches!(info.trapped, Trap::True));
+        assert_eq!(
+            info.creation_date.as_ref().unwrap().to_string(),
+            "2024-01-02 03:04:05 +00:00"
+        );
+    }
+
+    #[test]
+    fn test_layers() {
+        let path = PdfBuilder::new()
+            .object(
+                1,
+                "<< /Type /Catalog /Pages 2 0 R /OCProperties << /OCGs [3 0 R 4 0 R 5 0 R] \
+                 /D << /BaseState /OFF /ON [3 0 R] /OFF [5 0 R] >> >> >>",
+            )
+            .object(2, EMPTY_PAGES)
+            .object(3, "<< /Type /OCG /Name (Background) >>")
+            .object(4, "<< /Type /OCG /Name <FEFF004E006F007400650073> >>")
+            .object(5, "<< /Type /OCG /Name (Watermark) >>")
+            .section("/Root 1 0 R")
+            .write("layers");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert_eq!(
+            document.layers().unwrap(),
+            vec![
+                Layer {
+                    name: "Background".to_string(),
+                    visible_default: true,
+                    id: IndirectReference { id: 3, gen_id: 0 },
+                },
+                Layer {
+                    name: "Notes".to_string(),
+                    visible_default: false,
+                    id: IndirectReference { id: 4, gen_id: 0 },
+                },
+                Layer {
+                    name: "Watermark".to_string(),
+                    visible_default: false,
+                    id: IndirectReference { id: 5, gen_id: 0 },
+                },
+            ]
+        );
+
+        let path = minimal_document().section("/Root 1 0 R").write("no-layers");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert_eq!(document.layers().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_pages_lenient() {
+        let page = |media_box: &str, contents: usize| {
+            format!(
+                "<< /Type /Page /Parent 2 0 R /Resources << >> /MediaBox {media_box} \
+                 /Contents {contents} 0 R >>"
+            )
+        };
+
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R 5 0 R] /Count 3 >>")
+            .object(3, &page("[0 0 612 792]", 6))
+            .object(4, &page("[0 0 612]", 6))
+            .object(5, &page("[0 0 300 400]", 7))
+            .stream(6, "", b"(first) Tj")
+            .stream(7, "", b"(third) Tj")
+            .section("/Root 1 0 R")
+            .write("pages-lenient");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert!(
+            document
+                .pages()
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .is_err()
+        );
+
+        let contents = document
+            .pages_lenient()
+            .map(|page| page.content_bytes().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            contents,
+            vec![b"(first) Tj".to_vec(), b"(third) Tj".to_vec()]
+        );
+
+        // Node 4 lists itself and page 3 again, each node is read once.
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 1 >>")
+            .object(3, &page("[0 0 612 792]", 5))
+            .object(
+                4,
+                "<< /Type /Pages /Parent 2 0 R /Kids [4 0 R 3 0 R] /Count 0 >>",
+            )
+            .stream(5, "", b"(only) Tj")
+            .section("/Root 1 0 R")
+            .write("pages-cyclic");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let contents = document
+            .pages_lenient()
+            .map(|page| page.content_bytes().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(contents, vec![b"(only) Tj".to_vec()]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let page = "<< /Type /Page /Parent 2 0 R /Resources << >> /MediaBox [0 0 10 10] >>";
+
+        struct TestCase {
+            name: &'static str,
+            file: PdfBuilder,
+            expected: Vec<ValidationIssue>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "consistent document",
+                file: PdfBuilder::new()
+                    .object(1, CATALOG)
+                    .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                    .object(3, page)
+                    .section("/Root 1 0 R"),
+                expected: vec![],
+            },
+            TestCase {
+                name: "size too small",
+                file: minimal_document()
+                    .object(3, "(unused)")
+                    .section("/Root 1 0 R /Size 3"),
+                expected: vec![ValidationIssue::SizeMismatch {
+                    size: 3,
+                    highest_object: 3,
+                }],
+            },
+            TestCase {
+                name: "size too large",
+                file: minimal_document().section("/Root 1 0 R /Size 10"),
+                expected: vec![ValidationIssue::SizeMismatch {
+                    size: 10,
+                    highest_object: 2,
+                }],
+            },
+            TestCase {
+                name: "root is not a catalog",
+                file: PdfBuilder::new()
+                    .object(1, "<< /Type /Outlines /Pages 2 0 R >>")
+                    .object(2, EMPTY_PAGES)
+                    .section("/Root 1 0 R"),
+                expected: vec![ValidationIssue::RootNotCatalog {
+                    found: Some("Outlines".to_string()),
+                }],
+            },
+            TestCase {
+                name: "root without type",
+                file: PdfBuilder::new()
+                    .object(1, "<< /Pages 2 0 R >>")
+                    .object(2, EMPTY_PAGES)
+                    .section("/Root 1 0 R"),
+                expected: vec![ValidationIssue::RootNotCatalog { found: None }],
+            },
+            TestCase {
+                name: "page count mismatch",
+                file: PdfBuilder::new()
+                    .object(1, CATALOG)
+                    .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 4 >>")
+                    .object(3, page)
+                    .section("/Root 1 0 R"),
+                expected: vec![ValidationIssue::PageCountMismatch {
+                    declared: 4,
+                    actual: 1,
+                }],
+            },
+            TestCase {
+                name: "page with wrong parent",
+                file: PdfBuilder::new()
+                    .object(1, CATALOG)
+                    .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>")
+                    .object(3, page)
+                    .object(
+                        4,
+                        "<< /Type /Page /Parent 1 0 R /Resources << >> /MediaBox [0 0 10 10] >>",
+                    )
+                    .section("/Root 1 0 R"),
+                expected: vec![ValidationIssue::ParentMismatch {
+                    node: IndirectReference { id: 4, gen_id: 0 },
+                    expected: IndirectReference { id: 2, gen_id: 0 },
+                    found: Some(IndirectReference { id: 1, gen_id: 0 }),
+                }],
+            },
+            TestCase {
+                name: "intermediate node without parent",
+                file: PdfBuilder::new()
+                    .object(1, CATALOG)
+                    .object(2, "<< /Type /Pages /Kids [4 0 R] /Count 1 >>")
+                    .object(3, page)
+                    .object(4, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                    .section("/Root 1 0 R"),
+                expected: vec![
+                    ValidationIssue::ParentMismatch {
+                        node: IndirectReference { id: 4, gen_id: 0 },
+                        expected: IndirectReference { id: 2, gen_id: 0 },
+                        found: None,
+                    },
+                    ValidationIssue::ParentMismatch {
+                        node: IndirectReference { id: 3, gen_id: 0 },
+                        expected: IndirectReference { id: 4, gen_id: 0 },
+                        found: Some(IndirectReference { id: 2, gen_id: 0 }),
+                    },
+                ],
+            },
+            TestCase {
+                name: "node listing itself in kids",
+                file: PdfBuilder::new()
+                    .object(1, CATALOG)
+                    .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                    .object(3, "<< /Type /Pages /Parent 2 0 R /Kids [3 0 R] /Count 1 >>")
+                    .section("/Root 1 0 R"),
+                expected: vec![
+                    ValidationIssue::PageCountMismatch {
+                        declared: 1,
+                        actual: 0,
+                    },
+                    ValidationIssue::PageTreeCycle {
+                        node: IndirectReference { id: 3, gen_id: 0 },
+                    },
+                ],
+            },
+        ];
+
+        for case in test_cases {
+            let path = case.file.write("validate");
+            let mut document = Document::from_path(&path).unwrap();
+
+            assert_eq!(
+                document.validate().unwrap(),
+                case.expected,
+                "Test '{}' failed: wrong issues",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_pages_tree_node_parent() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 0 >>")
+            .object(3, "<< /Type /Pages /Parent 2 0 R /Kids [5 0 R] /Count 0 >>")
+            .object(4, "<< /Type /Pages /Parent 2 0 R /Kids [6 0 R] /Count 0 >>")
+            .object(5, "<< /Type /Pages /Parent 3 0 R /Kids [] /Count 0 >>")
+            .object(6, "<< /Type /Pages /Parent 4 0 R /Kids [] /Count 0 >>")
+            .section("/Root 1 0 R")
+            .write("pages-tree-parent");
+        let mut document = Document::from_path(&path).unwrap();
+        assert_eq!(document.root.pages.parent, None);
+
+        let mut nodes = vec![(IndirectReference { id: 2, gen_id: 0 }, None)];
+        while let Some((reference, expected_parent)) = nodes.pop() {
+            let object = document.objects.get_object(&reference).unwrap();
+            let node = PagesTreeNode::from_dictionary(
+                object.as_dictionary().unwrap(),
+                None,
+                Strictness::Strict,
+            )
+            .unwrap();
+            assert_eq!(node.parent, expected_parent, "wrong parent of {reference}");
+
+            nodes.extend(node.kids.iter().map(|kid| match kid {
+                PageTreeKid::Reference(kid) => (*kid, Some(reference)),
+                PageTreeKid::Inline(_) => panic!("unexpected direct kid of {reference}"),
+            }));
+        }
+
+        assert_eq!(document.validate().unwrap(), vec![]);
+
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Parent (root) /Kids [] /Count 0 >>")
+            .section("/Root 1 0 R")
+            .write("pages-tree-parent-invalid");
+        assert!(Document::from_path(&path).is_err());
+    }
+
+    #[test]
+    fn test_inline_kids() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(
+                2,
+                "<< /Type /Pages /MediaBox [0 0 612 792] /Resources << >> /Count 3 \
+                 /Kids [3 0 R << /Type /Page /MediaBox [0 0 200 100] >> 4 0 R] >>",
+            )
+            .object(3, "<< /Type /Page /Parent 2 0 R >>")
+            .object(4, "<< /Type /Page /Parent 2 0 R >>")
+            .section("/Root 1 0 R")
+            .write("inline-kids");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let widths = document
+            .pages()
+            .map(|page| page.unwrap().media_box().width())
+            .collect::<Vec<_>>();
+        assert_eq!(widths, [612.0, 200.0, 612.0]);
+
+        assert_eq!(
+            document
+                .page_index(IndirectReference { id: 4, gen_id: 0 })
+                .unwrap(),
+            Some(2)
+        );
+        assert_eq!(document.page_tree_stats().unwrap().leaf_count, 3);
+        assert_eq!(document.validate().unwrap(), vec![]);
+
+        let options = ParseOptions {
+            strictness: Strictness::Strict,
+            ..Default::default()
+        };
+        assert!(
+            Document::from_path_with_options(&path, DocumentLimits::default(), options).is_err()
+        );
+
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 7] /Count 1 >>")
+            .object(3, "<< /Type /Page /Parent 2 0 R >>")
+            .section("/Root 1 0 R")
+            .write("inline-kids-invalid");
+        assert!(Document::from_path(&path).is_err());
+
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(3, "(not a page)")
+            .section("/Root 1 0 R")
+            .write("kid-invalid-type");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let error = document.pages().next().unwrap().unwrap_err();
+        let message = error.full_message();
+        assert!(
+            message.contains("Invalid object type for field `Kids`. Used ref: `3 0 R`"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_error_full_message() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /Resources /Broken /MediaBox [0 0 10 10] >>",
+            )
+            .section("/Root 1 0 R")
+            .write("error-full-message");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let error = document.pages().next().unwrap().unwrap_err();
+        let message = error.full_message();
+
+        assert!(message.starts_with(&error.to_string()), "{message}");
+        assert!(
+            message.contains("Invalid object type for field `Resources`"),
+            "{message}"
+        );
+        assert!(
+            message.ends_with("Unexpected object type. Expected = Dictionary. Got = Name(Name { data: \"Broken\" })"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_embedded_files() {
+        let path = PdfBuilder::new()
+            .object(
+                1,
+                "<< /Type /Catalog /Pages 2 0 R /Names << /EmbeddedFiles 3 0 R >> >>",
+            )
+            .object(2, EMPTY_PAGES)
+            .object(3, "<< /Kids [4 0 R] >>")
+            .object(4, "<< /Names [(a) 5 0 R (b) 7 0 R] >>")
+            .object(
+                5,
+                "<< /Type /Filespec /F (resume.txt) \
+                 /UF <FEFF007200E900730075006D00E9002E007400780074> \
+                 /Desc (Latest version) /EF << /F 6 0 R >> >>",
+            )
+            .stream(
+                6,
+                "/Type /EmbeddedFile /Params << /Size 5 /CreationDate (D:20240102030405Z) >>",
+                b"hello",
+            )
+            .object(7, "<< /Type /Filespec /F (data.bin) >>")
+            .section("/Root 1 0 R")
+            .write("embedded-files");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let files = document.embedded_files().unwrap();
+        assert_eq!(files.len(), 2);
+
+        assert_eq!(files[0].key, "a");
+        assert_eq!(files[0].filename, "résumé.txt");
+        assert_eq!(files[0].description.as_deref(), Some("Latest version"));
+        assert_eq!(files[0].size, Some(5));
+        assert_eq!(
+            files[0].creation_date.map(|date| date.to_string()),
+            Some("2024-01-02 03:04:05 +00:00".to_string())
+        );
+        assert_eq!(
+            files[0].stream,
+            Some(IndirectReference { id: 6, gen_id: 0 })
+        );
+
+        assert_eq!(files[1].key, "b");
+        assert_eq!(files[1].filename, "data.bin");
+        assert_eq!(files[1].description, None);
+        assert_eq!(files[1].size, None);
+        assert_eq!(files[1].stream, None);
+
+        let path = PdfBuilder::new()
+            .object(
+                1,
+                "<< /Type /Catalog /Pages 2 0 R /Names << /EmbeddedFiles << /Names [(a) 3 0 R] >> >> >>",
+            )
+            .object(2, EMPTY_PAGES)
+            .object(3, "<< /Type /Filespec /F 42 >>")
+            .section("/Root 1 0 R")
+            .write("embedded-file-invalid-name");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let message = document.embedded_files().unwrap_err().full_message();
+        assert!(
+            message.contains("Invalid object type for field `F`"),
+            "{message}"
+        );
+
+        let path = minimal_document()
+            .section("/Root 1 0 R")
+            .write("no-embedded-files");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert!(document.embedded_files().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_signatures() {
+        let path = PdfBuilder::new()
+            .object(
+                1,
+                "<< /Type /Catalog /Pages 2 0 R \
+                 /AcroForm << /Fields [3 0 R 6 0 R] /SigFlags 3 >> /Perms << /DocMDP 4 0 R >> >>",
+            )
+            .object(2, EMPTY_PAGES)
+            .object(3, "<< /T (Approval) /Kids [5 0 R] >>")
+            .object(
+                4,
+                "<< /Type /Sig /Filter /Adobe.PPKLite /SubFilter /adbe.pkcs7.detached \
+                 /Name (Jane Doe) /ByteRange [0 100 200 50] /Contents <3082AB00> >>",
+            )
+            .object(5, "<< /T (Signer) /FT /Sig /V 4 0 R /Kids [7 0 R] >>")
+            .object(6, "<< /T (Name) /FT /Tx /V (text) >>")
+            .object(7, "<< /Type /Annot /Subtype /Widget /Parent 5 0 R >>")
+            .section("/Root 1 0 R")
+            .write("signatures");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert!(document.has_perms());
+
+        let fields = document.signatures().unwrap();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].name, "Approval.Signer");
+
+        let signature = fields[0].signature.as_ref().unwrap();
+        assert!(!signature.byte_range.is_empty());
+        assert_eq!(signature.byte_range, vec![0..100, 200..250]);
+        assert_eq!(signature.contents, vec![0x30, 0x82, 0xAB, 0x00]);
+        assert_eq!(signature.sub_filter.as_deref(), Some("adbe.pkcs7.detached"));
+        assert_eq!(signature.signer_name.as_deref(), Some("Jane Doe"));
+
+        let path = minimal_document()
+            .section("/Root 1 0 R")
+            .write("no-signatures");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert!(!document.has_perms());
+        assert!(document.signatures().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_acroform_info() {
+        let path = PdfBuilder::new()
+            .object(
+                1,
+                "<< /Type /Catalog /Pages 2 0 R /AcroForm << /Fields 3 0 R /NeedAppearances true \
+                 /SigFlags 3 /DR 4 0 R /DA (/Helv 0 Tf 0 g) >> >>",
+            )
+            .object(2, EMPTY_PAGES)
+            .object(3, "[5 0 R 6 0 R]")
+            .object(4, "<< /Font << /Helv 7 0 R >> >>")
+            .object(5, "<< /T (Name) /FT /Tx /Kids [8 0 R 9 0 R] >>")
+            .object(6, "<< /T (Signature) /FT /Sig >>")
+            .object(7, "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>")
+            .section("/Root 1 0 R")
+            .write("acroform-info");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let info = document.acroform_info().unwrap().unwrap();
+        assert!(info.need_appearances);
+        assert_eq!(info.fields_count, 2);
+        assert_eq!(
+            info.sig_flags,
+            AcroFormInfo::SIGNATURES_EXIST | AcroFormInfo::APPEND_ONLY
+        );
+        let resources = info.default_resources.unwrap();
+        assert_eq!(
+            resources
+                .get_dictionary("Font")
+                .unwrap()
+                .unwrap()
+                .get("Helv"),
+            Some(&Object::IndirectReference(IndirectReference {
+                id: 7,
+                gen_id: 0
+            }))
+        );
+
+        let path = PdfBuilder::new()
+            .object(
+                1,
+                "<< /Type /Catalog /Pages 2 0 R /AcroForm << /Fields [] >> >>",
+            )
+            .object(2, EMPTY_PAGES)
+            .section("/Root 1 0 R")
+            .write("acroform-info-defaults");
+        let mut document = Document::from_path(&path).unwrap();
+        assert_eq!(
+            document.acroform_info().unwrap(),
+            Some(AcroFormInfo::default())
+        );
+
+        let path = minimal_document()
+            .section("/Root 1 0 R")
+            .write("acroform-info-missing");
+        let mut document = Document::from_path(&path).unwrap();
+        assert_eq!(document.acroform_info().unwrap(), None);
+    }
+
+    #[test]
+    fn test_pages_from_previous_revision() {
+        let first_revision = || {
+            PdfBuilder::new()
+                .object(1, CATALOG)
+                .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                .object(
+                    3,
+                    "<< /Type /Page /Parent 2 0 R /Resources << >> /MediaBox [0 0 10 10] >>",
+                )
+                .section("/Root 1 0 R")
+        };
+
+        struct TestCase {
+            name: &'static str,
+            file: PdfBuilder,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "new catalog pointing at old page tree",
+                file: first_revision()
+                    .object(4, "<< /Type /Catalog /Pages 2 0 R /Lang (en) >>")
+                    .section("/Root 4 0 R"),
+            },
+            TestCase {
+                name: "update trailer without /Root",
+                file: first_revision()
+                    .object(4, "<< /Producer (update) >>")
+                    .section("/Info 4 0 R"),
+            },
+            TestCase {
+                name: "update stream without /Root",
+                file: first_revision()
+                    .object(4, "<< /Producer (update) >>")
+                    .stream_section(5, ""),
+            },
+        ];
+
+        for case in test_cases {
+            let path = case.file.write("previous-revision");
+            let mut document = Document::from_path(&path)
+                .unwrap_or_else(|err| panic!("Test '{}' failed: {err:?}", case.name));
+
+            let pages = document
+                .pages()
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .unwrap_or_else(|err| panic!("Test '{}' failed: {err:?}", case.name));
+            assert_eq!(
+                pages.len(),
+                1,
+                "Test '{}' failed: wrong page count",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_rewrite_uncompressed() {
+        let path = PdfBuilder::new()
+            .object_stream(
+                3,
+                &[
+                    (1, CATALOG),
+                    (
+                        2,
+                        "<< /Type /Pages /Kids [4 0 R] /Count 1 /MediaBox [0 0 10 10] >>",
+                    ),
+                    (
+                        4,
+                        "<< /Type /Page /Parent 2 0 R /Resources << >> /Contents 5 0 R >>",
+                    ),
+                ],
+            )
+            .stream(5, "", b"0 0 m 10 10 l S")
+            .object(7, "<< /Title (Rewritten) >>")
+            .stream_section(6, "/Root 1 0 R /Info 7 0 R")
+            .write("rewrite-original");
+        let mut original = Document::from_path(&path).unwrap();
+
+        let mut output = Vec::new();
+        original.rewrite_uncompressed(&mut output).unwrap();
+        let path = crate::test_utils::write_temp("rewrite-uncompressed", &output);
+        let mut rewritten = Document::from_path(&path).unwrap();
+
+        assert_eq!(rewritten.xref_metadata().kind, XrefKind::Table);
+        assert_eq!(rewritten.title().as_deref(), Some("Rewritten"));
+
+        let expected = original
+            .objects
+            .in_use_references()
+            .unwrap()
+            .into_iter()
+            .filter(|reference| reference.id != 3 && reference.id != 6)
+            .collect::<Vec<_>>();
+        let references = rewritten.objects.in_use_references().unwrap();
+        assert_eq!(references, expected);
+
+        let inner = |object: Object| match object {
+            Object::IndirectDefinition(definition) => (*definition).clone(),
+            object => object,
+        };
+        for reference in references {
+            let before = inner(original.objects.get_object(&reference).unwrap());
+            let after = inner(rewritten.objects.get_object(&reference).unwrap());
+            assert_eq!(after, before, "object {reference} changed");
+        }
+
+        let pages = rewritten
+            .pages()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(pages[0].content_bytes().unwrap(), b"0 0 m 10 10 l S");
+    }
+
+    #[test]
+    fn test_save_optimized() {
+        let mut file = PdfBuilder::with_header("%PDF-1.4\n")
+            .object(1, CATALOG)
+            .object(
+                2,
+                "<< /Type /Pages /Kids [3 0 R] /Count 1 /MediaBox [0 0 10 10] >>",
+            )
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /Resources << >> /Contents 4 0 R >>",
+            )
+            .stream(4, "", b"0 0 m 10 10 l S")
+            .object(5, "<< /Title (Optimized) /Producer (docv) >>");
+        for id in 6..40 {
+            file = file.object(id, "<< /Type /Annot /Subtype /Text /Rect [0 0 1 1] >>");
+        }
+        let original_bytes = file.section("/Root 1 0 R /Info 5 0 R").build();
+        let path = crate::test_utils::write_temp("optimize-original", &original_bytes);
+        let mut original = Document::from_path(&path).unwrap();
+
+        let mut output = Vec::new();
+        original.save_optimized(&mut output).unwrap();
+        assert!(
+            output.len() < original_bytes.len(),
+            "optimized file has {} bytes, original {}",
+            output.len(),
+            original_bytes.len()
+        );
+
+        let path = crate::test_utils::write_temp("optimize-output", &output);
+        let mut optimized = Document::from_path(&path).unwrap();
+        assert_eq!(optimized.xref_metadata().kind, XrefKind::Stream);
+        assert_eq!(optimized.version(), &Version::Pdf1_5);
+        assert_eq!(optimized.title().as_deref(), Some("Optimized"));
+
+        let expected = original.objects.in_use_references().unwrap();
+        let references = optimized
+            .objects
+            .in_use_references()
+            .unwrap()
+            .into_iter()
+            .filter(|reference| {
+                let object = optimized.objects.get_object(reference).unwrap();
+                !is_cross_reference_data(&object)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(references, expected);
+
+        let inner = |object: Object| match object {
+            Object::IndirectDefinition(definition) => (*definition).clone(),
+            object => object,
+        };
+        for reference in references {
+            let before = inner(original.objects.get_object(&reference).unwrap());
+            let after = inner(optimized.objects.get_object(&reference).unwrap());
+            assert_eq!(after, before, "object {reference} changed");
+        }
+
+        let pages = optimized
+            .pages()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(pages[0].content_bytes().unwrap(), b"0 0 m 10 10 l S");
+    }
+
+    #[test]
+    fn test_viewer_preferences() {
+        let path = PdfBuilder::new()
+            .object(
+                1,
+                "<< /Type /Catalog /Pages 2 0 R /ViewerPreferences 3 0 R >>",
+            )
+            .object(2, EMPTY_PAGES)
+            .object(
+                3,
+                "<< /HideToolbar true /DisplayDocTitle true /FitWindow false \
+                 /NonFullScreenPageMode /UseOutlines /Direction /R2L >>",
+            )
+            .section("/Root 1 0 R")
+            .write("viewer-preferences");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert_eq!(
+            document.viewer_preferences().unwrap(),
+            Some(ViewerPreferences {
+                hide_toolbar: true,
+                display_doc_title: true,
+                non_full_screen_page_mode: NonFullScreenPageMode::UseOutlines,
+                direction: Direction::R2L,
+                ..Default::default()
+            })
+        );
+
+        let path = minimal_document()
+            .section("/Root 1 0 R")
+            .write("no-viewer-preferences");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert_eq!(document.viewer_preferences().unwrap(), None);
+    }
+
+    #[test]
+    fn test_is_tagged() {
+        struct TestCase {
+            name: &'static str,
+            catalog: &'static str,
+            expected: bool,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "tagged",
+                catalog: "<< /Type /Catalog /Pages 2 0 R /MarkInfo << /Marked true >> \
+                          /StructTreeRoot 3 0 R >>",
+                expected: true,
+            },
+            TestCase {
+                name: "not marked",
+                catalog: CATALOG,
+                expected: false,
+            },
+            TestCase {
+                name: "marked false",
+                catalog: "<< /Type /Catalog /Pages 2 0 R /MarkInfo << /Marked false >> \
+                          /StructTreeRoot 3 0 R >>",
+                expected: false,
+            },
+            TestCase {
+                name: "marked without structure tree",
+                catalog: "<< /Type /Catalog /Pages 2 0 R /MarkInfo << /Marked true >> >>",
+                expected: false,
+            },
+        ];
+
+        for case in &test_cases {
+            let path = PdfBuilder::new()
+                .object(1, case.catalog)
+                .object(2, EMPTY_PAGES)
+                .object(3, "<< /Type /StructTreeRoot /K [] >>")
+                .section("/Root 1 0 R")
+                .write("tagged");
+            let mut document = Document::from_path(&path).unwrap();
+
+            assert_eq!(
+                document.is_tagged().unwrap(),
+                case.expected,
+                "Test '{}' failed: wrong tagged state",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_xref_chain_limits() {
+        let file = minimal_document();
+        let offset = file.offset();
+        let path = file
+            .section(&format!("/Root 1 0 R /Prev {offset}"))
+            .write("cyclic-prev");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let error = format!("{:?}", document.object_count().unwrap_err());
+        assert!(error.contains("CyclicXrefChain"), "got {error}");
+
+        let mut file = minimal_document().section("/Root 1 0 R");
+        for id in 3..8 {
+            file = file.object(id, "(update)").section("/Root 1 0 R");
+        }
+        let path = file.write("long-prev");
+
+        let limits = DocumentLimits {
+            max_xref_sections: Some(3),
+            ..Default::default()
+        };
+        // The catalog is only reachable through the oldest section.
+        let error = format!(
+            "{:?}",
+            Document::from_path_with_limits(&path, limits).unwrap_err()
+        );
+        assert!(error.contains("XrefChainTooLong"), "got {error}");
+
+        let mut document = Document::from_path(&path).unwrap();
+        assert_eq!(document.object_count().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_revision_offsets() {
+        let file = minimal_document();
+        let first = file.offset();
+        let file = file
+            .section("/Root 1 0 R")
+            .object(3, "<< /Producer (update) >>");
+        let second = file.offset();
+        let path = file.section("/Root 1 0 R /Info 3 0 R").write("revisions");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert_eq!(document.startxref_offset(), second as u64);
+        assert_eq!(
+            document.revision_offsets().unwrap(),
+            vec![second as u64, first as u64]
+        );
+
+        let trailer = document.raw_trailer_bytes();
+        assert!(trailer.starts_with(b"trailer"));
+        assert!(trailer.ends_with(b">>"));
+        assert!(
+            trailer
+                .windows(b"/Info 3 0 R".len())
+                .any(|window| window == b"/Info 3 0 R")
+        );
+    }
+
+    #[test]
+    fn test_startxref_layouts() {
+        let file = minimal_document();
+        let xref = file.offset();
+        let data = file.section("/Root 1 0 R").build();
+        let keyword = data
+            .windows(b"startxref\n".len())
+            .rposition(|window| window == b"startxref\n")
+            .unwrap();
+
+        let same_line = [&data[..keyword], b"startxref ", &data[keyword + 10..]].concat();
+        for (name, data) in [("next-line", data.clone()), ("same-line", same_line)] {
+            let path = crate::test_utils::write_temp(&format!("startxref-{name}"), &data);
+            let mut document = Document::from_path(&path).unwrap();
+            assert_eq!(
+                document.startxref_offset(),
+                xref as u64,
+                "Test '{name}' failed: wrong offset"
+            );
+            assert_eq!(document.pages().count(), 0, "Test '{name}' failed");
+
+            let options = ParseOptions {
+                strictness: Strictness::Strict,
+                ..Default::default()
+            };
+            let strict =
+                Document::from_path_with_options(&path, DocumentLimits::default(), options);
+            assert_eq!(
+                strict.is_ok(),
+                name == "next-line",
+                "Test '{name}' failed: wrong result in strict mode"
+            );
+        }
+    }
+
+    #[test]
+    fn test_open_action() {
+        struct TestCase {
+            name: &'static str,
+            open_action: &'static str,
+            expected: Option<Destination>,
+        }
+
+        let third_page = IndirectReference { id: 6, gen_id: 0 };
+        let test_cases = [
+            TestCase {
+                name: "explicit destination",
+                open_action: "[6 0 R /XYZ 0 792 null]",
+                expected: Some(Destination {
+                    page: DestinationPage::Reference(third_page),
+                    view: DestinationView::Xyz {
+                        left: Some(0.0),
+                        top: Some(792.0),
+                        zoom: None,
+                    },
+                }),
+            },
+            TestCase {
+                name: "goto action",
+                open_action: "<< /S /GoTo /D [6 0 R /FitH 700] >>",
+                expected: Some(Destination {
+                    page: DestinationPage::Reference(third_page),
+                    view: DestinationView::FitH { top: Some(700.0) },
+                }),
+            },
+            TestCase {
+                name: "goto action with named destination",
+                open_action: "<< /S /GoTo /D (chapter) >>",
+                expected: Some(Destination {
+                    page: DestinationPage::Reference(third_page),
+                    view: DestinationView::Fit,
+                }),
+            },
+            TestCase {
+                name: "javascript action",
+                open_action: "<< /S /JavaScript /JS (app.alert\\(1\\)) >>",
+                expected: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let catalog = format!(
+                "<< /Type /Catalog /Pages 2 0 R /OpenAction {} \
+                 /Names << /Dests << /Names [(chapter) << /D [6 0 R /Fit] >>] >> >> >>",
+                case.open_action
+            );
+            let path = PdfBuilder::new()
+                .object(1, &catalog)
+                .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 3 >>")
+                .object(3, "<< /Type /Page /Parent 2 0 R >>")
+                .object(
+                    4,
+                    "<< /Type /Pages /Parent 2 0 R /Kids [5 0 R 6 0 R] /Count 2 >>",
+                )
+                .object(5, "<< /Type /Page /Parent 4 0 R >>")
+                .object(6, "<< /Type /Page /Parent 4 0 R >>")
+                .section("/Root 1 0 R")
+                .write("open-action");
+            let mut document = Document::from_path(&path).unwrap();
+
+            let destination = document.open_action().unwrap();
+            assert_eq!(
+                destination, case.expected,
+                "Test '{}' failed: wrong destination",
+                case.name
+            );
+
+            if let Some(Destination {
+                page: DestinationPage::Reference(page),
+                ..
+            }) = destination
+            {
+                assert_eq!(
+                    document.page_index(page).unwrap(),
+                    Some(2),
+                    "Test '{}' failed: wrong page index",
+                    case.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_legacy_dests() {
+        struct TestCase {
+            name: &'static str,
+            catalog: &'static str,
+            lookup: &'static str,
+            expected: Option<DestinationView>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "legacy array",
+                catalog: "/Dests 4 0 R",
+                lookup: "intro",
+                expected: Some(DestinationView::Fit),
+            },
+            TestCase {
+                name: "legacy dictionary with D",
+                catalog: "/Dests 4 0 R",
+                lookup: "summary",
+                expected: Some(DestinationView::FitH { top: Some(500.0) }),
+            },
+            TestCase {
+                name: "missing name",
+                catalog: "/Dests 4 0 R",
+                lookup: "appendix",
+                expected: None,
+            },
+            TestCase {
+                name: "name tree preferred",
+                catalog: "/Dests 4 0 R /Names << /Dests << /Names [(intro) [3 0 R /FitB]] >> >>",
+                lookup: "intro",
+                expected: Some(DestinationView::FitB),
+            },
+            TestCase {
+                name: "legacy fallback next to name tree",
+                catalog: "/Dests 4 0 R /Names << /Dests << /Names [(intro) [3 0 R /FitB]] >> >>",
+                lookup: "summary",
+                expected: Some(DestinationView::FitH { top: Some(500.0) }),
+            },
+        ];
+
+        for case in &test_cases {
+            let catalog = format!("<< /Type /Catalog /Pages 2 0 R {} >>", case.catalog);
+            let path = PdfBuilder::new()
+                .object(1, &catalog)
+                .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                .object(3, "<< /Type /Page /Parent 2 0 R >>")
+                .object(
+                    4,
+                    "<< /intro [3 0 R /Fit] /summary << /D [3 0 R /FitH 500] >> >>",
+                )
+                .section("/Root 1 0 R")
+                .write("legacy-dests");
+            let mut document = Document::from_path(&path).unwrap();
+
+            let destination = document.named_destination(case.lookup).unwrap();
+            let expected = case.expected.map(|view| Destination {
+                page: DestinationPage::Reference(IndirectReference { id: 3, gen_id: 0 }),
+                view,
+            });
+            assert_eq!(destination, expected, "Test '{}' failed", case.name);
+        }
+    }
+
+    #[test]
+    fn test_images() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 /MediaBox [0 0 612 792] >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /Resources << /XObject << /Im1 5 0 R /Fm1 6 0 R >> >> >>",
+            )
+            .object(
+                4,
+                "<< /Type /Page /Parent 2 0 R /Resources << /XObject << /Logo 5 0 R >> >> >>",
+            )
+            .stream(
+                5,
+                "/Type /XObject /Subtype /Image /Width 2 /Height 1 \
+                 /ColorSpace [/Indexed /DeviceRGB 1 <000000FFFFFF>] /BitsPerComponent 8",
+                &[0, 1],
+            )
+            .stream(6, "/Type /XObject /Subtype /Form /BBox [0 0 1 1]", b"")
+            .section("/Root 1 0 R")
+            .write("images");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let images = document.images().unwrap();
+        assert_eq!(images.len(), 1);
+
+        let image = &images[0];
+        assert_eq!(image.reference, IndirectReference { id: 5, gen_id: 0 });
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.color_space.as_deref(), Some("Indexed"));
+        assert_eq!(
+            image.resolved_color_space,
+            Some(ColorSpace::Indexed {
+                base: Box::new(ColorSpace::DeviceRGB),
+                hival: 1,
+                lookup: vec![0x00, 0x00, 0x00, 0xff, 0xff, 0xff],
+            })
+        );
+        assert_eq!(image.bits_per_component, Some(8));
+        assert!(image.filters.is_empty());
+        assert_eq!(image.decoded_data().unwrap(), vec![0, 1]);
+        assert_eq!(image.format().unwrap(), None);
+    }
+
+    #[test]
+    fn test_image_masks() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(
+                2,
+                "<< /Type /Pages /Kids [3 0 R] /Count 1 /MediaBox [0 0 612 792] >>",
+            )
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R \
+                 /Resources << /XObject << /Im1 4 0 R /Im2 5 0 R /Im3 6 0 R >> >> >>",
+            )
+            .stream(
+                4,
+                "/Type /XObject /Subtype /Image /Width 2 /Height 1 \
+                 /ColorSpace /DeviceGray /BitsPerComponent 8 /SMask 7 0 R",
+                &[0, 255],
+            )
+            .stream(
+                5,
+                "/Type /XObject /Subtype /Image /Width 2 /Height 1 \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8 /Mask [0 10 250 255 0 0]",
+                &[0, 0, 0, 255, 255, 255],
+            )
+            .stream(
+                6,
+                "/Type /XObject /Subtype /Image /Width 2 /Height 1 \
+                 /ColorSpace /DeviceGray /BitsPerComponent 8 /Mask 8 0 R",
+                &[0, 255],
+            )
+            .stream(
+                7,
+                "/Type /XObject /Subtype /Image /Width 4 /Height 2 \
+                 /ColorSpace /DeviceGray /BitsPerComponent 8",
+                &[0, 64, 128, 255, 255, 128, 64, 0],
+            )
+            .stream(
+                8,
+                "/Type /XObject /Subtype /Image /Width 2 /Height 1 \
+                 /ImageMask true /BitsPerComponent 1",
+                &[0b0100_0000],
+            )
+            .section("/Root 1 0 R")
+            .write("image_masks");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let images = document.images().unwrap();
+        let objects = document.objects_mut();
+
+        let soft_mask = images[0].soft_mask(objects).unwrap().unwrap();
+        assert_eq!((soft_mask.width, soft_mask.height), (4, 2));
+        assert_eq!(soft_mask.color_space, Some(ColorSpace::DeviceGray));
+        assert_eq!(soft_mask.data, [0, 64, 128, 255, 255, 128, 64, 0]);
+        assert_eq!(images[0].image_mask(objects).unwrap(), None);
+
+        assert_eq!(
+            images[1].image_mask(objects).unwrap(),
+            Some(ImageMask::ColorKey(vec![(0, 10), (250, 255), (0, 0)]))
+        );
+        assert_eq!(images[1].soft_mask(objects).unwrap(), None);
+
+        let Some(ImageMask::Stencil(stencil)) = images[2].image_mask(objects).unwrap() else {
+            panic!("expected a stencil mask");
+        };
+        assert_eq!((stencil.width, stencil.height), (2, 1));
+        assert_eq!(stencil.color_space, None);
+        assert_eq!(stencil.data, [0b0100_0000]);
+    }
+
+    #[test]
+    fn test_pages_skip_missing() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(
+                2,
+                "<< /Type /Pages /Kids [3 0 R 9 0 R 4 0 R] /Count 3 \
+                 /MediaBox [0 0 612 792] /Resources << >> >>",
+            )
+            .object(3, "<< /Type /Page /Parent 2 0 R >>")
+            .object(4, "<< /Type /Page /Parent 2 0 R /Rotate 90 >>")
+            .section("/Root 1 0 R")
+            .write("pages_skip_missing");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert!(document.pages().any(|page| page.is_err()));
+
+        let mut pages = document.pages().skip_missing();
+        let loaded = pages.by_ref().collect::<crate::Result<Vec<_>>>().unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].rotate(), 90);
+        assert_eq!(pages.missing(), [IndirectReference { id: 9, gen_id: 0 }]);
+    }
+
+    #[test]
+    fn test_save_incremental() {
+        let path = minimal_document()
+            .object(3, "<< /Title (Original) >>")
+            .section("/Root 1 0 R /Info 3 0 R")
+            .write("incremental-original");
+        let original = fs::read(&path).unwrap();
+        let mut document = Document::from_path(&path).unwrap();
+
+        let objects = document.objects_mut();
+        let info = IndirectReference { id: 3, gen_id: 0 };
+        let mut dictionary = objects
+            .get_object(&info)
+            .unwrap()
+            .as_dictionary()
+            .unwrap()
+            .clone();
+        dictionary.insert("Title", Object::string("Edited"));
+        objects.stage(info, Object::Dictionary(dictionary));
+        let added = objects.stage_new(Object::string("added")).unwrap();
+        assert_eq!(added, IndirectReference { id: 4, gen_id: 0 });
+
+        let mut output = Vec::new();
+        document.save_incremental(&mut output).unwrap();
+        assert!(output.starts_with(&original));
+
+        let path = crate::test_utils::write_temp("incremental-updated", &output);
+        let mut updated = Document::from_path(&path).unwrap();
+        assert_eq!(updated.title().as_deref(), Some("Edited"));
+        assert_eq!(updated.object_count().unwrap(), 4);
+        assert_eq!(updated.objects.revision_offsets().unwrap().len(), 2);
+        let Object::IndirectDefinition(object) = updated.objects.get_object(&added).unwrap() else {
+            panic!("object {added} isn't an indirect definition");
+        };
+        assert_eq!(object.as_bytes().unwrap(), b"added");
+
+        // Offsets count from the header, not from the start of the file.
+        let data = minimal_document()
+            .object(3, "<< /Title (Original) >>")
+            .section("/Root 1 0 R /Info 3 0 R")
+            .build();
+        let data = [b"leading garbage\n".as_slice(), &data].concat();
+        let path = crate::test_utils::write_temp("incremental-leading-garbage", &data);
+        let mut document = Document::from_path(&path).unwrap();
+
+        let objects = document.objects_mut();
+        objects.stage(
+            info,
+            Object::Dictionary(Dictionary::from([("Title", Object::string("Edited"))])),
+        );
+        let mut output = Vec::new();
+        document.save_incremental(&mut output).unwrap();
+
+        let path = crate::test_utils::write_temp("incremental-leading-garbage-updated", &output);
+        let options = ParseOptions {
+            strictness: Strictness::Strict,
+            ..Default::default()
+        };
+        let mut updated =
+            Document::from_path_with_options(&path, DocumentLimits::default(), options).unwrap();
+        assert_eq!(updated.title().as_deref(), Some("Edited"));
+        assert_eq!(updated.objects.revision_offsets().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_orphan_objects() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] /Resources << >> /Contents 4 0 R >>",
+            )
+            .stream(4, "", b"0 0 m 10 10 l S")
+            .object(5, "<< /Title (Orphans) >>")
+            .object(6, "<< /Unused true /Next 7 0 R >>")
+            .object(7, "[6 0 R]")
+            .object_stream(8, &[(9, "(compressed orphan)")])
+            .object_raw(11, b"not an object\n")
+            .stream_section(10, "/Root 1 0 R /Info 5 0 R")
+            .write("orphan-objects");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let orphans = document.orphan_objects().unwrap();
+        assert_eq!(
+            orphans,
+            [6, 7, 9].map(|id| IndirectReference { id, gen_id: 0 })
+        );
+    }
+
+    #[test]
+    fn test_filters_used() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] \
+                 /Resources << /XObject << /Im1 5 0 R >> >> /Contents 4 0 R >>",
+            )
+            .stream(4, "/Filter /FlateDecode", b"not inflated")
+            .stream(
+                5,
+                "/Type /XObject /Subtype /Image /Width 1 /Height 1 \
+                 /ColorSpace /DeviceGray /BitsPerComponent 8 /Filter /DCTDecode",
+                b"not a jpeg",
+            )
+            .stream(6, "/Filter [/A85 /FlateDecode]", b"")
+            .stream(7, "/Filter null", b"")
+            .stream(8, "", b"")
+            .section("/Root 1 0 R")
+            .write("filters-used");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let filters = document.filters_used().unwrap();
+        assert_eq!(
+            filters.iter().map(String::as_str).collect::<Vec<_>>(),
+            ["ASCII85Decode", "DCTDecode", "FlateDecode"]
+        );
+    }
+
+    #[test]
+    fn test_first_page() {
+        let page =
+            |size: u32| format!("<< /Type /Page /MediaBox [0 0 {size} {size}] /Resources << >> >>");
+
+        let test_cases = [
+            (
+                "flat",
+                PdfBuilder::new()
+                    .object(1, CATALOG)
+                    .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>")
+                    .object(3, &page(10))
+                    .object(4, &page(20)),
+            ),
+            (
+                "nested",
+                PdfBuilder::new()
+                    .object(1, CATALOG)
+                    .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>")
+                    .object(3, "<< /Type /Pages /Kids [5 0 R] /Count 1 >>")
+                    .object(5, "<< /Type /Pages /Kids [6 0 R] /Count 1 >>")
+                    .object(6, &page(10))
+                    .object(4, &page(20)),
+            ),
+            (
+                "empty first branch",
+                PdfBuilder::new()
+                    .object(1, CATALOG)
+                    .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 1 >>")
+                    .object(3, "<< /Type /Pages /Kids [] /Count 0 >>")
+                    .object(4, &page(10)),
+            ),
+        ];
+
+        for (name, file) in test_cases {
+            let path = file.section("/Root 1 0 R").write("first-page");
+            let mut document = Document::from_path(&path).unwrap();
+
+            let first = document.first_page().unwrap();
+            let pages = document.pages().collect::<crate::Result<Vec<_>>>().unwrap();
+            assert_eq!(
+                first.media_box(),
+                pages[0].media_box(),
+                "Test '{name}' failed"
+            );
+            assert_eq!(
+                first.to_string(),
+                pages[0].to_string(),
+                "Test '{name}' failed"
+            );
+        }
+
+        // The rest of the tree isn't loaded, so a broken later branch doesn't matter.
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 9 0 R] /Count 2 >>")
+            .object(3, &page(10))
+            .section("/Root 1 0 R")
+            .write("first-page-broken-tail");
+        let mut document = Document::from_path(&path).unwrap();
+        assert!(document.first_page().is_ok());
+        assert!(document.pages().any(|page| page.is_err()));
+
+        let path = minimal_document()
+            .section("/Root 1 0 R")
+            .write("first-page-empty");
+        let mut document = Document::from_path(&path).unwrap();
+        let message = document.first_page().unwrap_err().full_message();
+        assert!(message.contains("Page tree has no pages"), "{message}");
+    }
+
+    #[test]
+    fn test_page_tree_stats() {
+        let page = "<< /Type /Page /MediaBox [0 0 10 10] /Resources << >> >>";
+
+        let flat = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R 5 0 R] /Count 3 >>")
+            .object(3, page)
+            .object(4, page)
+            .object(5, page)
+            .section("/Root 1 0 R")
+            .write("page-tree-flat");
+        let nested = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 6 0 R] /Count 3 >>")
+            .object(3, "<< /Type /Pages /Kids [4 0 R 7 0 R] /Count 2 >>")
+            .object(7, "<< /Type /Pages /Kids [5 0 R] /Count 1 >>")
+            .object(4, page)
+            .object(5, page)
+            .object(6, page)
+            .section("/Root 1 0 R")
+            .write("page-tree-nested");
+
+        let test_cases = [
+            (
+                "flat",
+                flat,
+                PageTreeStats {
+                    max_depth: 1,
+                    node_count: 1,
+                    leaf_count: 3,
+                    max_kids: 3,
+                },
+            ),
+            (
+                "nested",
+                nested,
+                PageTreeStats {
+                    max_depth: 3,
+                    node_count: 3,
+                    leaf_count: 3,
+                    max_kids: 2,
+                },
+            ),
+        ];
+
+        for (name, path, expected) in test_cases {
+            let mut document = Document::from_path(&path).unwrap();
+            let stats = document.page_tree_stats().unwrap();
+            assert_eq!(stats, expected, "Test '{name}' failed");
+            assert!(!stats.is_degenerate(), "Test '{name}' failed");
+        }
+
+        let kids = (3..203).map(|id| format!("{id} 0 R")).collect::<Vec<_>>();
+        let mut builder = PdfBuilder::new().object(1, CATALOG).object(
+            2,
+            &format!("<< /Type /Pages /Kids [{}] /Count 200 >>", kids.join(" ")),
+        );
+        for id in 3..203 {
+            builder = builder.object(id, page);
+        }
+        let path = builder.section("/Root 1 0 R").write("page-tree-degenerate");
+        let mut document = Document::from_path(&path).unwrap();
+        assert!(document.page_tree_stats().unwrap().is_degenerate());
+    }
+
+    #[test]
+    fn test_rewrite_preserving() {
+        let path = PdfBuilder::new()
+            .object(1, "<<  /Type /Catalog\n    /Pages 2 0 R  >>")
+            .object(2, "<< /Type /Pages /Kids [] /Count 0 >> % no pages yet")
+            .object(3, "<< /Title (Before) >>")
+            .object_stream(4, &[(5, "[1 2 3]")])
+            .stream_section(6, "/Root 1 0 R /Info 3 0 R")
+            .write("preserving-original");
+        let original = fs::read(&path).unwrap();
+        let mut document = Document::from_path(&path).unwrap();
+
+        let info = IndirectReference { id: 3, gen_id: 0 };
+        document.objects_mut().stage(
+            info,
+            Object::Dictionary(Dictionary::from([("Title", Object::string("After"))])),
+        );
+
+        let mut output = Vec::new();
+        document.rewrite_preserving(&mut output).unwrap();
+        let path = crate::test_utils::write_temp("preserving-rewritten", &output);
+        let rewritten = Document::from_path(&path).unwrap();
+        assert_eq!(rewritten.title().as_deref(), Some("After"));
+
+        for id in [1, 2] {
+            let reference = IndirectReference { id, gen_id: 0 };
+            let before = &original[document.objects.byte_range(&reference).unwrap()];
+            let after = &output[rewritten.objects.byte_range(&reference).unwrap()];
+            assert_eq!(after, before, "object {reference} changed");
+        }
+
+        let reference = IndirectReference { id: 5, gen_id: 0 };
+        let after = &output[rewritten.objects.byte_range(&reference).unwrap()];
+        assert_eq!(after, b"5 0 obj\n[1 2 3]\nendobj");
+    }
+
+    #[test]
+    fn test_snapshot_threads() {
+        let mut file = PdfBuilder::new().object(1, CATALOG).object(
+            2,
+            "<< /Type /Pages /Kids [3 0 R 4 0 R 5 0 R] /Count 3 \
+                 /MediaBox [0 0 10 10] /Resources << >> >>",
+        );
+        for (id, text) in [(3, "one"), (4, "two"), (5, "three")] {
+            let content = format!("BT ({text}) Tj ET");
+            file = file
+                .object(
+                    id,
+                    &format!("<< /Type /Page /Parent 2 0 R /Contents {} 0 R >>", id + 3),
+                )
+                .stream(id + 3, "", content.as_bytes());
+        }
+        let path = file.section("/Root 1 0 R").write("snapshot");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let snapshot = document.snapshot().unwrap();
+        assert_eq!(snapshot.page_count(), 3);
+
+        let texts = std::thread::scope(|scope| {
+            let handles = snapshot
+                .pages()
+                .iter()
+                .map(|page| scope.spawn(move || page.extract_text().unwrap()))
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(texts, ["one", "two", "three"]);
+    }
+
+    #[test]
+    fn test_pdfa_conformance() {
+        let xmp = br#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="" xmlns:pdfaid="http://www.aiim.org/pdfa/ns/id/">
+      <pdfaid:part>2</pdfaid:part>
+      <pdfaid:conformance>B</pdfaid:conformance>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>"#;
+        let path = PdfBuilder::new()
+            .object(1, "<< /Type /Catalog /Pages 2 0 R /Metadata 3 0 R >>")
+            .object(2, EMPTY_PAGES)
+            .stream(3, "/Type /Metadata /Subtype /XML", xmp)
+            .section("/Root 1 0 R")
+            .write("pdfa");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert!(
+            document
+                .metadata_xml()
+                .unwrap()
+                .unwrap()
+                .contains("pdfaid:part")
+        );
+        assert_eq!(document.pdfa_conformance().unwrap().as_deref(), Some("2B"));
+
+        let path = minimal_document().section("/Root 1 0 R").write("not-pdfa");
+        let mut document = Document::from_path(&path).unwrap();
+        assert_eq!(document.metadata_xml().unwrap(), None);
+        assert_eq!(document.pdfa_conformance().unwrap(), None);
+    }
+
+    #[test]
+    fn test_info_from_xmp() {
+        let xmp = br#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/">
+      <dc:title><rdf:Alt><rdf:li xml:lang="x-default">Annual report</rdf:li></rdf:Alt></dc:title>
+      <dc:creator><rdf:Seq><rdf:li>Jane Doe</rdf:li></rdf:Seq></dc:creator>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>"#;
+        let file = |trailer: &str| {
+            PdfBuilder::new()
+                .object(1, "<< /Type /Catalog /Pages 2 0 R /Metadata 3 0 R >>")
+                .object(2, EMPTY_PAGES)
+                .stream(3, "/Type /Metadata /Subtype /XML", xmp)
+                .object(4, "<< /Title (From Info) >>")
+                .section(trailer)
+        };
+
+        for (name, trailer) in [
+            ("no Info", "/Root 1 0 R"),
+            ("dangling Info", "/Root 1 0 R /Info 9 0 R"),
+        ] {
+            let path = file(trailer).write("info-from-xmp");
+            let mut document = Document::from_path(&path).unwrap();
+
+            assert!(document.info().is_none(), "Test '{name}' failed");
+            assert_eq!(document.title(), None, "Test '{name}' failed");
+            assert_eq!(
+                document.title_or_xmp().unwrap().as_deref(),
+                Some("Annual report"),
+                "Test '{name}' failed"
+            );
+            assert_eq!(
+                document.author_or_xmp().unwrap().as_deref(),
+                Some("Jane Doe"),
+                "Test '{name}' failed"
+            );
+        }
+
+        let path = file("/Root 1 0 R /Info 4 0 R").write("info-over-xmp");
+        let mut document = Document::from_path(&path).unwrap();
+        assert_eq!(
+            document.title_or_xmp().unwrap().as_deref(),
+            Some("From Info")
+        );
+        assert_eq!(
+            document.author_or_xmp().unwrap().as_deref(),
+            Some("Jane Doe")
+        );
+
+        let path = minimal_document()
+            .section("/Root 1 0 R")
+            .write("info-without-xmp");
+        let mut document = Document::from_path(&path).unwrap();
+        assert_eq!(document.title_or_xmp().unwrap(), None);
+    }
+
+    #[test]
+    fn test_trailer_references_from_prior_revision() {
+        struct TestCase {
+            name: &'static str,
+            file: PdfBuilder,
+        }
+
+        // Only the newest trailer names `/Info`, the object itself is listed
+        // by an older section that is reached through `/Prev`.
+        let test_cases = [
+            TestCase {
+                name: "xref tables",
+                file: minimal_document()
+                    .object(3, "<< /Title (Prior) >>")
+                    .section("/Root 1 0 R")
+                    .object(4, "(update)")
+                    .section("/Root 1 0 R /Info 3 0 R"),
+            },
+            TestCase {
+                name: "xref streams",
+                file: minimal_document()
+                    .object(3, "<< /Title (Prior) >>")
+                    .stream_section(4, "/Root 1 0 R")
+                    .object(5, "(update)")
+                    .stream_section(6, "/Root 1 0 R /Info 3 0 R"),
+            },
+            TestCase {
+                name: "three revisions",
+                file: minimal_document()
+                    .object(3, "<< /Title (Prior) >>")
+                    .section("/Root 1 0 R")
+                    .object(4, "(first update)")
+                    .section("/Root 1 0 R")
+                    .object(5, "(second update)")
+                    .section("/Root 1 0 R /Info 3 0 R"),
+            },
+        ];
+
+        for case in test_cases {
+            let path = case.file.write("trailer-prior-revision");
+            let mut document = Document::from_path(&path).unwrap();
+
+            assert_eq!(
+                document.xref_metadata().info_id,
+                Some(IndirectReference { id: 3, gen_id: 0 }),
+                "Test '{}' failed",
+                case.name
+            );
+            assert!(
+                document.xref_metadata().prev.is_some(),
+                "Test '{}' failed",
+                case.name
+            );
+            assert_eq!(
+                document.title().as_deref(),
+                Some("Prior"),
+                "Test '{}' failed",
+                case.name
+            );
+            assert_eq!(document.pages().count(), 0, "Test '{}' failed", case.name);
+        }
+    }
+
+    #[test]
+    fn test_xref_section_bytes() {
+        let file = minimal_document()
+            .section("/Root 1 0 R")
+            .object(3, "<< /Title (Update) >>");
+        let start = file.offset();
+        let data = file.section("/Root 1 0 R /Info 3 0 R").build();
+        let path = crate::test_utils::write_temp("xref-section-table", &data);
+        let document = Document::from_path(&path).unwrap();
+
+        let bytes = document.xref_section_bytes();
+        assert!(bytes.starts_with(b"xref"), "got {bytes:?}");
+        assert!(bytes.ends_with(b">>"), "got {bytes:?}");
+        assert!(bytes.ends_with(document.raw_trailer_bytes()));
+        assert!(!bytes.windows(b"startxref".len()).any(|w| w == b"startxref"));
+        assert!(data[start..].starts_with(&bytes));
+
+        let path = minimal_document()
+            .stream_section(3, "/Root 1 0 R")
+            .write("xref-section-stream");
+        let document = Document::from_path(&path).unwrap();
+
+        let bytes = document.xref_section_bytes();
+        assert!(bytes.starts_with(b"3 0 obj"), "got {bytes:?}");
+        assert!(bytes.ends_with(b"endobj"), "got {bytes:?}");
+
+        let path = minimal_document().write("xref-section-scanned");
+        let document = Document::from_path(&path).unwrap();
+        assert!(document.xref_section_bytes().is_empty());
+    }
+
+    #[test]
+    fn test_xref_stream_trailer() {
+        let file = minimal_document();
+        let first = file.offset();
+        let data = file
+            .stream_section(3, "/Root 1 0 R")
+            .object(4, "<< /Title (Streams only) >>")
+            .stream_section(5, "/Root 1 0 R /Info 4 0 R /ID [<01> <02>]")
+            .build();
+        assert!(
+            !data
+                .windows(b"trailer".len())
+                .any(|window| window == b"trailer"),
+            "file must not contain a trailer keyword"
+        );
+
+        let path = crate::test_utils::write_temp("xref-stream-trailer", &data);
+        let mut document = Document::from_path(&path).unwrap();
+        let metadata = document.xref_metadata();
+
+        assert_eq!(metadata.kind, XrefKind::Stream);
+        assert_eq!(metadata.root_id, IndirectReference { id: 1, gen_id: 0 });
+        assert_eq!(
+            metadata.info_id,
+            Some(IndirectReference { id: 4, gen_id: 0 })
+        );
+        assert_eq!(metadata.size, 6);
+        assert_eq!(metadata.prev, Some(first as u64));
+        assert!(metadata.hash.is_some());
+        assert!(document.raw_trailer_bytes().starts_with(b"5 0 obj"));
+
+        assert_eq!(document.title().as_deref(), Some("Streams only"));
+        assert_eq!(document.pages().count(), 0);
+    }
+
+    #[test]
+    fn test_mismatched_section_sizes() {
+        let path = minimal_document()
+            .object(3, "(first)")
+            .section("/Root 1 0 R /Size 4")
+            .object(4, "(second)")
+            .stream_section(5, "/Root 1 0 R /Size 3")
+            .write("mismatched-section-sizes");
+        let mut document = Document::from_path(&path).unwrap();
+
+        let objects = document.objects_mut();
+        for (id, expected) in [(3, b"first".as_slice()), (4, b"second")] {
+            let Object::IndirectDefinition(object) = objects
+                .get_object(&IndirectReference { id, gen_id: 0 })
+                .unwrap()
+            else {
+                panic!("object {id} must be an indirect definition");
+            };
+            assert_eq!(object.as_string().unwrap().as_bytes(), expected);
+        }
+        assert_eq!(objects.size().unwrap(), 4);
+
+        assert_eq!(
+            document.validate().unwrap(),
+            vec![
+                ValidationIssue::SizeMismatch {
+                    size: 4,
+                    highest_object: 5,
+                },
+                ValidationIssue::SectionSizesDiffer { sizes: vec![3, 4] },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_language() {
+        let page = "/Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] /Resources << >>";
+        let path = PdfBuilder::new()
+            .object(1, "<< /Type /Catalog /Pages 2 0 R /Lang (en-US) >>")
+            .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>")
+            .object(3, &format!("<< {page} >>"))
+            .object(4, &format!("<< {page} /Lang 5 0 R >>"))
+            .object(5, "<FEFF00640065002D00440045>")
+            .section("/Root 1 0 R")
+            .write("language");
+        let mut document = Document::from_path(&path).unwrap();
+
+        assert_eq!(document.language().unwrap().as_deref(), Some("en-US"));
+
+        let languages = document
+            .pages()
+            .map(|page| page.unwrap().language().map(str::to_string))
+            .collect::<Vec<_>>();
+        assert_eq!(languages, [None, Some("de-DE".to_string())]);
+
+        let path = minimal_document()
+            .section("/Root 1 0 R")
+            .write("no-language");
+        let mut document = Document::from_path(&path).unwrap();
+        assert_eq!(document.language().unwrap(), None);
+    }
+
+    #[test]
+    fn test_scan_without_xref() {
+        let page = "/Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] /Resources << >>";
+        let objects = || {
+            PdfBuilder::new()
+                .object(1, CATALOG)
+                .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                .object(3, &format!("<< {page} >>"))
+                .object(4, "<< /Title (First) >>")
+                // Appended by an update, replaces the definitions above.
+                .object(3, &format!("<< {page} /Rotate 90 >>"))
+                .object(4, "<< /Title (Second) >>")
+        };
+
+        struct TestCase {
+            name: &'static str,
+            file: PdfBuilder,
+            expected_title: Option<&'static str>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "no xref and no trailer",
+                file: objects(),
+                expected_title: None,
+            },
+            TestCase {
+                name: "trailer without xref",
+                file: objects().raw(b"trailer\n<< /Root 1 0 R /Info 4 0 R >>\n%%EOF\n"),
+                expected_title: Some("Second"),
+            },
+            TestCase {
+                name: "startxref past the end of the file",
+                file: objects().raw(b"startxref\n99999\n%%EOF\n"),
+                expected_title: None,
+            },
+        ];
+
+        for case in test_cases {
+            let path = case.file.write("scan-without-xref");
+            let mut document = Document::from_path(&path)
+                .unwrap_or_else(|err| panic!("Test '{}' failed: {err:?}", case.name));
+
+            assert_eq!(
+                document.xref_metadata().kind,
+                XrefKind::Scanned,
+                "Test '{}' failed",
+                case.name
+            );
+            assert_eq!(
+                document.xref_metadata().root_id,
+                IndirectReference { id: 1, gen_id: 0 },
+                "Test '{}' failed",
+                case.name
+            );
+            assert_eq!(
+                document.title().as_deref(),
+                case.expected_title,
+                "Test '{}' failed",
+                case.name
+            );
+            assert_eq!(document.object_count().unwrap(), 4);
+
+            let pages = document
+                .pages()
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(pages.len(), 1, "Test '{}' failed", case.name);
+            assert_eq!(pages[0].rotate(), 90, "Test '{}' failed", case.name);
+
+            assert!(document.save_incremental(Vec::new()).is_err());
+
+            let options = ParseOptions {
+                strictness: Strictness::Strict,
+                ..Default::default()
+            };
+            assert!(
+                Document::from_path_with_options(&path, DocumentLimits::default(), options)
+                    .is_err(),
+                "Test '{}' failed: opened in strict mode",
+                case.name
+            );
         }
-        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use snafu::Snafu;
 
-use crate::{document, pages};
+use crate::{document, pages, structures::page};
 
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(super)), context(suffix(false)))]
@@ -10,4 +10,7 @@ pub(super) enum Error {
 
     #[snafu(display("Error while reading document pages"))]
     Pages { source: pages::Error },
+
+    #[snafu(display("Error while reading page"))]
+    Page { source: page::Error },
 }
@@ -3,12 +3,68 @@ mod error;
 mod objects;
 mod pages;
 mod parser;
+mod snapshot;
 mod structures;
+#[cfg(test)]
+mod test_utils;
 mod types;
+mod writer;
 
 pub use document::Document;
-pub use structures::page::Page;
+pub use objects::{DocumentLimits, LoadPhase, LoadProgress, Objects, ParseOptions, Strictness};
+pub use snapshot::DocumentSnapshot;
+pub use structures::{
+    acroform::AcroFormInfo,
+    color_space::ColorSpace,
+    destination::{Destination, DestinationPage, DestinationView},
+    embedded_file::EmbeddedFile,
+    encryption::{
+        EncryptionInfo,
+        crypt::{CryptFilters, CryptMethod},
+    },
+    image::{DecodedImage, ImageMask, ImageRef},
+    page::{
+        Page,
+        annotation::{Annotation, LinkTarget},
+        graphics_state::{ContentOpsWithState, GraphicsState, StatefulOp},
+        group::TransparencyGroup,
+        text::{TextOptions, TextRun},
+        transition::{Transition, TransitionDimension, TransitionStyle},
+    },
+    root::{
+        optional_content::Layer,
+        pages_tree::PageTreeStats,
+        viewer_preferences::{Direction, NonFullScreenPageMode, ViewerPreferences},
+    },
+    signature::{Signature, SignatureField},
+    validation::{IssueSeverity, ValidationIssue},
+    xref::{XrefKind, XrefMetadata},
+};
+pub use types::{
+    Array, ContentOp, Dictionary, DictionaryBuilder, DuplicateKeyPolicy, ImageFormat,
+    IndirectReference, Matrix, Name, Numeric, Object, PdfString, Rectangle, Stream,
+};
 
 #[derive(Debug, snafu::Snafu)]
 pub struct Error(error::Error);
 type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Message of the error followed by every underlying cause, separated by `: `.
+    ///
+    /// `Display` only shows the outermost context, which rarely says what
+    /// actually went wrong. This walks [`std::error::Error::source`] down to
+    /// the root cause.
+    pub fn full_message(&self) -> String {
+        let mut message = self.to_string();
+
+        let mut source = std::error::Error::source(self);
+        while let Some(error) = source {
+            message.push_str(": ");
+            message.push_str(&error.to_string());
+            source = error.source();
+        }
+
+        message
+    }
+}
@@ -1,61 +1,487 @@
-use std::{collections::BTreeMap, fs::File};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    ops::Range,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
 use memmap2::Mmap;
-use snafu::{OptionExt, ResultExt, Snafu};
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
 
 use crate::{
-    parser::read_object,
+    parser::{object_length, read_indirect_object_lenient, read_object_with_policy},
     structures::object_stream::ObjectStream,
     structures::xref::{Xref, XrefEntry, XrefMetadata},
-    types::{IndirectReference, Object},
+    types::{DuplicateKeyPolicy, IndirectObject, IndirectReference, Object, Rectangle, Stream},
 };
 
 #[derive(Debug, Snafu)]
 pub struct Error(error::Error);
 type Result<T> = std::result::Result<T, Error>;
 
+/// Indirect objects of a document, loaded on demand through the cross-reference table.
+///
+/// Edits are staged in memory with [`Objects::stage`] and take precedence
+/// over the file when objects are loaded. They are written out by
+/// [`crate::Document::save_incremental`].
 #[derive(Debug)]
 pub struct Objects {
     file: Mmap,
     xref: Xref,
+    limits: DocumentLimits,
+    options: ParseOptions,
+    budget: DecodeBudget,
 
     object_streams: BTreeMap<usize, ObjectStream>,
+    /// Objects replaced or added since the document was opened.
+    staged: BTreeMap<IndirectReference, Object>,
+    /// Number of objects loaded from the file so far.
+    resolved: usize,
+}
+
+/// Step of opening a document, reported by [`crate::Document::from_path_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadPhase {
+    /// Header version and `startxref` offset read
+    Header,
+    /// Latest cross-reference section and its trailer read
+    Xref,
+    /// Catalog and document information referenced by the trailer loaded
+    Trailer,
+    /// Root of the page tree read
+    PageIndex,
+}
+
+/// Progress of opening a document, passed to the callback after each phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// Phase that has just completed
+    pub phase: LoadPhase,
+    /// Number of indirect objects loaded from the file so far
+    pub objects_resolved: usize,
+}
+
+/// Document-wide caps applied while loading a PDF.
+///
+/// Intended for untrusted input: a crafted file can declare millions of
+/// objects, chain thousands of incremental updates or carry compression
+/// bombs. Every limit is disabled when set to `None`, which is the default,
+/// except for [`DocumentLimits::max_xref_sections`].
+///
+/// # Example
+/// ```
+/// use docv_pdf::DocumentLimits;
+///
+/// let limits = DocumentLimits {
+///     max_xref_sections: Some(64),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DocumentLimits {
+    /// Maximum number of cross-reference entries across all sections.
+    pub max_objects: Option<usize>,
+    /// Maximum number of cross-reference sections (`/Prev` and `/XRefStm` chain).
+    /// The chain is followed even without limits, so `None` caps it at 1024 sections.
+    pub max_xref_sections: Option<usize>,
+    /// Maximum number of bytes produced by decoding streams, counted across
+    /// every stream the document decodes, including page contents and images.
+    pub max_total_decompressed: Option<usize>,
+}
+
+/// Choices on how to read parts of a PDF the specification leaves open.
+///
+/// Unlike [`DocumentLimits`] these don't cap anything, they pick one of the
+/// readings that real-world readers disagree on.
+///
+/// # Example
+/// ```
+/// use docv_pdf::{DuplicateKeyPolicy, ParseOptions};
+///
+/// let options = ParseOptions {
+///     duplicate_keys: DuplicateKeyPolicy::Error,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ParseOptions {
+    /// How dictionaries that repeat a key are read. Set it to
+    /// [`DuplicateKeyPolicy::Error`] to reject such ambiguous dictionaries.
+    pub duplicate_keys: DuplicateKeyPolicy,
+    /// Media box of pages when neither the page nor any ancestor has
+    /// `/MediaBox`. The specification has no default, so `None` fails such
+    /// pages. Set it to [`Rectangle::LETTER`] to read them like Acrobat does.
+    pub default_media_box: Option<Rectangle>,
+    /// Whether known deviations of broken producers are recovered from, see
+    /// [`Strictness`].
+    pub strictness: Strictness,
+}
+
+/// How documents that break the specification in common ways are read.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strictness {
+    /// Recover from known producer mistakes, logging a warning for each:
+    /// - objects missing `endobj`,
+    /// - no usable cross-reference section, objects are found by scanning,
+    /// - `startxref` with its offset on the same line,
+    /// - malformed numbers like `--5` in content streams,
+    /// - page dictionaries written directly into `/Kids`.
+    #[default]
+    Lenient,
+    /// Reject everything the specification doesn't allow.
+    Strict,
+}
+
+/// Bytes decoded from the streams of one document so far, checked against
+/// [`DocumentLimits::max_total_decompressed`].
+///
+/// Clones share the count, so pages and images decoding their streams later
+/// on draw from the same budget as the cross-reference and object streams.
+/// Every decode counts, also repeated ones of the same stream.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DecodeBudget {
+    limit: Option<usize>,
+    used: Arc<AtomicUsize>,
+}
+
+impl DecodeBudget {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            used: Arc::default(),
+        }
+    }
+
+    /// Decodes `stream` in place, failing as soon as its data exceeds the
+    /// bytes left in the budget.
+    pub(crate) fn process_filters(
+        &self,
+        stream: &mut Stream,
+    ) -> std::result::Result<(), crate::types::stream::Error> {
+        let remaining = self
+            .limit
+            .map(|limit| limit.saturating_sub(self.used.load(Ordering::Relaxed)));
+        stream.process_filters_with_limit(remaining)?;
+        self.used.fetch_add(stream.data.len(), Ordering::Relaxed);
+
+        Ok(())
+    }
 }
 
 impl Objects {
-    pub fn from_file(file: File) -> Result<(Self, XrefMetadata)> {
+    pub fn from_file(file: File, limits: DocumentLimits) -> Result<(Self, XrefMetadata)> {
+        Self::from_file_with_progress(file, limits, ParseOptions::default(), &mut |_| {})
+    }
+
+    /// Same as [`Objects::from_file`], reading objects as chosen by `options`
+    /// and reporting the `Header` and `Xref` phases to `progress`.
+    pub(crate) fn from_file_with_progress(
+        file: File,
+        limits: DocumentLimits,
+        options: ParseOptions,
+        progress: &mut impl FnMut(LoadProgress),
+    ) -> Result<(Self, XrefMetadata)> {
         let file = unsafe { Mmap::map(&file) }.context(error::Mmap)?;
-        let mut xref = Xref::default();
+        let budget = DecodeBudget::new(limits.max_total_decompressed);
+        let mut xref = Xref::new(limits.max_xref_sections, budget.clone(), options.strictness);
 
         // #[cfg(unix)]
         // {
         //     file.advise(Advice::Sequential)?; // Sequential access expected
         // }
 
-        let xref_offset = xref
-            .read_startxref(&file, file.len())
-            .context(error::ReadXref)?;
-        let metadata = xref
-            .read_table(&file, xref_offset)
+        let metadata = match xref.read_startxref(&file, file.len()).and_then(|offset| {
+            progress(LoadProgress {
+                phase: LoadPhase::Header,
+                objects_resolved: 0,
+            });
+
+            xref.read_table(&file, offset)
+        }) {
+            Ok(metadata) => metadata,
+            Err(err) if err.is_missing_section() && options.strictness == Strictness::Lenient => {
+                tracing::warn!("No usable cross-reference section, scanning for objects: {err}");
+
+                xref = Xref::new(limits.max_xref_sections, budget.clone(), options.strictness);
+                xref.read_by_scanning(&file).context(error::ReadXref)?
+            }
+            Err(err) => Err(err).context(error::ReadXref)?,
+        };
+
+        let objects = Self {
+            file,
+            xref,
+            limits,
+            options,
+            budget,
+            object_streams: BTreeMap::default(),
+            staged: BTreeMap::default(),
+            resolved: 0,
+        };
+        objects.check_limits()?;
+
+        progress(LoadProgress {
+            phase: LoadPhase::Xref,
+            objects_resolved: 0,
+        });
+
+        Ok((objects, metadata))
+    }
+
+    fn read_additional_table(&mut self) -> Result<()> {
+        self.xref
+            .read_additional_table(&self.file)
             .context(error::ReadXref)?;
 
-        Ok((
-            Self {
-                file,
-                xref,
-                object_streams: BTreeMap::default(),
-            },
-            metadata,
-        ))
+        self.check_limits()
+    }
+
+    fn check_limits(&self) -> Result<()> {
+        if let Some(limit) = self.limits.max_objects {
+            ensure!(
+                self.xref.entry_count() <= limit,
+                error::ObjectLimit { limit }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Number of indirect objects loaded from the file so far.
+    pub fn resolved_count(&self) -> usize {
+        self.resolved
+    }
+
+    /// Options the document was opened with.
+    pub(crate) fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Budget shared by everything decoding streams of this document.
+    pub(crate) fn decode_budget(&self) -> &DecodeBudget {
+        &self.budget
+    }
+
+    /// Reads every cross-reference section still reachable through `/Prev`
+    /// and `/XRefStm`, so the xref reflects the whole document.
+    pub fn read_all_tables(&mut self) -> Result<()> {
+        while self.xref.has_more_tables() {
+            self.read_additional_table()?;
+        }
+
+        Ok(())
+    }
+
+    /// Number of objects in use according to the merged xref.
+    pub fn object_count(&mut self) -> Result<usize> {
+        self.read_all_tables()?;
+
+        Ok(self.xref.object_count())
+    }
+
+    /// Number of free objects according to the merged xref.
+    pub fn free_count(&mut self) -> Result<usize> {
+        self.read_all_tables()?;
+
+        Ok(self.xref.free_count())
+    }
+
+    /// Checks that every object number below the trailer's `/Size` has an xref entry.
+    pub fn is_xref_complete(&mut self) -> Result<bool> {
+        self.read_all_tables()?;
+
+        Ok(self.xref.is_complete())
+    }
+
+    /// Highest object number in the merged xref.
+    pub fn highest_object(&mut self) -> Result<Option<usize>> {
+        self.read_all_tables()?;
+
+        Ok(self.xref.highest_object())
+    }
+
+    /// Largest `/Size` across all cross-reference sections.
+    pub fn size(&mut self) -> Result<usize> {
+        self.read_all_tables()?;
+
+        Ok(self.xref.size())
+    }
+
+    /// `/Size` of every cross-reference section, in reading order.
+    pub fn section_sizes(&mut self) -> Result<Vec<usize>> {
+        self.read_all_tables()?;
+
+        Ok(self.xref.section_sizes().to_vec())
+    }
+
+    /// References of all objects in use according to the merged xref.
+    pub fn in_use_references(&mut self) -> Result<Vec<IndirectReference>> {
+        self.read_all_tables()?;
+
+        Ok(self.xref.in_use_references())
+    }
+
+    /// Offsets of the cross-reference sections of every revision, newest first.
+    pub fn revision_offsets(&mut self) -> Result<Vec<u64>> {
+        self.read_all_tables()?;
+
+        Ok(self.xref.revision_offsets().to_vec())
+    }
+
+    /// Offset given by the `startxref` keyword at the end of the file, 0 if
+    /// the objects were found by scanning the file.
+    pub fn startxref_offset(&self) -> u64 {
+        self.xref
+            .revision_offsets()
+            .first()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Position in the file of the newest cross-reference section, see [`Xref::startxref_position`].
+    pub fn startxref_position(&self) -> Option<usize> {
+        self.xref.startxref_position()
+    }
+
+    /// Raw bytes of the file in `range`, `None` if it's out of bounds.
+    pub fn file_bytes(&self, range: Range<usize>) -> Option<&[u8]> {
+        self.file.get(range)
+    }
+
+    /// Bytes of the file taken by an indirect object, from `N G obj` to `endobj`.
+    ///
+    /// Only cross-reference sections read so far are consulted. Objects
+    /// stored inside object streams have no range of their own.
+    pub fn byte_range(&self, object_reference: &IndirectReference) -> Option<Range<usize>> {
+        let XrefEntry::Occupied { offset } = *self.xref.find_entry(object_reference)? else {
+            return None;
+        };
+
+        let length = object_length(self.file.get(offset..)?).ok()?;
+
+        Some(offset..offset + length)
+    }
+
+    /// Stages `object` as the new value of `reference`, replacing or adding it.
+    ///
+    /// The object is returned by [`Objects::get_object`] from now on. An
+    /// indirect definition is stored as the object it wraps.
+    pub fn stage(&mut self, reference: IndirectReference, object: Object) {
+        let object = match object {
+            Object::IndirectDefinition(definition) => (*definition).clone(),
+            object => object,
+        };
+
+        self.staged.insert(reference, object);
+    }
+
+    /// Stages `object` under a new object number after the highest one in use.
+    pub fn stage_new(&mut self, object: Object) -> Result<IndirectReference> {
+        self.read_all_tables()?;
+
+        let id = [
+            self.xref.highest_object(),
+            self.staged.keys().next_back().map(|reference| reference.id),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .map_or(1, |id| id + 1);
+        let reference = IndirectReference { id, gen_id: 0 };
+        self.stage(reference, object);
+
+        Ok(reference)
+    }
+
+    /// Objects staged so far, ordered by object number.
+    pub fn staged(&self) -> impl Iterator<Item = (&IndirectReference, &Object)> {
+        self.staged.iter()
+    }
+
+    /// Length of the underlying file in bytes.
+    pub fn file_len(&self) -> usize {
+        self.file.len()
+    }
+
+    /// Position of the `%PDF-` header in the file, which offsets count from.
+    pub(crate) fn first_byte(&self) -> usize {
+        self.xref.first_byte()
+    }
+
+    /// Original bytes of an object that isn't staged, from `N G obj` to `endobj`.
+    ///
+    /// See [`Objects::byte_range`] for the objects that have a range.
+    pub fn raw_bytes(&self, object_reference: &IndirectReference) -> Option<&[u8]> {
+        if self.staged.contains_key(object_reference) {
+            return None;
+        }
+
+        self.file_bytes(self.byte_range(object_reference)?)
     }
 
     pub fn get_object(&mut self, object_reference: &IndirectReference) -> Result<Object> {
+        if let Some(object) = self.staged.get(object_reference) {
+            return Ok(Object::IndirectDefinition(IndirectObject::new(
+                object_reference.id,
+                object_reference.gen_id,
+                object.clone(),
+            )));
+        }
+
+        let object = self.load_object(object_reference)?;
+        self.resolved += 1;
+
+        Ok(self.resolve_stream_length(object))
+    }
+
+    /// Replaces an indirect `/Length` of a stream with the referenced integer.
+    ///
+    /// Stream data is delimited by `endstream` while parsing, so the length is
+    /// only needed for decoding. If the reference can't be resolved to an
+    /// integer the stream is returned unchanged.
+    fn resolve_stream_length(&mut self, object: Object) -> Object {
+        let Object::IndirectDefinition(definition) = &object else {
+            return object;
+        };
+        let Ok(stream) = definition.as_stream() else {
+            return object;
+        };
+        let Some(length_id) = stream
+            .dictionary
+            .get("Length")
+            .and_then(|length| length.as_indirect_ref().ok())
+            .copied()
+        else {
+            return object;
+        };
+
+        let length = match self.load_object(&length_id) {
+            Ok(Object::IndirectDefinition(length)) => length.as_integer::<i64>().ok(),
+            Ok(length) => length.as_integer::<i64>().ok(),
+            Err(_) => None,
+        };
+        let Some(length) = length else {
+            return object;
+        };
+
+        let mut stream = stream.clone();
+        stream.dictionary.insert("Length", Object::integer(length));
+
+        Object::IndirectDefinition(IndirectObject::new(
+            definition.id,
+            definition.gen_id,
+            Object::Stream(stream),
+        ))
+    }
+
+    fn load_object(&mut self, object_reference: &IndirectReference) -> Result<Object> {
         let mut entry = self.xref.find_entry(object_reference);
 
         while entry.is_none() && self.xref.has_more_tables() {
-            self.xref
-                .read_additional_table(&self.file)
-                .context(error::ReadXref)?;
+            self.read_additional_table()?;
 
             entry = self.xref.find_entry(object_reference);
         }
@@ -70,9 +496,28 @@ impl Objects {
             }
             .into()),
             XrefEntry::Occupied { offset } => {
-                let object = read_object(&self.file[offset..])
-                    .ok()
-                    .context(error::ReadEntry)?;
+                let input = &self.file[offset..];
+                // Without `endobj` only the object number itself would parse.
+                let policy = self.options.duplicate_keys;
+                let object = match read_object_with_policy(input, policy) {
+                    Ok(object @ Object::IndirectDefinition(_)) => object,
+                    Ok(_) if self.options.strictness == Strictness::Strict => {
+                        return Err(error::Error::MissingEndobj {
+                            object: *object_reference,
+                        }
+                        .into());
+                    }
+                    Err(_) if self.options.strictness == Strictness::Strict => {
+                        return Err(error::Error::ReadEntry.into());
+                    }
+                    strict => match read_indirect_object_lenient(input, policy) {
+                        Ok(object) => {
+                            tracing::warn!("Object {object_reference} has no `endobj` keyword");
+                            object
+                        }
+                        Err(_) => strict.ok().context(error::ReadEntry)?,
+                    },
+                };
 
                 Ok(object)
             }
@@ -85,7 +530,11 @@ impl Objects {
                 match stream {
                     Some(stream) => {
                         let object = stream
-                            .get_object_by_index(stream_ind)
+                            .get_object_by_index(
+                                object_reference.id,
+                                stream_ind,
+                                self.options.duplicate_keys,
+                            )
                             .context(error::GetObjectFromStreamObject)?;
 
                         Ok(object)
@@ -96,11 +545,15 @@ impl Objects {
                             gen_id: 0,
                         })?;
                         let object = object.as_stream().cloned().context(error::Object)?;
-                        let stream =
-                            ObjectStream::from_stream(object).context(error::CreateObjectStream)?;
+                        let stream = ObjectStream::from_stream(object, &self.budget)
+                            .context(error::CreateObjectStream)?;
 
                         let object = stream
-                            .get_object_by_index(stream_ind)
+                            .get_object_by_index(
+                                object_reference.id,
+                                stream_ind,
+                                self.options.duplicate_keys,
+                            )
                             .context(error::GetObjectFromStreamObject)?;
 
                         self.object_streams.insert(stream_id, stream);
@@ -139,6 +592,9 @@ mod error {
         #[snafu(display("Entry for indirect object {object} is free"))]
         EntryIsFree { object: IndirectReference },
 
+        #[snafu(display("Indirect object {object} has no `endobj` keyword"))]
+        MissingEndobj { object: IndirectReference },
+
         #[snafu(display("Invalid object type"))]
         Object { source: crate::types::object::Error },
 
@@ -147,9 +603,170 @@ mod error {
             source: crate::structures::object_stream::Error,
         },
 
+        #[snafu(display("Document has more than {limit} objects"))]
+        ObjectLimit { limit: usize },
+
         #[snafu(display("Failed to get object from ObjectStream"))]
         GetObjectFromStreamObject {
             source: crate::structures::object_stream::Error,
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::minimal_document;
+
+    #[test]
+    fn test_stream_length_reference() {
+        let path = minimal_document()
+            .object_bytes(3, b"<< /Length 10 0 R >>\nstream\n0123456789\nendstream")
+            .object(10, "10")
+            .object_bytes(4, b"<< /Length 11 0 R >>\nstream\nabc\nendstream")
+            .object(11, "(not a length)")
+            .section("/Root 1 0 R")
+            .write("stream-length-reference");
+        let file = File::open(&path).unwrap();
+        let (mut objects, _) = Objects::from_file(file, DocumentLimits::default()).unwrap();
+
+        let object = objects
+            .get_object(&IndirectReference { id: 3, gen_id: 0 })
+            .unwrap();
+        let mut stream = object.as_stream().unwrap().clone();
+        assert_eq!(stream.dictionary.get("Length"), Some(&Object::integer(10)));
+        stream.process_filters().unwrap();
+        assert_eq!(stream.data, b"0123456789");
+
+        let object = objects
+            .get_object(&IndirectReference { id: 4, gen_id: 0 })
+            .unwrap();
+        assert_eq!(
+            object.as_stream().unwrap().dictionary.get("Length"),
+            Some(&Object::IndirectReference(IndirectReference {
+                id: 11,
+                gen_id: 0
+            }))
+        );
+    }
+
+    #[test]
+    fn test_direct_reference_chain() {
+        let path = minimal_document()
+            .object(3, "4 0 R")
+            .object(4, "<< /Chained true >>")
+            .object(5, "6 0 R")
+            .object(6, "5 0 R")
+            .object(7, "9 0 R")
+            .section("/Root 1 0 R")
+            .write("direct-reference-chain");
+        let file = File::open(&path).unwrap();
+        let (mut objects, _) = Objects::from_file(file, DocumentLimits::default()).unwrap();
+        let reference = |id| Object::IndirectReference(IndirectReference { id, gen_id: 0 });
+
+        let start = reference(3);
+        let object = start.direct(&mut objects);
+        assert_eq!(
+            object.as_dictionary().unwrap().get("Chained"),
+            Some(&Object::Boolean(true))
+        );
+
+        // Cycles and dangling links give back the original reference.
+        let start = reference(5);
+        assert_eq!(*start.direct(&mut objects), reference(5));
+        let start = reference(7);
+        assert_eq!(*start.direct(&mut objects), reference(7));
+
+        let object = Object::integer(1);
+        assert_eq!(*object.direct(&mut objects), Object::integer(1));
+    }
+
+    #[test]
+    fn test_byte_range() {
+        let builder = minimal_document().object(3, "(abc)");
+        let start = builder.offset();
+        let data = builder
+            .object(4, "<< /Key [1 2 3] >>")
+            .object_stream(5, &[(6, "(compressed)")])
+            .stream_section(7, "/Root 1 0 R")
+            .build();
+        let path = crate::test_utils::write_temp("byte-range", &data);
+        let file = File::open(&path).unwrap();
+        let (objects, metadata) = Objects::from_file(file, DocumentLimits::default()).unwrap();
+
+        let range = objects
+            .byte_range(&IndirectReference { id: 4, gen_id: 0 })
+            .unwrap();
+        assert_eq!(range.start, start);
+        assert_eq!(&data[range], b"4 0 obj\n<< /Key [1 2 3] >>\nendobj");
+
+        assert_eq!(
+            objects.byte_range(&IndirectReference { id: 6, gen_id: 0 }),
+            None
+        );
+        assert_eq!(
+            objects.byte_range(&IndirectReference { id: 42, gen_id: 0 }),
+            None
+        );
+
+        let trailer = &data[metadata.trailer_range];
+        assert!(trailer.starts_with(b"7 0 obj"));
+        assert!(trailer.ends_with(b"endobj"));
+
+        let data = minimal_document().section("/Root 1 0 R").build();
+        let path = crate::test_utils::write_temp("trailer-range", &data);
+        let file = File::open(&path).unwrap();
+        let (_, metadata) = Objects::from_file(file, DocumentLimits::default()).unwrap();
+
+        assert_eq!(
+            &data[metadata.trailer_range],
+            b"trailer\n<< /Size 3 /Root 1 0 R >>"
+        );
+    }
+
+    #[test]
+    fn test_missing_endobj() {
+        let data = minimal_document()
+            .object_raw(3, b"3 0 obj\n<< /Type /Example >>\n")
+            .object_raw(4, b"4 0 obj\n(last)\n")
+            .section("/Root 1 0 R")
+            .build();
+        let path = crate::test_utils::write_temp("missing-endobj", &data);
+        let file = File::open(&path).unwrap();
+        let (mut objects, _) = Objects::from_file(file, DocumentLimits::default()).unwrap();
+
+        let object = objects
+            .get_object(&IndirectReference { id: 3, gen_id: 0 })
+            .unwrap();
+        assert_eq!(
+            object.as_dictionary().unwrap().get("Type"),
+            Some(&Object::name("Example"))
+        );
+
+        // The last object is directly followed by the xref table.
+        let object = objects
+            .get_object(&IndirectReference { id: 4, gen_id: 0 })
+            .unwrap();
+        let Object::IndirectDefinition(object) = object else {
+            panic!("expected an indirect definition, got {object:?}");
+        };
+        assert_eq!(*object, Object::string("last"));
+
+        let options = ParseOptions {
+            strictness: Strictness::Strict,
+            ..Default::default()
+        };
+        let file = File::open(&path).unwrap();
+        let (mut objects, _) =
+            Objects::from_file_with_progress(file, DocumentLimits::default(), options, &mut |_| {})
+                .unwrap();
+
+        let err = objects
+            .get_object(&IndirectReference { id: 3, gen_id: 0 })
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Indirect object 3 0 R has no `endobj` keyword"
+        );
+    }
+}
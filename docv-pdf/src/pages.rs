@@ -1,16 +1,19 @@
+use std::collections::BTreeSet;
+
 use snafu::{OptionExt, ResultExt, Snafu};
 
 use crate::{
     objects::Objects,
     structures::{
         page::Page,
-        root::pages_tree::{InheritableAttributes, PagesTreeNode},
+        root::pages_tree::{InheritableAttributes, PageTreeKid, PagesTreeNode},
     },
     types::IndirectReference,
 };
 
 #[derive(Debug, Snafu)]
-pub struct Error(error::Error);
+#[snafu(source(from(error::Error, Box::new)))]
+pub struct Error(Box<error::Error>);
 type Result<T> = std::result::Result<T, Error>;
 
 /// Iterator over pages in a PDF document's page tree.
@@ -27,6 +30,8 @@ type Result<T> = std::result::Result<T, Error>;
 /// - Attributes can be inherited from parent pages nodes
 ///
 /// This iterator performs a depth-first traversal of this tree structure.
+/// A node referenced more than once, as in cyclic `/Kids`, is only visited
+/// the first time, later references are skipped with a warning.
 ///
 /// # Usage
 /// ```
@@ -48,10 +53,14 @@ type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug)]
 pub struct Pages<'a> {
     root: PagesTreeNode,
-    stack: Vec<(std::vec::IntoIter<IndirectReference>, InheritableAttributes)>,
-    current_iter: std::vec::IntoIter<IndirectReference>,
+    stack: Vec<(std::vec::IntoIter<PageTreeKid>, InheritableAttributes)>,
+    current_iter: std::vec::IntoIter<PageTreeKid>,
     current_inheritable: InheritableAttributes,
     objects: &'a mut Objects,
+    skip_missing: bool,
+    missing: Vec<IndirectReference>,
+    /// Kids loaded so far, to stop on cyclic `/Kids`
+    visited: BTreeSet<IndirectReference>,
 }
 
 impl<'a> std::iter::Iterator for Pages<'a> {
@@ -78,9 +87,35 @@ impl<'a> Pages<'a> {
             current_iter: pages.kids.clone().into_iter(),
             current_inheritable: pages.inheritable_attributes.clone(),
             objects,
+            skip_missing: false,
+            missing: Vec::new(),
+            visited: BTreeSet::new(),
         }
     }
 
+    /// Skips `/Kids` entries whose object can't be loaded instead of failing.
+    ///
+    /// Skipped references are available from [`Pages::missing`], so the
+    /// available pages of a partially corrupt file can still be shown.
+    ///
+    /// ```no_run
+    /// # use std::path::PathBuf;
+    /// # use docv_pdf::Document;
+    /// let mut document = Document::from_path(&PathBuf::from("broken.pdf")).unwrap();
+    /// let mut pages = document.pages().skip_missing();
+    /// let loaded = pages.by_ref().filter_map(|page| page.ok()).count();
+    /// println!("{loaded} pages, missing: {:?}", pages.missing());
+    /// ```
+    pub fn skip_missing(mut self) -> Self {
+        self.skip_missing = true;
+        self
+    }
+
+    /// References of kids skipped so far because their object was missing.
+    pub fn missing(&self) -> &[IndirectReference] {
+        &self.missing
+    }
+
     /// Computes the next page in the iteration sequence.
     ///
     /// This private method performs the actual traversal logic, following
@@ -102,24 +137,50 @@ impl<'a> Pages<'a> {
     /// - `Error::InvalidPageNode` if page tree node data cannot be parsed
     fn compute_next(&mut self) -> Result<Option<Page>> {
         loop {
-            if let Some(kid_ref) = self.current_iter.next() {
-                let kid_obj = self
-                    .objects
-                    .get_object(&kid_ref)
-                    .context(error::ObjectNotFound {
-                        reference: kid_ref,
-                        field: "Pages",
-                    })?;
-                let dictionary = kid_obj.as_dictionary().context(error::InvalidKidType {
-                    field: "Kids",
-                    indirect_reference: kid_ref,
-                })?;
+            if let Some(kid) = self.current_iter.next() {
+                let dictionary = match kid {
+                    PageTreeKid::Reference(kid_ref) => {
+                        if !self.visited.insert(kid_ref) {
+                            tracing::warn!(
+                                "Skipping page tree node {kid_ref} listed more than once"
+                            );
+                            continue;
+                        }
+
+                        let kid_obj = match self.objects.get_object(&kid_ref) {
+                            Ok(object) => object,
+                            Err(err) if self.skip_missing => {
+                                tracing::warn!("Skipping missing page tree node {kid_ref}: {err}");
+                                self.missing.push(kid_ref);
+                                continue;
+                            }
+                            Err(source) => {
+                                return Err(error::Error::ObjectNotFound {
+                                    reference: kid_ref,
+                                    field: "Pages",
+                                    source,
+                                }
+                                .into());
+                            }
+                        };
+
+                        kid_obj
+                            .as_dictionary()
+                            .cloned()
+                            .context(error::InvalidKidType {
+                                field: "Kids",
+                                indirect_reference: kid_ref,
+                            })?
+                    }
+                    PageTreeKid::Inline(dictionary) => *dictionary,
+                };
+                let dictionary = &dictionary;
                 let node_type = dictionary
-                    .get("Type")
-                    .and_then(|obj| obj.as_name().ok())
+                    .get_name("Type")
+                    .and_then(|name| name.ok())
                     .context(error::FieldNotFound { field: "Type" })?;
 
-                match node_type.as_str() {
+                match node_type {
                     "Page" => {
                         return Ok(Some(
                             Page::from_dictionary(
@@ -134,6 +195,7 @@ impl<'a> Pages<'a> {
                         let new_node = PagesTreeNode::from_dictionary(
                             dictionary,
                             Some(self.current_inheritable.clone()),
+                            self.objects.options().strictness,
                         )
                         .context(error::InvalidPageNode)?;
                         let old_iter =
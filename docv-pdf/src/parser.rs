@@ -1,5 +1,6 @@
 mod array;
 mod boolean;
+mod content;
 mod date;
 mod dictionary;
 mod file;
@@ -13,9 +14,14 @@ mod stream;
 mod string;
 mod whitespace;
 
+pub use content::parse_content_stream;
 pub use date::read_date;
 pub use file::{
-    XrefObject, XrefTableSection, read_startxref, read_trailer, read_version, read_xref,
+    XrefObject, XrefTableSection, read_startxref, read_startxref_lenient, read_trailer,
+    read_version, read_xref,
+};
+pub use object::{
+    object_length, read_indirect_object_lenient, read_object, read_object_with_policy, read_objects,
 };
-pub use object::read_object;
 pub use object_stream::read_object_stream_header;
+pub use whitespace::{is_delimiter, is_whitespace};
@@ -2,10 +2,10 @@ use nom::{IResult, Parser, branch::alt, bytes::complete::tag, multi::many0, sequ
 
 use crate::{
     parser::{
-        object::object,
+        object::object_with_policy,
         whitespace::{comment, eol, whitespace},
     },
-    types::Array,
+    types::{Array, DuplicateKeyPolicy},
 };
 
 /// Parses a PDF array from the input.
@@ -22,9 +22,15 @@ use crate::{
 /// # Returns
 /// `IResult` containing remaining input and parsed `Vec<Object>` on success
 pub fn array(input: &[u8]) -> IResult<&[u8], Array> {
+    array_with_policy(input, DuplicateKeyPolicy::default())
+}
+
+/// Parses a PDF array, see [`crate::parser::dictionary::dictionary_with_policy`]
+/// for `policy`.
+pub fn array_with_policy(input: &[u8], policy: DuplicateKeyPolicy) -> IResult<&[u8], Array> {
     let contents = many0(delimited(
         many0(alt((whitespace, comment, eol))),
-        object,
+        |input| object_with_policy(input, policy),
         many0(alt((whitespace, comment, eol))),
     ));
 
@@ -36,7 +42,7 @@ pub fn array(input: &[u8]) -> IResult<&[u8], Array> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::types::{Numeric, Object, PdfString};
+    use crate::types::{IndirectReference, Numeric, Object, PdfString};
     use nom::error::dbg_dmp;
 
     #[test]
@@ -80,7 +86,7 @@ mod test {
                 expected_result: Some(
                     vec![
                         Object::Numeric(Numeric::Integer(1)),
-                        Object::String(PdfString::Literal("two".to_string())),
+                        Object::String(PdfString::Literal("two".into())),
                         Object::Name("three".into()),
                     ]
                     .into(),
@@ -94,7 +100,7 @@ mod test {
                 expected_result: Some(
                     vec![
                         Object::Numeric(Numeric::Integer(1)),
-                        Object::String(PdfString::Literal("two".to_string())),
+                        Object::String(PdfString::Literal("two".into())),
                         Object::Name("three".into()),
                     ]
                     .into(),
@@ -115,7 +121,63 @@ mod test {
                             ]
                             .into(),
                         ),
-                        Object::String(PdfString::Literal("four".to_string())),
+                        Object::String(PdfString::Literal("four".into())),
+                    ]
+                    .into(),
+                ),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "valid array of references",
+                input: b"[1 0 R 2 0 R]",
+                expected: true,
+                expected_result: Some(
+                    vec![
+                        Object::IndirectReference(IndirectReference { id: 1, gen_id: 0 }),
+                        Object::IndirectReference(IndirectReference { id: 2, gen_id: 0 }),
+                    ]
+                    .into(),
+                ),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "valid references mixed with a number",
+                input: b"[1 0 R 5 6 0 R]",
+                expected: true,
+                expected_result: Some(
+                    vec![
+                        Object::IndirectReference(IndirectReference { id: 1, gen_id: 0 }),
+                        Object::Numeric(Numeric::Integer(5)),
+                        Object::IndirectReference(IndirectReference { id: 6, gen_id: 0 }),
+                    ]
+                    .into(),
+                ),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "valid numbers before a reference",
+                input: b"[1 2 3 0 R 4]",
+                expected: true,
+                expected_result: Some(
+                    vec![
+                        Object::Numeric(Numeric::Integer(1)),
+                        Object::Numeric(Numeric::Integer(2)),
+                        Object::IndirectReference(IndirectReference { id: 3, gen_id: 0 }),
+                        Object::Numeric(Numeric::Integer(4)),
+                    ]
+                    .into(),
+                ),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "valid reference without spaces around brackets",
+                input: b"[4 0 R/Name 7 1 R]",
+                expected: true,
+                expected_result: Some(
+                    vec![
+                        Object::IndirectReference(IndirectReference { id: 4, gen_id: 0 }),
+                        Object::Name("Name".into()),
+                        Object::IndirectReference(IndirectReference { id: 7, gen_id: 1 }),
                     ]
                     .into(),
                 ),
@@ -137,6 +199,20 @@ mod test {
                 expected_remainder: Some(b"rest"),
             },
             // Invalid arrays
+            TestCase {
+                name: "invalid real as object number",
+                input: b"[1.0 0 R]",
+                expected: false,
+                expected_result: None,
+                expected_remainder: None,
+            },
+            TestCase {
+                name: "invalid keyword starting with R",
+                input: b"[1 0 RG]",
+                expected: false,
+                expected_result: None,
+                expected_remainder: None,
+            },
             TestCase {
                 name: "invalid unclosed array",
                 input: b"[1 2 3",
@@ -0,0 +1,445 @@
+use nom::{
+    Finish, IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take, take_while1},
+    combinator::{all_consuming, map_res, not},
+    error::{Error, ErrorKind},
+    multi::many0,
+    sequence::{preceded, terminated},
+};
+
+use crate::{
+    objects::Strictness,
+    parser::{
+        array::array,
+        boolean::boolean,
+        dictionary::dictionary,
+        name::name,
+        null::null,
+        numeric::{numeric, numeric_lenient},
+        string::pdf_string,
+        whitespace::{comment, eol, is_delimiter, is_whitespace, whitespace},
+    },
+    types::{ContentOp, Dictionary, Object, Stream},
+};
+
+/// Parses a decoded page content stream into a sequence of operations.
+///
+/// Each operation is a list of operands followed by an operator keyword.
+/// Operands are direct objects only: indirect references and streams are
+/// not allowed inside content streams.
+///
+/// # Example
+/// ```text
+/// BT
+///   /F1 12 Tf
+///   72 712 Td
+///   (Hello) Tj
+/// ET
+/// ```
+///
+/// # Arguments
+/// * `input` - Decoded content stream bytes
+/// * `strictness` - Whether malformed numbers like `--5` are recovered with
+///   [`numeric_lenient`] or fail the stream
+///
+/// # Returns
+/// `Result` containing the operations in stream order, or `Error` if some
+/// part of the input can't be tokenized or operands are left without an operator
+pub fn parse_content_stream(
+    input: &[u8],
+    strictness: Strictness,
+) -> Result<Vec<ContentOp>, Error<&[u8]>> {
+    let (_, operations) = all_consuming(terminated(
+        many0(|input| content_op(input, strictness)),
+        separators,
+    ))
+    .parse(input)
+    .finish()?;
+
+    Ok(operations)
+}
+
+/// Skips any whitespace, comments and end-of-line markers.
+fn separators(input: &[u8]) -> IResult<&[u8], ()> {
+    many0(alt((whitespace, comment, eol)))
+        .map(|_| ())
+        .parse(input)
+}
+
+/// Parses an operand of a content stream operator.
+///
+/// Leniently numbers are read with [`numeric_lenient`], as malformed ones
+/// like `--5` are mostly found in content streams.
+fn operand(input: &[u8], strictness: Strictness) -> IResult<&[u8], Object> {
+    let number = |input| match strictness {
+        Strictness::Lenient => numeric_lenient(input),
+        // `1.2.3` would otherwise be read as the numbers `1.2` and `.3`.
+        Strictness::Strict => terminated(numeric, not(regular)).parse(input),
+    };
+
+    alt((
+        dictionary.map(Object::Dictionary),
+        array.map(Object::Array),
+        number.map(Object::Numeric),
+        pdf_string.map(Object::String),
+        name.map(Object::Name),
+        boolean.map(Object::Boolean),
+        null.map(|_| Object::Null),
+    ))
+    .parse(input)
+}
+
+/// Parses an operator keyword, a run of regular characters.
+///
+/// Operators aren't checked against the ones defined by PDF, so vendor
+/// extensions come through as ordinary operations. Closing delimiters and
+/// braces that can't start an operand are reported as operators too, for
+/// consumers to ignore, instead of failing the whole stream.
+fn operator(input: &[u8]) -> IResult<&[u8], &str> {
+    map_res(
+        alt((
+            regular,
+            take_while1(|c| matches!(c, b'{' | b'}' | b')' | b']' | b'>')),
+        )),
+        str::from_utf8,
+    )
+    .parse(input)
+}
+
+/// Parses a run of regular characters.
+fn regular(input: &[u8]) -> IResult<&[u8], &[u8]> {
+    take_while1(|c| !is_whitespace(c) && !is_delimiter(c)).parse(input)
+}
+
+fn content_op(input: &[u8], strictness: Strictness) -> IResult<&[u8], ContentOp> {
+    let (input, operands) =
+        many0(preceded(separators, |input| operand(input, strictness))).parse(input)?;
+    let (input, operator) = preceded(separators, operator).parse(input)?;
+
+    // Operators never start like a number, so this is a malformed one.
+    if strictness == Strictness::Strict
+        && operator.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.'))
+    {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Digit)));
+    }
+
+    if operator == "BI" {
+        return inline_image(input, strictness);
+    }
+
+    Ok((input, ContentOp::new(operator, operands)))
+}
+
+/// Parses inline image parameters and data following the `BI` operator.
+///
+/// The data starts after a single whitespace character following `ID` and
+/// runs up to the first `EI` keyword that is surrounded by whitespace.
+fn inline_image(input: &[u8], strictness: Strictness) -> IResult<&[u8], ContentOp> {
+    let key_value = (
+        preceded(separators, name),
+        preceded(separators, |input| operand(input, strictness)),
+    )
+        .map(|(name, object)| (name.to_string(), object));
+
+    let (input, parameters) =
+        terminated(many0(key_value), (separators, tag("ID"), take(1usize))).parse(input)?;
+
+    let end = input
+        .windows(2)
+        .enumerate()
+        .position(|(i, window)| {
+            window == b"EI"
+                && i > 0
+                && is_whitespace(input[i - 1])
+                && input
+                    .get(i + 2)
+                    .is_none_or(|c| is_whitespace(*c) || is_delimiter(*c))
+        })
+        .ok_or_else(|| nom::Err::Error(Error::new(input, ErrorKind::TakeUntil)))?;
+
+    let image = Stream {
+        dictionary: Dictionary::from(parameters),
+        data: input[..end - 1].to_vec(),
+    };
+
+    Ok((
+        &input[end + 2..],
+        ContentOp::new("BI", vec![Object::Stream(image)]),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Numeric, PdfString};
+
+    #[test]
+    fn test_content_stream_parser() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            expected: bool,
+            expected_result: Option<Vec<ContentOp>>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "empty stream",
+                input: b"",
+                expected: true,
+                expected_result: Some(vec![]),
+            },
+            TestCase {
+                name: "only whitespace and comments",
+                input: b"  % comment\n\r\n",
+                expected: true,
+                expected_result: Some(vec![]),
+            },
+            TestCase {
+                name: "operator without operands",
+                input: b"q Q",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new("q", vec![]),
+                    ContentOp::new("Q", vec![]),
+                ]),
+            },
+            TestCase {
+                name: "path operators",
+                input: b"10 20 m\n30.5 -4 l S",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new(
+                        "m",
+                        vec![
+                            Object::Numeric(Numeric::Integer(10)),
+                            Object::Numeric(Numeric::Integer(20)),
+                        ],
+                    ),
+                    ContentOp::new(
+                        "l",
+                        vec![
+                            Object::Numeric(Numeric::Real(30.5)),
+                            Object::Numeric(Numeric::Integer(-4)),
+                        ],
+                    ),
+                    ContentOp::new("S", vec![]),
+                ]),
+            },
+            TestCase {
+                name: "malformed numbers",
+                input: b"--10 1.5.0 m",
+                expected: true,
+                expected_result: Some(vec![ContentOp::new(
+                    "m",
+                    vec![
+                        Object::Numeric(Numeric::Integer(-10)),
+                        Object::Numeric(Numeric::Real(1.5)),
+                    ],
+                )]),
+            },
+            TestCase {
+                name: "operator directly after delimiter",
+                input: b"BT /F1 12 Tf(Hello)Tj ET",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new("BT", vec![]),
+                    ContentOp::new(
+                        "Tf",
+                        vec![
+                            Object::Name("F1".into()),
+                            Object::Numeric(Numeric::Integer(12)),
+                        ],
+                    ),
+                    ContentOp::new(
+                        "Tj",
+                        vec![Object::String(PdfString::Literal("Hello".into()))],
+                    ),
+                    ContentOp::new("ET", vec![]),
+                ]),
+            },
+            TestCase {
+                name: "quote operators and T*",
+                input: b"T* (a) ' 1 2 (b) \"",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new("T*", vec![]),
+                    ContentOp::new("'", vec![Object::String(PdfString::Literal("a".into()))]),
+                    ContentOp::new(
+                        "\"",
+                        vec![
+                            Object::Numeric(Numeric::Integer(1)),
+                            Object::Numeric(Numeric::Integer(2)),
+                            Object::String(PdfString::Literal("b".into())),
+                        ],
+                    ),
+                ]),
+            },
+            TestCase {
+                name: "array and dictionary operands",
+                input: b"[(A) -250 (B)] TJ /OC << /MCID 0 >> BDC",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new(
+                        "TJ",
+                        vec![Object::Array(
+                            vec![
+                                Object::String(PdfString::Literal("A".into())),
+                                Object::Numeric(Numeric::Integer(-250)),
+                                Object::String(PdfString::Literal("B".into())),
+                            ]
+                            .into(),
+                        )],
+                    ),
+                    ContentOp::new(
+                        "BDC",
+                        vec![
+                            Object::Name("OC".into()),
+                            Object::Dictionary(Dictionary::from([(
+                                "MCID",
+                                Object::Numeric(Numeric::Integer(0)),
+                            )])),
+                        ],
+                    ),
+                ]),
+            },
+            TestCase {
+                name: "inline image",
+                input: b"q BI /W 2 /H 1 /BPC 8 /CS /G ID \x00EI\xff EI Q",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new("q", vec![]),
+                    ContentOp::new(
+                        "BI",
+                        vec![Object::Stream(Stream {
+                            dictionary: Dictionary::from([
+                                ("W", Object::Numeric(Numeric::Integer(2))),
+                                ("H", Object::Numeric(Numeric::Integer(1))),
+                                ("BPC", Object::Numeric(Numeric::Integer(8))),
+                                ("CS", Object::Name("G".into())),
+                            ]),
+                            data: b"\x00EI\xff".to_vec(),
+                        })],
+                    ),
+                    ContentOp::new("Q", vec![]),
+                ]),
+            },
+            TestCase {
+                name: "vendor operators",
+                input: b"/Acme:Note << /Id 7 >> BDC 1 2 3 acmeXform EMC",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new(
+                        "BDC",
+                        vec![
+                            Object::Name("Acme:Note".into()),
+                            Object::Dictionary(Dictionary::from([(
+                                "Id",
+                                Object::Numeric(Numeric::Integer(7)),
+                            )])),
+                        ],
+                    ),
+                    ContentOp::new(
+                        "acmeXform",
+                        vec![
+                            Object::Numeric(Numeric::Integer(1)),
+                            Object::Numeric(Numeric::Integer(2)),
+                            Object::Numeric(Numeric::Integer(3)),
+                        ],
+                    ),
+                    ContentOp::new("EMC", vec![]),
+                ]),
+            },
+            TestCase {
+                name: "empty hex string closing a marked-content dictionary",
+                input: b"/Span <</ActualText <>>>BDC EMC",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new(
+                        "BDC",
+                        vec![
+                            Object::Name("Span".into()),
+                            Object::Dictionary(Dictionary::from([(
+                                "ActualText",
+                                Object::String(PdfString::Hexadecimal(vec![])),
+                            )])),
+                        ],
+                    ),
+                    ContentOp::new("EMC", vec![]),
+                ]),
+            },
+            TestCase {
+                name: "stray braces and closing delimiters",
+                input: b"{ 1 } q ]) Q",
+                expected: true,
+                expected_result: Some(vec![
+                    ContentOp::new("{", vec![]),
+                    ContentOp::new("}", vec![Object::Numeric(Numeric::Integer(1))]),
+                    ContentOp::new("q", vec![]),
+                    ContentOp::new("])", vec![]),
+                    ContentOp::new("Q", vec![]),
+                ]),
+            },
+            TestCase {
+                name: "operands without operator",
+                input: b"10 20 m 30",
+                expected: false,
+                expected_result: None,
+            },
+            TestCase {
+                name: "unterminated inline image",
+                input: b"BI /W 1 ID \x00\x00",
+                expected: false,
+                expected_result: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let result = parse_content_stream(case.input, Strictness::default());
+            assert_eq!(
+                result.is_ok(),
+                case.expected,
+                "Test '{}' failed: expected success: {}, got: {:?}",
+                case.name,
+                case.expected,
+                result
+            );
+
+            if let Some(expected) = &case.expected_result {
+                assert_eq!(
+                    &result.unwrap(),
+                    expected,
+                    "Test '{}' failed: wrong operations",
+                    case.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_numbers() {
+        let test_cases: [(&str, &[u8], bool); 6] = [
+            ("well-formed numbers", b"10 -2.5 +.5 m", true),
+            ("doubled sign", b"--10 0 m", false),
+            ("second decimal point", b"1.5.0 0 m", false),
+            ("sign without digits", b"- 0 m", false),
+            (
+                "malformed inline image parameter",
+                b"BI /W --1 ID x EI",
+                false,
+            ),
+            ("quote operator", b"(a) '", true),
+        ];
+
+        for (name, input, expected) in test_cases {
+            let result = parse_content_stream(input, Strictness::Strict);
+            assert_eq!(
+                result.is_ok(),
+                expected,
+                "Test '{name}' failed: got {result:?}"
+            );
+        }
+    }
+}
@@ -2,6 +2,7 @@ use nom::{
     IResult, Parser,
     branch::alt,
     bytes::complete::tag,
+    error::{Error, ErrorKind},
     multi::many0,
     sequence::{delimited, preceded},
 };
@@ -9,10 +10,10 @@ use nom::{
 use crate::{
     parser::{
         name::name,
-        object::object,
+        object::object_with_policy,
         whitespace::{comment, eol, whitespace},
     },
-    types::Dictionary,
+    types::{Dictionary, DuplicateKeyPolicy},
 };
 
 /// Parses a PDF dictionary from the input.
@@ -39,9 +40,21 @@ use crate::{
 /// - Remaining input after parsing
 /// - Dictionary key-value pairs on success
 pub fn dictionary(input: &[u8]) -> IResult<&[u8], Dictionary> {
+    dictionary_with_policy(input, DuplicateKeyPolicy::default())
+}
+
+/// Parses a PDF dictionary, reading repeated keys according to `policy`.
+///
+/// The policy applies to every dictionary nested inside as well.
+pub fn dictionary_with_policy(
+    input: &[u8],
+    policy: DuplicateKeyPolicy,
+) -> IResult<&[u8], Dictionary> {
     let key_value = (
         name,
-        preceded(many0(alt((whitespace, comment, eol))), object),
+        preceded(many0(alt((whitespace, comment, eol))), |input| {
+            object_with_policy(input, policy)
+        }),
     )
         .map(|(name, object)| (name.to_string(), object));
 
@@ -49,15 +62,30 @@ pub fn dictionary(input: &[u8]) -> IResult<&[u8], Dictionary> {
         many0(alt((whitespace, comment, eol))),
         key_value,
         many0(alt((whitespace, comment, eol))),
-    ))
-    .map(Dictionary::from);
+    ));
 
-    delimited(
+    let (remaining, entries) = delimited(
         (tag("<<"), many0(alt((whitespace, comment, eol)))),
         contents,
         tag(">>"),
     )
-    .parse(input)
+    .parse(input)?;
+
+    let mut dictionary = Dictionary::default();
+    for (key, value) in entries {
+        if dictionary.contains_key(key.as_str()) {
+            match policy {
+                DuplicateKeyPolicy::KeepFirst => continue,
+                DuplicateKeyPolicy::KeepLast => {}
+                DuplicateKeyPolicy::Error => {
+                    return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+                }
+            }
+        }
+        dictionary.insert(key, value);
+    }
+
+    Ok((remaining, dictionary))
 }
 
 #[cfg(test)]
@@ -190,27 +218,58 @@ mod tests {
                 expected_result: Some(Dictionary::from([
                     (
                         "CreationDate".to_string(),
-                        Object::String(PdfString::Literal("D:20211230134641+11'00'".to_string())),
+                        Object::String(PdfString::Literal("D:20211230134641+11'00'".into())),
                     ),
                     (
                         "Creator".to_string(),
-                        Object::String(PdfString::Literal("By hand".to_string())),
+                        Object::String(PdfString::Literal("By hand".into())),
                     ),
                     (
                         "ModDate".to_string(),
-                        Object::String(PdfString::Literal("D:20211230134824+11'00'".to_string())),
+                        Object::String(PdfString::Literal("D:20211230134824+11'00'".into())),
                     ),
                     (
                         "Producer".to_string(),
-                        Object::String(PdfString::Literal("By hand".to_string())),
+                        Object::String(PdfString::Literal("By hand".into())),
                     ),
                     (
                         "Subject".to_string(),
-                        Object::String(PdfString::Literal("test file".to_string())),
+                        Object::String(PdfString::Literal("test file".into())),
                     ),
                 ])),
                 expected_remainder: Some(b""),
             },
+            TestCase {
+                name: "empty name key",
+                input: b"<</ /Value>>",
+                expected: true,
+                expected_result: Some(Dictionary::from([(
+                    "".to_string(),
+                    Object::Name("Value".into()),
+                )])),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "empty name key and value",
+                input: b"<</ />>",
+                expected: true,
+                expected_result: Some(Dictionary::from([(
+                    "".to_string(),
+                    Object::Name("".into()),
+                )])),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "empty name key next to other keys",
+                input: b"<</A 1/ 2/B/>>",
+                expected: true,
+                expected_result: Some(Dictionary::from([
+                    ("A".to_string(), Object::Numeric(Numeric::Integer(1))),
+                    ("".to_string(), Object::Numeric(Numeric::Integer(2))),
+                    ("B".to_string(), Object::Name("".into())),
+                ])),
+                expected_remainder: Some(b""),
+            },
             // Invalid dictionaries
             TestCase {
                 name: "invalid unclosed dictionary",
@@ -266,4 +325,172 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_angle_brackets() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            expected: Vec<(&'static str, Object)>,
+            expected_remainder: &'static [u8],
+        }
+
+        let hex = |data: &[u8]| Object::String(PdfString::Hexadecimal(data.to_vec()));
+        let empty = || Object::Dictionary(Dictionary::default());
+
+        let test_cases = [
+            TestCase {
+                name: "empty hex string",
+                input: b"<</K <>>>",
+                expected: vec![("K", hex(b""))],
+                expected_remainder: b"",
+            },
+            TestCase {
+                name: "empty hex string without spaces",
+                input: b"<</K<>>>",
+                expected: vec![("K", hex(b""))],
+                expected_remainder: b"",
+            },
+            TestCase {
+                name: "hex string",
+                input: b"<</K <41>>>",
+                expected: vec![("K", hex(b"A"))],
+                expected_remainder: b"",
+            },
+            TestCase {
+                name: "hex string with spaces",
+                input: b"<</K < 4 1 >>>",
+                expected: vec![("K", hex(b"A"))],
+                expected_remainder: b"",
+            },
+            TestCase {
+                name: "empty dictionary",
+                input: b"<</K <<>>>>",
+                expected: vec![("K", empty())],
+                expected_remainder: b"",
+            },
+            TestCase {
+                name: "hex string before dictionary",
+                input: b"<</A <>/B <<>>>>",
+                expected: vec![("A", hex(b"")), ("B", empty())],
+                expected_remainder: b"",
+            },
+            TestCase {
+                name: "dictionary before hex string",
+                input: b"<</A<<>>/B<>>>",
+                expected: vec![("A", empty()), ("B", hex(b""))],
+                expected_remainder: b"",
+            },
+            TestCase {
+                name: "hex string in array",
+                input: b"<</K [<><<>>]>>",
+                expected: vec![("K", Object::Array([hex(b""), empty()].into()))],
+                expected_remainder: b"",
+            },
+            TestCase {
+                name: "trailing bracket",
+                input: b"<</K <>>>>",
+                expected: vec![("K", hex(b""))],
+                expected_remainder: b">",
+            },
+        ];
+
+        for case in test_cases {
+            let (remainder, actual) = dictionary(case.input)
+                .unwrap_or_else(|err| panic!("Test '{}' failed: {err}", case.name));
+
+            assert_eq!(
+                actual,
+                Dictionary::from(case.expected),
+                "Test '{}' failed: wrong dictionary",
+                case.name
+            );
+            assert_eq!(
+                remainder, case.expected_remainder,
+                "Test '{}' failed: wrong remainder",
+                case.name
+            );
+        }
+
+        assert!(dictionary(b"<</K <>>").is_err());
+        assert!(dictionary(b"<</K <<>>").is_err());
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            policy: DuplicateKeyPolicy,
+            expected: Option<Object>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "keep first",
+                input: b"<</K 1 /K 2>>",
+                policy: DuplicateKeyPolicy::KeepFirst,
+                expected: Some(Object::integer(1)),
+            },
+            TestCase {
+                name: "keep last",
+                input: b"<</K 1 /K 2>>",
+                policy: DuplicateKeyPolicy::KeepLast,
+                expected: Some(Object::integer(2)),
+            },
+            TestCase {
+                name: "error",
+                input: b"<</K 1 /K 2>>",
+                policy: DuplicateKeyPolicy::Error,
+                expected: None,
+            },
+            TestCase {
+                name: "error in nested dictionary",
+                input: b"<</K << /A 1 /A 2 >> >>",
+                policy: DuplicateKeyPolicy::Error,
+                expected: None,
+            },
+            TestCase {
+                name: "keep first in nested dictionary",
+                input: b"<</K [<< /A 1 /A 2 >>] >>",
+                policy: DuplicateKeyPolicy::KeepFirst,
+                expected: Some(Object::Array(
+                    [Object::Dictionary(Dictionary::from([(
+                        "A",
+                        Object::integer(1),
+                    )]))]
+                    .into(),
+                )),
+            },
+            TestCase {
+                name: "distinct keys under error",
+                input: b"<</K 1 /L 2>>",
+                policy: DuplicateKeyPolicy::Error,
+                expected: Some(Object::integer(1)),
+            },
+        ];
+
+        for case in &test_cases {
+            let result = dictionary_with_policy(case.input, case.policy);
+
+            match &case.expected {
+                Some(expected) => {
+                    let (remainder, dictionary) = result.expect(case.name);
+                    assert!(remainder.is_empty(), "Test '{}' failed", case.name);
+                    assert_eq!(
+                        dictionary.get("K"),
+                        Some(expected),
+                        "Test '{}' failed",
+                        case.name
+                    );
+                }
+                None => assert!(result.is_err(), "Test '{}' failed", case.name),
+            }
+        }
+
+        assert_eq!(
+            dictionary(b"<</K 1 /K 2>>").unwrap().1.get("K"),
+            Some(&Object::integer(2))
+        );
+    }
 }
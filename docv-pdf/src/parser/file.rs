@@ -1,9 +1,9 @@
 use nom::{
     Finish, IResult, ParseTo, Parser,
     branch::alt,
-    bytes::complete::{tag, take, take_until},
+    bytes::complete::{tag, take, take_until, take_while},
     character::complete::digit1,
-    combinator::{opt, recognize, value},
+    combinator::{recognize, value},
     error::Error,
     multi::many0,
     sequence::{delimited, preceded, separated_pair, terminated},
@@ -119,6 +119,31 @@ pub fn read_startxref(input: &[u8]) -> Result<(&[u8], u64), Error<&[u8]>> {
     .finish()
 }
 
+/// Same as [`read_startxref`], but accepts any whitespace around the offset.
+///
+/// Some writers put the offset on the same line as the keyword, which the
+/// strict parser rejects.
+///
+/// # Example
+/// ```text
+/// startxref 12345
+/// %%EOF
+/// ```
+pub fn read_startxref_lenient(input: &[u8]) -> Result<(&[u8], u64), Error<&[u8]>> {
+    let value = digit1.map_opt(|res: &[u8]| res.parse_to());
+
+    preceded(
+        take_until("startxref"),
+        delimited(
+            (tag("startxref"), whitespace),
+            value,
+            (whitespace, tag("%%EOF")),
+        ),
+    )
+    .parse(input)
+    .finish()
+}
+
 /// Parses either a cross-reference table or an object stream containing cross-references.
 ///
 /// PDF cross-references can be stored in two formats:
@@ -188,14 +213,17 @@ pub fn read_trailer(input: &[u8]) -> Result<(&[u8], Dictionary), Error<&[u8]>> {
 /// 2. A series of entries with format: `offset gen_id status`
 ///
 /// # Format
-/// Each entry is 20 bytes long:
+/// Each entry is nominally 20 bytes long:
 /// - Bytes 0-9: Offset (10-digit number)
 /// - Byte 10: Space
 /// - Bytes 11-15: Generation number (5-digit number)
 /// - Byte 16: Space
 /// - Byte 17: Status ('n' for in-use, 'f' for free)
-/// - Byte 18: Optional space
-/// - Bytes 19-20: Line ending
+/// - Bytes 18-19: Space and CR or LF, or CR and LF
+///
+/// Writers often get the line ending wrong, so any number of spaces
+/// followed by any end-of-line marker is accepted after the status and
+/// after subsection headers, making entries 19 to 21 bytes long.
 ///
 /// # Arguments
 /// * `input` - Byte slice to parse
@@ -211,10 +239,9 @@ fn xref_table(input: &[u8]) -> IResult<&[u8], Vec<XrefTableSection>> {
         take(5usize).map_opt(|res: &[u8]| res.parse_to()),
         value((), tag(" ")),
         alt((value(true, tag("n")), value(false, tag("f")))),
-        opt(tag(" ")),
-        eol,
+        line_end,
     )
-        .map(|(offset, _, gen_id, _, occupied, _, _)| XrefTableEntry {
+        .map(|(offset, _, gen_id, _, occupied, _)| XrefTableEntry {
             offset,
             gen_id,
             occupied,
@@ -227,7 +254,7 @@ fn xref_table(input: &[u8]) -> IResult<&[u8], Vec<XrefTableSection>> {
                 tag(" "),
                 digit1.map_opt(|res: &[u8]| res.parse_to()),
             ),
-            eol,
+            line_end,
         ),
         many0(entry),
     )
@@ -240,6 +267,11 @@ fn xref_table(input: &[u8]) -> IResult<&[u8], Vec<XrefTableSection>> {
     preceded((tag("xref"), eol), many0(subsection)).parse(input)
 }
 
+/// Parses padding spaces followed by an end-of-line marker.
+fn line_end(input: &[u8]) -> IResult<&[u8], ()> {
+    value((), preceded(take_while(|c| c == b' '), eol)).parse(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -530,6 +562,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_startxref_layouts() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            strict: Option<u64>,
+            lenient: Option<u64>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "offset on next line",
+                input: b"startxref\n12345\n%%EOF",
+                strict: Some(12345),
+                lenient: Some(12345),
+            },
+            TestCase {
+                name: "offset on next line with CRLF",
+                input: b"startxref\r\n12345\r\n%%EOF",
+                strict: Some(12345),
+                lenient: Some(12345),
+            },
+            TestCase {
+                name: "offset on same line",
+                input: b"startxref 12345\n%%EOF",
+                strict: None,
+                lenient: Some(12345),
+            },
+            TestCase {
+                name: "everything on one line",
+                input: b"startxref 12345 %%EOF",
+                strict: None,
+                lenient: Some(12345),
+            },
+            TestCase {
+                name: "offset glued to keyword",
+                input: b"startxref12345\n%%EOF",
+                strict: None,
+                lenient: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let strict = read_startxref(case.input).ok().map(|(_, offset)| offset);
+            assert_eq!(
+                strict, case.strict,
+                "Test '{}' failed: wrong strict result",
+                case.name
+            );
+
+            let lenient = read_startxref_lenient(case.input)
+                .ok()
+                .map(|(_, offset)| offset);
+            assert_eq!(
+                lenient, case.lenient,
+                "Test '{}' failed: wrong lenient result",
+                case.name
+            );
+        }
+    }
+
     #[test]
     fn test_xref_table_parser() {
         #[derive(Debug)]
@@ -568,6 +661,46 @@ mod tests {
                 expected_entries: Some(1),
                 expected_remainder: Some(b"trailer"),
             },
+            TestCase {
+                name: "entry ending with space and LF",
+                input: b"xref\n0 2\n0000000000 65535 f \n0000000010 00000 n \ntrailer",
+                expected: true,
+                expected_sections: Some(1),
+                expected_entries: Some(2),
+                expected_remainder: Some(b"trailer"),
+            },
+            TestCase {
+                name: "entry ending with CR LF",
+                input: b"xref\r\n0 2\r\n0000000000 65535 f\r\n0000000010 00000 n\r\ntrailer",
+                expected: true,
+                expected_sections: Some(1),
+                expected_entries: Some(2),
+                expected_remainder: Some(b"trailer"),
+            },
+            TestCase {
+                name: "entry ending with space and CR LF",
+                input: b"xref\n0 2\n0000000000 65535 f \r\n0000000010 00000 n \r\ntrailer",
+                expected: true,
+                expected_sections: Some(1),
+                expected_entries: Some(2),
+                expected_remainder: Some(b"trailer"),
+            },
+            TestCase {
+                name: "entry ending with bare LF",
+                input: b"xref\n0 2\n0000000000 65535 f\n0000000010 00000 n\ntrailer",
+                expected: true,
+                expected_sections: Some(1),
+                expected_entries: Some(2),
+                expected_remainder: Some(b"trailer"),
+            },
+            TestCase {
+                name: "padded subsection header and entries",
+                input: b"xref\n0 2 \n0000000000 65535 f  \n0000000010 00000 n  \r\ntrailer",
+                expected: true,
+                expected_sections: Some(1),
+                expected_entries: Some(2),
+                expected_remainder: Some(b"trailer"),
+            },
             // Invalid xref tables
             TestCase {
                 name: "invalid missing xref keyword",
@@ -697,6 +830,26 @@ mod tests {
                 )])),
                 expected_remainder: Some(b"startxref"),
             },
+            TestCase {
+                name: "valid trailer with comment before dictionary",
+                input: b"trailer\n%comment\n<< /Size 5 >>",
+                expected: true,
+                expected_dict: Some(Dictionary::from([(
+                    "Size",
+                    Object::Numeric(Numeric::Integer(5)),
+                )])),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "valid trailer with several comments before dictionary",
+                input: b"trailer %a\r\n%\n  % b\r<< /Size 5 >>",
+                expected: true,
+                expected_dict: Some(Dictionary::from([(
+                    "Size",
+                    Object::Numeric(Numeric::Integer(5)),
+                )])),
+                expected_remainder: Some(b""),
+            },
             // Invalid trailers
             TestCase {
                 name: "invalid missing trailer keyword",
@@ -3,16 +3,17 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::digit1,
+    combinator::{eof, peek, value},
     multi::many0,
     sequence::{delimited, terminated},
 };
 
 use crate::{
     parser::{
-        object::object,
+        object::object_with_policy,
         whitespace::{comment, eol, whitespace},
     },
-    types::{IndirectObject, IndirectReference},
+    types::{DuplicateKeyPolicy, IndirectObject, IndirectReference, Object},
 };
 
 /// Parses a PDF indirect object from the input.
@@ -32,20 +33,79 @@ use crate::{
 /// - Remaining input after parsing
 /// - Parsed `IndirectObject` on success
 pub fn indirect_object(input: &[u8]) -> IResult<&[u8], IndirectObject> {
+    indirect_object_with_policy(input, DuplicateKeyPolicy::default())
+}
+
+/// Parses a PDF indirect object, see [`crate::parser::dictionary::dictionary_with_policy`]
+/// for `policy`.
+pub fn indirect_object_with_policy(
+    input: &[u8],
+    policy: DuplicateKeyPolicy,
+) -> IResult<&[u8], IndirectObject> {
+    let contents = |input| contents(input, policy);
+
+    (object_header, terminated(contents, tag("endobj")))
+        .map(|((id, gen_id), object)| IndirectObject::new(id, gen_id, object))
+        .parse(input)
+}
+
+/// Parses a PDF indirect object whose `endobj` keyword may be missing.
+///
+/// Some writers omit `endobj` and start the next object right away. Without
+/// `endobj` the object has to be followed by the next `N G obj`, `xref`,
+/// `trailer` or `startxref` keyword or the end of input, which is not
+/// consumed.
+///
+/// # Arguments
+/// * `input` - Byte slice to parse
+/// * `policy` - How repeated dictionary keys are read
+///
+/// # Returns
+/// `IResult` containing:
+/// - Remaining input after parsing
+/// - Parsed `IndirectObject` on success
+pub fn indirect_object_lenient(
+    input: &[u8],
+    policy: DuplicateKeyPolicy,
+) -> IResult<&[u8], IndirectObject> {
+    let contents = |input| contents(input, policy);
+    let end = alt((value((), tag("endobj")), value((), peek(object_end))));
+
+    (object_header, terminated(contents, end))
+        .map(|((id, gen_id), object)| IndirectObject::new(id, gen_id, object))
+        .parse(input)
+}
+
+/// Object and generation numbers followed by the `obj` keyword.
+fn object_header(input: &[u8]) -> IResult<&[u8], (usize, usize)> {
     let id =
         terminated(digit1, many0(alt((whitespace, comment, eol)))).map_opt(|res| res.parse_to());
     let gen_id =
         terminated(digit1, many0(alt((whitespace, comment, eol)))).map_opt(|res| res.parse_to());
 
-    let contents = delimited(
+    terminated((id, gen_id), tag("obj")).parse(input)
+}
+
+/// Object between `obj` and `endobj`, including surrounding whitespace.
+fn contents(input: &[u8], policy: DuplicateKeyPolicy) -> IResult<&[u8], Object> {
+    delimited(
         many0(alt((whitespace, comment, eol))),
-        object,
+        |input| object_with_policy(input, policy),
         many0(alt((whitespace, comment, eol))),
-    );
+    )
+    .parse(input)
+}
 
-    (id, gen_id, delimited(tag("obj"), contents, tag("endobj")))
-        .map(|(id, gen_id, object)| IndirectObject::new(id, gen_id, object))
-        .parse(input)
+/// Start of whatever may follow an indirect object in a file.
+fn object_end(input: &[u8]) -> IResult<&[u8], ()> {
+    alt((
+        value((), object_header),
+        value((), tag("xref")),
+        value((), tag("trailer")),
+        value((), tag("startxref")),
+        value((), eof),
+    ))
+    .parse(input)
 }
 
 /// Parses a PDF indirect object reference from the input.
@@ -74,7 +134,7 @@ pub fn indirect_reference(input: &[u8]) -> IResult<&[u8], IndirectReference> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Numeric, Object};
+    use crate::types::Numeric;
     use nom::error::dbg_dmp;
 
     #[test]
@@ -108,7 +168,7 @@ mod tests {
                 expected_id: Some(2),
                 expected_gen_id: Some(0),
                 expected_object: Some(Object::String(crate::types::PdfString::Literal(
-                    String::from_utf8(b"Hello".to_vec()).unwrap(),
+                    b"Hello".to_vec(),
                 ))),
                 expected_remainder: Some(b""),
             },
@@ -221,6 +281,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_indirect_object_lenient_parser() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            expected: Option<(usize, Object, &'static [u8])>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "with endobj",
+                input: b"1 0 obj true endobj 2 0 obj",
+                expected: Some((1, Object::Boolean(true), b" 2 0 obj")),
+            },
+            TestCase {
+                name: "followed by next object",
+                input: b"1 0 obj << /A 1 >>\n2 0 obj null endobj",
+                expected: Some((
+                    1,
+                    Object::Dictionary([("A", Object::integer(1))].into()),
+                    b"2 0 obj null endobj",
+                )),
+            },
+            TestCase {
+                name: "followed by xref",
+                input: b"3 0 obj 42\nxref",
+                expected: Some((3, Object::integer(42), b"xref")),
+            },
+            TestCase {
+                name: "followed by trailer",
+                input: b"4 0 obj (text) trailer",
+                expected: Some((4, Object::string("text"), b"trailer")),
+            },
+            TestCase {
+                name: "at end of input",
+                input: b"5 0 obj null\n",
+                expected: Some((5, Object::Null, b"")),
+            },
+            TestCase {
+                name: "followed by garbage",
+                input: b"6 0 obj true garbage",
+                expected: None,
+            },
+            TestCase {
+                name: "missing object content",
+                input: b"7 0 obj 8 0 obj",
+                expected: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let result = indirect_object_lenient(case.input, DuplicateKeyPolicy::default());
+            match (&result, &case.expected) {
+                (Ok((remainder, actual)), Some((id, object, expected_remainder))) => {
+                    assert_eq!(actual.id, *id, "Test '{}' failed: wrong id", case.name);
+                    assert_eq!(
+                        **actual, *object,
+                        "Test '{}' failed: wrong object",
+                        case.name
+                    );
+                    assert_eq!(
+                        remainder, expected_remainder,
+                        "Test '{}' failed: wrong remainder",
+                        case.name
+                    );
+                }
+                (Err(_), None) => {}
+                _ => panic!("Test '{}' failed: got {:?}", case.name, result),
+            }
+        }
+    }
+
     #[test]
     fn test_indirect_reference_parser() {
         #[derive(Debug)]
@@ -40,7 +40,7 @@ pub fn name(input: &[u8]) -> IResult<&[u8], Name> {
 
             String::from_utf8(result).map_err(|_| Error::new(input, ErrorKind::Fail))
         })
-        .map(|res| Name::from(res));
+        .map(Name::from);
 
     preceded(tag("/"), name).parse(input)
 }
@@ -192,7 +192,7 @@ mod test {
         ];
 
         for case in &test_cases {
-            let result = dbg_dmp(name, "name").parse(&case.input);
+            let result = dbg_dmp(name, "name").parse(case.input);
             assert_eq!(
                 result.is_ok(),
                 case.expected,
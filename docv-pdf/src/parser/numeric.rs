@@ -1,8 +1,10 @@
 use nom::{
     IResult, ParseTo, Parser,
-    bytes::complete::tag,
+    branch::alt,
+    bytes::complete::{tag, take_while},
     character::complete::{digit0, digit1, one_of},
     combinator::{opt, recognize},
+    multi::many0,
     sequence::preceded,
 };
 
@@ -11,11 +13,16 @@ use crate::types::Numeric;
 /// Parses a numeric value from a byte slice as defined in the PDF 2.0 standard.
 ///
 /// This parser supports both integer and real number formats, including negative values
-/// and optional decimal points. It consumes input until the end of the number.
+/// and optional decimal points. Digits may be omitted on either side of the
+/// point, as in `-.20` or `4.`, but not on both. It consumes input until the
+/// end of the number.
 pub fn numeric(input: &[u8]) -> IResult<&[u8], Numeric> {
     let (remaining, num_str) = recognize(preceded(
         opt(one_of("+-")),
-        (opt((digit0, tag("."))), digit1),
+        alt((
+            recognize((digit1, opt((tag("."), digit0)))),
+            recognize((tag("."), digit1)),
+        )),
     ))
     .parse(input)?;
 
@@ -32,6 +39,38 @@ pub fn numeric(input: &[u8]) -> IResult<&[u8], Numeric> {
     }
 }
 
+/// Parses a numeric value, recovering from the malformed numbers some
+/// producers write.
+///
+/// The recovery rule:
+/// - A run of signs before the digits counts as a single sign, which is `-`
+///   if any sign of the run is `-`: `--5` and `+-5` are `-5`, `++5` is `5`.
+/// - Everything from a second decimal point up to the end of the digits
+///   following it is consumed and ignored: `1.2.3` is `1.2`, `4..5` is `4.`.
+///
+/// Input that has no digit after the signs, such as `-` or `--.`, is still
+/// an error. Well-formed numbers are read exactly as by [`numeric`].
+pub fn numeric_lenient(input: &[u8]) -> IResult<&[u8], Numeric> {
+    let (remaining, signs) = take_while(|c| matches!(c, b'+' | b'-')).parse(input)?;
+    let (remaining, number) = recognize(alt((
+        recognize((digit1, opt((tag("."), digit0)))),
+        recognize((tag("."), digit1)),
+    )))
+    .parse(remaining)?;
+    let (remaining, _) = if number.contains(&b'.') {
+        many0((tag("."), digit0)).parse(remaining)?
+    } else {
+        (remaining, Vec::new())
+    };
+
+    let sign: &[u8] = if signs.contains(&b'-') { b"-" } else { b"" };
+    let (_, numeric) = numeric(&[sign, number].concat()).map_err(|_| {
+        nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Float))
+    })?;
+
+    Ok((remaining, numeric))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -126,8 +165,29 @@ mod test {
                 expected_remainder: None,
             },
             TestCase {
-                name: "invalid '12.' (no digits after decimal)",
+                name: "valid '12.' (no digits after decimal)",
                 input: b"12.",
+                expected: true,
+                expected_result: Some(Numeric::Real(12.0)),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "valid negative real number '-.20'",
+                input: b"-.20",
+                expected: true,
+                expected_result: Some(Numeric::Real(-0.2)),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "invalid lone decimal point '.'",
+                input: b".",
+                expected: false,
+                expected_result: None,
+                expected_remainder: None,
+            },
+            TestCase {
+                name: "invalid sign followed by decimal point '-.'",
+                input: b"-.",
                 expected: false,
                 expected_result: None,
                 expected_remainder: None,
@@ -182,4 +242,89 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_numeric_recovery() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            strict: Option<(Numeric, &'static [u8])>,
+            lenient: Option<(Numeric, &'static [u8])>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "well-formed number",
+                input: b"-12.5 ",
+                strict: Some((Numeric::Real(-12.5), b" ")),
+                lenient: Some((Numeric::Real(-12.5), b" ")),
+            },
+            TestCase {
+                name: "double minus",
+                input: b"--5 l",
+                strict: None,
+                lenient: Some((Numeric::Integer(-5), b" l")),
+            },
+            TestCase {
+                name: "double plus",
+                input: b"++5",
+                strict: None,
+                lenient: Some((Numeric::Integer(5), b"")),
+            },
+            TestCase {
+                name: "mixed signs",
+                input: b"+-.5",
+                strict: None,
+                lenient: Some((Numeric::Real(-0.5), b"")),
+            },
+            TestCase {
+                name: "second decimal point",
+                input: b"1.2.3 m",
+                strict: Some((Numeric::Real(1.2), b".3 m")),
+                lenient: Some((Numeric::Real(1.2), b" m")),
+            },
+            TestCase {
+                name: "repeated decimal points",
+                input: b"4..5.",
+                strict: Some((Numeric::Real(4.0), b".5.")),
+                lenient: Some((Numeric::Real(4.0), b"")),
+            },
+            TestCase {
+                name: "integer followed by a sign",
+                input: b"7-3",
+                strict: Some((Numeric::Integer(7), b"-3")),
+                lenient: Some((Numeric::Integer(7), b"-3")),
+            },
+            TestCase {
+                name: "signs without digits",
+                input: b"-- ",
+                strict: None,
+                lenient: None,
+            },
+            TestCase {
+                name: "signs and point without digits",
+                input: b"--.",
+                strict: None,
+                lenient: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let strict = numeric(case.input).ok().map(|(rest, value)| (value, rest));
+            assert_eq!(
+                strict, case.strict,
+                "Test '{}' failed: wrong strict result",
+                case.name
+            );
+
+            let lenient = numeric_lenient(case.input)
+                .ok()
+                .map(|(rest, value)| (value, rest));
+            assert_eq!(
+                lenient, case.lenient,
+                "Test '{}' failed: wrong lenient result",
+                case.name
+            );
+        }
+    }
 }
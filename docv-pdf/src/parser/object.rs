@@ -1,20 +1,85 @@
-use nom::{Finish, IResult, Parser, branch::alt, error::Error};
+use nom::{
+    Finish, IResult, Parser,
+    branch::alt,
+    combinator::all_consuming,
+    error::Error,
+    multi::many0,
+    sequence::{preceded, terminated},
+};
 
 use crate::{
     parser::{
-        array::array,
+        array::array_with_policy,
         boolean::boolean,
-        dictionary::dictionary,
-        indirect_object::{indirect_object, indirect_reference},
+        dictionary::dictionary_with_policy,
+        indirect_object::{
+            indirect_object_lenient, indirect_object_with_policy, indirect_reference,
+        },
         name::name,
         null::null,
         numeric::numeric,
-        stream::stream,
+        stream::stream_with_policy,
         string::pdf_string,
+        whitespace::{comment, eol, whitespace},
     },
-    types::Object,
+    types::{DuplicateKeyPolicy, Object},
 };
 
+/// Measures the PDF object at the start of the input.
+///
+/// # Arguments
+/// * `input` - Byte slice to parse
+///
+/// # Returns
+/// Number of bytes taken by the object, for indirect objects up to and
+/// including `endobj`
+pub fn object_length(input: &[u8]) -> Result<usize, Error<&[u8]>> {
+    let (remainder, _) = object(input).finish()?;
+
+    Ok(input.len() - remainder.len())
+}
+
+/// Parses a sequence of PDF objects separated by whitespace or comments.
+///
+/// # Arguments
+/// * `input` - Byte slice to parse
+///
+/// # Returns
+/// `Result` containing the objects in input order, or `Error` if some part
+/// of the input is not an object
+pub fn read_objects(input: &[u8]) -> Result<Vec<Object>, Error<&[u8]>> {
+    let separators = || many0(alt((whitespace, comment, eol)));
+
+    let (_, objects) = all_consuming(terminated(
+        many0(preceded(separators(), object)),
+        separators(),
+    ))
+    .parse(input)
+    .finish()?;
+
+    Ok(objects)
+}
+
+/// Parses an indirect object that may lack the `endobj` keyword.
+///
+/// See [`indirect_object_lenient`] for where such an object ends.
+///
+/// # Arguments
+/// * `input` - Byte slice to parse
+/// * `policy` - How repeated dictionary keys are read
+///
+/// # Returns
+/// `Result` containing the parsed [`Object::IndirectDefinition`], or `Error`
+/// if the input doesn't start with an indirect object
+pub fn read_indirect_object_lenient(
+    input: &[u8],
+    policy: DuplicateKeyPolicy,
+) -> Result<Object, Error<&[u8]>> {
+    let (_, object) = indirect_object_lenient(input, policy).finish()?;
+
+    Ok(Object::IndirectDefinition(object))
+}
+
 /// Parses a PDF object from the input.
 ///
 /// Attempts to parse any of the fundamental PDF object types:
@@ -34,7 +99,24 @@ use crate::{
 /// # Returns
 /// `IResult` containing remaining input and parsed [`Object`] on success
 pub fn read_object(input: &[u8]) -> Result<Object, Error<&[u8]>> {
-    let (_, object) = object(input).finish()?;
+    read_object_with_policy(input, DuplicateKeyPolicy::default())
+}
+
+/// Parses a PDF object from the input, reading repeated dictionary keys
+/// according to `policy`.
+///
+/// # Arguments
+/// * `input` - Byte slice to parse
+/// * `policy` - How repeated dictionary keys are read
+///
+/// # Returns
+/// `Result` containing the parsed [`Object`], or `Error` if the input doesn't
+/// start with an object or `policy` rejects one of its dictionaries
+pub fn read_object_with_policy(
+    input: &[u8],
+    policy: DuplicateKeyPolicy,
+) -> Result<Object, Error<&[u8]>> {
+    let (_, object) = object_with_policy(input, policy).finish()?;
 
     Ok(object)
 }
@@ -58,12 +140,18 @@ pub fn read_object(input: &[u8]) -> Result<Object, Error<&[u8]>> {
 /// # Returns
 /// `IResult` containing remaining input and parsed [`Object`] on success
 pub fn object(input: &[u8]) -> IResult<&[u8], Object> {
+    object_with_policy(input, DuplicateKeyPolicy::default())
+}
+
+/// Parses a PDF object, see [`crate::parser::dictionary::dictionary_with_policy`]
+/// for `policy`.
+pub fn object_with_policy(input: &[u8], policy: DuplicateKeyPolicy) -> IResult<&[u8], Object> {
     alt((
-        indirect_object.map(Object::IndirectDefinition),
+        (|input| indirect_object_with_policy(input, policy)).map(Object::IndirectDefinition),
         indirect_reference.map(Object::IndirectReference),
-        stream.map(Object::Stream),
-        dictionary.map(Object::Dictionary),
-        array.map(Object::Array),
+        (|input| stream_with_policy(input, policy)).map(Object::Stream),
+        (|input| dictionary_with_policy(input, policy)).map(Object::Dictionary),
+        (|input| array_with_policy(input, policy)).map(Object::Array),
         numeric.map(Object::Numeric),
         pdf_string.map(Object::String),
         name.map(Object::Name),
@@ -115,9 +203,40 @@ mod tests {
             },
             TestCase {
                 name: "real numeric",
-                input: b"3.14",
+                input: b"2.75",
+                expected: true,
+                expected_value: Some(Object::Numeric(Numeric::Real(2.75))),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "real numeric without integer part",
+                input: b"-.20",
                 expected: true,
-                expected_value: Some(Object::Numeric(Numeric::Real(3.14))),
+                expected_value: Some(Object::Numeric(Numeric::Real(-0.2))),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "real numeric without fractional part",
+                input: b"4.",
+                expected: true,
+                expected_value: Some(Object::Numeric(Numeric::Real(4.0))),
+                expected_remainder: Some(b""),
+            },
+            TestCase {
+                name: "matrix with short reals",
+                input: b"[1. 0 0 -.5 .25 4.]",
+                expected: true,
+                expected_value: Some(Object::Array(
+                    vec![
+                        Object::Numeric(Numeric::Real(1.0)),
+                        Object::Numeric(Numeric::Integer(0)),
+                        Object::Numeric(Numeric::Integer(0)),
+                        Object::Numeric(Numeric::Real(-0.5)),
+                        Object::Numeric(Numeric::Real(0.25)),
+                        Object::Numeric(Numeric::Real(4.0)),
+                    ]
+                    .into(),
+                )),
                 expected_remainder: Some(b""),
             },
             // String tests
@@ -125,9 +244,7 @@ mod tests {
                 name: "literal string",
                 input: b"(Hello World)",
                 expected: true,
-                expected_value: Some(Object::String(PdfString::Literal(
-                    "Hello World".to_string(),
-                ))),
+                expected_value: Some(Object::String(PdfString::Literal("Hello World".into()))),
                 expected_remainder: Some(b""),
             },
             TestCase {
@@ -177,7 +294,7 @@ mod tests {
                 expected: true,
                 expected_value: Some(Object::Dictionary(Dictionary::from([(
                     "Key",
-                    Object::String(PdfString::Literal("Value".to_string())),
+                    Object::String(PdfString::Literal("Value".into())),
                 )]))),
                 expected_remainder: Some(b""),
             },
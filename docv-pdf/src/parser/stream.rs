@@ -8,10 +8,10 @@ use nom::{
 
 use crate::{
     parser::{
-        dictionary::dictionary,
+        dictionary::dictionary_with_policy,
         whitespace::{comment, eol, whitespace},
     },
-    types::Stream,
+    types::{DuplicateKeyPolicy, Stream},
 };
 
 /// Parses a PDF stream object from the input.
@@ -45,6 +45,12 @@ use crate::{
 ///   1. Iterator over stream dictionary records
 ///   2. Raw stream content bytes (data between `stream` and `endstream`)
 pub fn stream(input: &[u8]) -> IResult<&[u8], Stream> {
+    stream_with_policy(input, DuplicateKeyPolicy::default())
+}
+
+/// Parses a PDF stream object, see [`crate::parser::dictionary::dictionary_with_policy`]
+/// for `policy`.
+pub fn stream_with_policy(input: &[u8], policy: DuplicateKeyPolicy) -> IResult<&[u8], Stream> {
     let raw_data = take_until("endstream").map(|res: &[u8]| -> &[u8] {
         if res.len() >= 2 && res.ends_with(b"\r\n") {
             return &res[..(res.len() - 2)];
@@ -65,7 +71,10 @@ pub fn stream(input: &[u8]) -> IResult<&[u8], Stream> {
     );
 
     (
-        terminated(dictionary, many0(alt((whitespace, comment, eol)))),
+        terminated(
+            |input| dictionary_with_policy(input, policy),
+            many0(alt((whitespace, comment, eol))),
+        ),
         content,
     )
         .map(|(dictionary, data)| Stream {
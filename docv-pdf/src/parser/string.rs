@@ -2,7 +2,7 @@ use nom::{
     AsChar, IResult, Parser,
     branch::alt,
     bytes::complete::{tag, take_while, take_while_m_n, take_while1},
-    combinator::{recognize, value},
+    combinator::value,
     multi::fold,
     sequence::{delimited, preceded},
 };
@@ -21,18 +21,28 @@ pub fn pdf_string(input: &[u8]) -> IResult<&[u8], PdfString> {
 
 /// Parses a literal string enclosed in parentheses, handling escape sequences and balanced parentheses.
 fn literal_string(input: &[u8]) -> IResult<&[u8], PdfString> {
+    literal_bytes.map(PdfString::Literal).parse(input)
+}
+
+/// Parses a literal string and returns its decoded content.
+///
+/// Nested strings are parsed recursively, so escapes inside them are
+/// decoded too and their balanced parentheses are kept.
+fn literal_bytes(input: &[u8]) -> IResult<&[u8], Vec<u8>> {
     #[derive(Debug)]
     enum Fragment<'a> {
         Literal(&'a [u8]),
         EscapedChar(u8),
         Whitespace,
-        InnerString(&'a [u8]),
+        InnerString(Vec<u8>),
     }
 
     let whitespace = preceded(tag("\\"), take_while1(is_whitespace)).map(|_| Fragment::Whitespace);
 
+    // High-order overflow of octal escapes is ignored, e.g. `\777` is 0xFF.
     let octal_char = take_while_m_n(1, 3, |c| matches!(c, b'0'..=b'7'))
-        .map_res(|res| u8::from_str_radix(str::from_utf8(res).unwrap(), 8));
+        .map_res(|res| u16::from_str_radix(str::from_utf8(res).unwrap(), 8))
+        .map(|value| value as u8);
 
     let escaped_char = preceded(
         tag("\\"),
@@ -56,7 +66,7 @@ fn literal_string(input: &[u8]) -> IResult<&[u8], PdfString> {
         literal,
         escaped_char,
         whitespace,
-        recognize(literal_string).map(Fragment::InnerString),
+        literal_bytes.map(Fragment::InnerString),
     ));
 
     let final_str = fold(0.., content, Vec::new, |mut data, fragment| {
@@ -64,29 +74,16 @@ fn literal_string(input: &[u8]) -> IResult<&[u8], PdfString> {
             Fragment::Literal(chunk) => data.extend_from_slice(chunk),
             Fragment::EscapedChar(c) => data.push(c),
             Fragment::Whitespace => {}
-            Fragment::InnerString(inner) => data.extend_from_slice(inner),
+            Fragment::InnerString(inner) => {
+                data.push(b'(');
+                data.extend_from_slice(&inner);
+                data.push(b')');
+            }
         }
         data
     });
 
-    delimited(tag("("), final_str, tag(")"))
-        .map(|mut data: Vec<u8>| {
-            if data.starts_with(&[0xfe, 0xff]) {
-                if !data.len().is_multiple_of(2) {
-                    data.push(0);
-                }
-
-                let data = data
-                    .chunks_exact(2)
-                    .map(|chunk: &[u8]| (chunk[0] as u16) << 8 | (chunk[1] as u16))
-                    .collect::<Vec<u16>>();
-
-                return PdfString::Literal(String::from_utf16_lossy(&data));
-            }
-
-            PdfString::Literal(String::from_utf8_lossy(&data).to_string())
-        })
-        .parse(input)
+    delimited(tag("("), final_str, tag(")")).parse(input)
 }
 
 /// Parses a hexadecimal string enclosed in angle brackets, ignoring non-hex characters.
@@ -122,6 +119,11 @@ mod test {
     use nom::error::dbg_dmp;
 
     use super::*;
+    use crate::{
+        objects::Strictness,
+        parser::{parse_content_stream, read_object},
+        types::{ContentOp, Object},
+    };
 
     #[test]
     fn test_string_parser() {
@@ -140,67 +142,63 @@ mod test {
                 name: "valid simple literal string",
                 input: b"(hello)",
                 expected: true,
-                expected_result: Some(PdfString::Literal("hello".to_string())),
+                expected_result: Some(PdfString::Literal("hello".into())),
                 expected_remainder: Some(b""),
             },
             TestCase {
                 name: "valid literal with escape sequences",
                 input: b"(hello\\nworld\\r\\t\\b\\f\\(\\)\\\\\\12)",
                 expected: true,
-                expected_result: Some(PdfString::Literal(
-                    "hello\nworld\r\t\x08\x0C()\\\n".to_string(),
-                )),
+                expected_result: Some(PdfString::Literal("hello\nworld\r\t\x08\x0C()\\\n".into())),
                 expected_remainder: Some(b""),
             },
             TestCase {
                 name: "valid literal with whitespace escape",
                 input: b"(hello\\ world)",
                 expected: true,
-                expected_result: Some(PdfString::Literal("helloworld".to_string())),
+                expected_result: Some(PdfString::Literal("helloworld".into())),
                 expected_remainder: Some(b""),
             },
             TestCase {
                 name: "valid literal with nested parentheses",
                 input: b"(hello (nested) world)",
                 expected: true,
-                expected_result: Some(PdfString::Literal("hello (nested) world".to_string())),
+                expected_result: Some(PdfString::Literal("hello (nested) world".into())),
                 expected_remainder: Some(b""),
             },
             TestCase {
                 name: "valid literal with octal escape",
                 input: b"(\\101)",
                 expected: true,
-                expected_result: Some(PdfString::Literal("A".to_string())),
+                expected_result: Some(PdfString::Literal("A".into())),
                 expected_remainder: Some(b""),
             },
             TestCase {
                 name: "valid literal with multiple octal escapes",
                 input: b"(\\101\\102\\103)",
                 expected: true,
-                expected_result: Some(PdfString::Literal("ABC".to_string())),
+                expected_result: Some(PdfString::Literal("ABC".into())),
                 expected_remainder: Some(b""),
             },
             TestCase {
                 name: "valid literal with mixed content",
                 input: b"(Mix\\055ed\\040Content)",
                 expected: true,
-                expected_result: Some(PdfString::Literal("Mix-ed Content".to_string())),
+                expected_result: Some(PdfString::Literal("Mix-ed Content".into())),
                 expected_remainder: Some(b""),
             },
             TestCase {
                 name: "valid empty literal string",
                 input: b"()",
                 expected: true,
-                expected_result: Some(PdfString::Literal("".to_string())),
+                expected_result: Some(PdfString::Literal("".into())),
                 expected_remainder: Some(b""),
             },
             TestCase {
                 name: "valid string with BOM marker",
                 input: b"(\\357\\273\\277D:20211230134641+11'00')",
                 expected: true,
-                expected_result: Some(PdfString::Literal(
-                    "\u{FEFF}D:20211230134641+11'00'".to_string(),
-                )),
+                expected_result: Some(PdfString::Literal("\u{FEFF}D:20211230134641+11'00'".into())),
                 expected_remainder: Some(b""),
             },
             // Valid hexadecimal strings
@@ -251,7 +249,7 @@ mod test {
                 name: "literal with remainder",
                 input: b"(hello)world",
                 expected: true,
-                expected_result: Some(PdfString::Literal("hello".to_string())),
+                expected_result: Some(PdfString::Literal("hello".into())),
                 expected_remainder: Some(b"world"),
             },
             TestCase {
@@ -338,4 +336,100 @@ mod test {
             }
         }
     }
+
+    /// Strings are parsed by `pdf_string` directly and through the object and
+    /// content stream parsers, all paths have to decode them the same way.
+    #[test]
+    fn test_string_corpus() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            expected: PdfString,
+        }
+
+        let literal = |text: &str| PdfString::Literal(text.into());
+        let test_cases = [
+            TestCase {
+                name: "octal escapes of one to three digits",
+                input: b"(\\5\\53\\053\\0053)",
+                expected: literal("\x05++\x053"),
+            },
+            TestCase {
+                name: "octal escape with high-order overflow",
+                input: b"(\\501)",
+                expected: literal("A"),
+            },
+            TestCase {
+                name: "nested parentheses",
+                input: b"(a (b (c)) d)",
+                expected: literal("a (b (c)) d"),
+            },
+            TestCase {
+                name: "escapes inside nested parentheses",
+                input: b"(a (b\\)c\\101) d)",
+                expected: literal("a (b)cA) d"),
+            },
+            TestCase {
+                name: "escaped parenthesis next to nested string",
+                input: b"(\\((x)\\))",
+                expected: literal("((x))"),
+            },
+            TestCase {
+                name: "line continuation",
+                input: b"(one\\\ntwo)",
+                expected: literal("onetwo"),
+            },
+            TestCase {
+                name: "non-UTF-8 bytes in literal string",
+                input: b"(\\377\\000\xE9)",
+                expected: PdfString::Literal(vec![0xFF, 0x00, 0xE9]),
+            },
+            TestCase {
+                name: "UTF-16BE literal string",
+                input: b"(\\376\\377\\000A)",
+                expected: PdfString::Literal(vec![0xFE, 0xFF, 0x00, b'A']),
+            },
+            TestCase {
+                name: "whitespace in hex string",
+                input: b"<48 65\n6C\t6C\r\n6F>",
+                expected: PdfString::Hexadecimal(b"Hello".to_vec()),
+            },
+            TestCase {
+                name: "odd-length hex string",
+                input: b"<48 6>",
+                expected: PdfString::Hexadecimal(vec![0x48, 0x60]),
+            },
+        ];
+
+        for case in test_cases {
+            let (remainder, result) = pdf_string(case.input).unwrap();
+            assert!(
+                remainder.is_empty(),
+                "Test '{}' failed: input left",
+                case.name
+            );
+            assert_eq!(
+                result, case.expected,
+                "Test '{}' failed: pdf_string result differs",
+                case.name
+            );
+
+            let object = read_object(case.input).unwrap();
+            assert_eq!(
+                object,
+                Object::String(case.expected.clone()),
+                "Test '{}' failed: object parser result differs",
+                case.name
+            );
+
+            let content = [case.input, b" Tj"].concat();
+            let operations = parse_content_stream(&content, Strictness::default()).unwrap();
+            assert_eq!(
+                operations,
+                [ContentOp::new("Tj", vec![Object::String(case.expected)])],
+                "Test '{}' failed: content stream parser result differs",
+                case.name
+            );
+        }
+    }
 }
@@ -1,10 +1,7 @@
 use nom::{
     IResult, Parser,
     branch::alt,
-    bytes::{
-        complete::{tag, take_while1},
-        is_not,
-    },
+    bytes::complete::{tag, take_till, take_while1},
     combinator::value,
     sequence::delimited,
 };
@@ -48,9 +45,13 @@ pub fn eol(input: &[u8]) -> IResult<&[u8], ()> {
 /// Parses PDF comments starting with `%` and ending at the first EOL.
 ///
 /// Returns `Ok` with an empty tuple if a comment is found, otherwise `Err`.
-/// Consumes the `%` and comment content, leaving the EOL character(s) in the remainder.
+/// Consumes the `%`, the comment content (which may be empty) and the terminating EOL.
 pub fn comment(input: &[u8]) -> IResult<&[u8], ()> {
-    value((), delimited(tag("%"), is_not("\x0D\x0A"), eol)).parse(input)
+    value(
+        (),
+        delimited(tag("%"), take_till(|c| c == b'\x0D' || c == b'\x0A'), eol),
+    )
+    .parse(input)
 }
 
 #[cfg(test)]
@@ -279,6 +280,12 @@ mod tests {
                 expected: false,
                 expected_remainder: b"%",
             },
+            TestCase {
+                name: "comment without text",
+                input: b"%\r\n<<",
+                expected: true,
+                expected_remainder: b"<<",
+            },
             TestCase {
                 name: "multiline comment",
                 input: b"% line 1\n% line 2",
@@ -0,0 +1,48 @@
+use crate::structures::page::Page;
+
+/// Owned, read-only copy of a document's pages, see [`crate::Document::snapshot`].
+///
+/// Every page is fully loaded, so nothing needs the object store anymore
+/// and the snapshot can be shared between threads. Page methods like
+/// [`Page::extract_text`] only take `&self`.
+///
+/// # Example
+/// ```no_run
+/// # use std::path::PathBuf;
+/// # use docv_pdf::Document;
+/// let mut document = Document::from_path(&PathBuf::from("report.pdf")).unwrap();
+/// let snapshot = document.snapshot().unwrap();
+///
+/// let texts = std::thread::scope(|scope| {
+///     let handles = snapshot
+///         .pages()
+///         .iter()
+///         .map(|page| scope.spawn(move || page.extract_text()))
+///         .collect::<Vec<_>>();
+///
+///     handles
+///         .into_iter()
+///         .map(|handle| handle.join().unwrap())
+///         .collect::<Vec<_>>()
+/// });
+/// ```
+#[derive(Debug)]
+pub struct DocumentSnapshot {
+    pages: Vec<Page>,
+}
+
+impl DocumentSnapshot {
+    pub(crate) fn new(pages: Vec<Page>) -> Self {
+        Self { pages }
+    }
+
+    /// Pages in document order.
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    /// Number of pages.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+}
@@ -1,6 +1,16 @@
+pub mod acroform;
+pub mod color_space;
+pub mod destination;
+pub mod embedded_file;
+pub mod encryption;
 pub mod hash;
+pub mod image;
 pub mod info;
+pub mod name_tree;
 pub mod object_stream;
 pub mod page;
 pub mod root;
+pub mod signature;
+pub mod validation;
+pub mod xmp;
 pub mod xref;
@@ -0,0 +1,102 @@
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    objects::Objects,
+    types::{Dictionary, Object},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Document-wide settings of the interactive form (`/AcroForm`).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AcroFormInfo {
+    /// Appearance streams of the fields must be regenerated before rendering (`/NeedAppearances`)
+    pub need_appearances: bool,
+    /// Resources shared by the appearances of the fields (`/DR`)
+    pub default_resources: Option<Dictionary>,
+    /// Number of top-level fields in `/Fields`, their kids aren't counted
+    pub fields_count: usize,
+    /// Signature flags (`/SigFlags`), bit 1 `SignaturesExist` and bit 2 `AppendOnly`
+    pub sig_flags: i64,
+}
+
+impl AcroFormInfo {
+    /// Whether the document has at least one signature field.
+    pub const SIGNATURES_EXIST: i64 = 1;
+    /// Whether the document must only be saved with incremental updates.
+    pub const APPEND_ONLY: i64 = 2;
+
+    /// Reads the interactive form dictionary.
+    ///
+    /// # Arguments
+    /// * `object` - Value of the catalog's `/AcroForm` entry
+    /// * `objects` - Object store used to resolve indirect references
+    pub fn from_object(object: &Object, objects: &mut Objects) -> Result<Self> {
+        let object = object.direct(objects).into_owned();
+        let dictionary = object
+            .as_dictionary()
+            .context(error::InvalidField { field: "AcroForm" })?;
+
+        let need_appearances = dictionary
+            .get("NeedAppearances")
+            .map(|object| object.direct(objects).as_bool())
+            .transpose()
+            .context(error::InvalidField {
+                field: "NeedAppearances",
+            })?
+            .unwrap_or(false);
+
+        let default_resources = dictionary
+            .get("DR")
+            .map(|object| object.direct(objects).as_dictionary().cloned())
+            .transpose()
+            .context(error::InvalidField { field: "DR" })?;
+
+        let fields_count = match dictionary.get("Fields") {
+            Some(fields) => {
+                let fields = match fields.direct(objects).into_owned() {
+                    Object::IndirectDefinition(definition) => (*definition).clone(),
+                    object => object,
+                };
+                match fields {
+                    Object::Array(fields) => fields.len(),
+                    object => return Err(error::Error::InvalidFields { object }.into()),
+                }
+            }
+            None => 0,
+        };
+
+        let sig_flags = dictionary
+            .get("SigFlags")
+            .map(|object| object.direct(objects).as_integer())
+            .transpose()
+            .context(error::InvalidField { field: "SigFlags" })?
+            .unwrap_or(0);
+
+        Ok(Self {
+            need_appearances,
+            default_resources,
+            fields_count,
+            sig_flags,
+        })
+    }
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("`Fields` is not an array. Got = {object:?}"))]
+        InvalidFields { object: crate::types::Object },
+    }
+}
@@ -0,0 +1,372 @@
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
+
+use crate::{
+    objects::Objects,
+    types::{Object, Stream},
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(source(from(error::Error, Box::new)))]
+pub struct Error(Box<error::Error>);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Color spaces can nest through `/Alternate` and `/Indexed` bases, but
+/// never this deep in a valid file.
+const MAX_DEPTH: usize = 8;
+
+/// Color space of image samples or content stream color operators.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorSpace {
+    DeviceGray,
+    DeviceRGB,
+    DeviceCMYK,
+    CalGray,
+    CalRGB,
+    Lab,
+    /// ICC profile stream with `n` components
+    ICCBased {
+        n: usize,
+        /// Space to use if the profile can't be applied (`/Alternate`)
+        alternate: Option<Box<ColorSpace>>,
+    },
+    /// Palette of `hival + 1` colors in `base`, each stored as consecutive components
+    Indexed {
+        base: Box<ColorSpace>,
+        hival: u8,
+        lookup: Vec<u8>,
+    },
+    /// Single colorant, tints are converted to `alternate` by a function
+    Separation {
+        name: String,
+        alternate: Box<ColorSpace>,
+    },
+    /// Several colorants, tints are converted to `alternate` by a function
+    DeviceN {
+        names: Vec<String>,
+        alternate: Box<ColorSpace>,
+    },
+    /// Pattern, with the space of the color components for uncolored patterns
+    Pattern {
+        base: Option<Box<ColorSpace>>,
+    },
+}
+
+impl ColorSpace {
+    /// Resolves a color space given as a family name or an array.
+    ///
+    /// Indirect references, the `/Indexed` lookup table and the `/ICCBased`
+    /// profile stream are loaded from `objects`.
+    pub fn from_object(object: &Object, objects: &mut Objects) -> Result<Self> {
+        Self::resolve(object, objects, 0)
+    }
+
+    /// Resolves one of the families that can be used without parameters.
+    ///
+    /// Content streams can name these directly in `cs` and `CS` instead of
+    /// going through the `/ColorSpace` resources.
+    ///
+    /// # Returns
+    /// `None` if `name` isn't such a family
+    pub fn from_name(name: &str) -> Option<Self> {
+        let color_space = match name {
+            "DeviceGray" | "G" => ColorSpace::DeviceGray,
+            "DeviceRGB" | "RGB" => ColorSpace::DeviceRGB,
+            "DeviceCMYK" | "CMYK" => ColorSpace::DeviceCMYK,
+            "Pattern" => ColorSpace::Pattern { base: None },
+            _ => return None,
+        };
+
+        Some(color_space)
+    }
+
+    /// Number of color components of a single sample.
+    pub fn components(&self) -> usize {
+        match self {
+            ColorSpace::DeviceGray | ColorSpace::CalGray => 1,
+            ColorSpace::DeviceRGB | ColorSpace::CalRGB | ColorSpace::Lab => 3,
+            ColorSpace::DeviceCMYK => 4,
+            ColorSpace::ICCBased { n, .. } => *n,
+            ColorSpace::Indexed { .. } | ColorSpace::Separation { .. } => 1,
+            ColorSpace::DeviceN { names, .. } => names.len(),
+            ColorSpace::Pattern { base } => base.as_ref().map_or(0, |base| base.components()),
+        }
+    }
+
+    fn resolve(object: &Object, objects: &mut Objects, depth: usize) -> Result<Self> {
+        ensure!(depth < MAX_DEPTH, error::TooDeep);
+
+        let object = match object.direct(objects).into_owned() {
+            Object::IndirectDefinition(definition) => (*definition).clone(),
+            object => object,
+        };
+
+        let (family, parameters) = match &object {
+            Object::Array(array) => {
+                let (family, parameters) = array.split_first().context(error::Empty)?;
+                (family.as_name().context(error::InvalidFamily)?, parameters)
+            }
+            object => (object.as_name().context(error::InvalidFamily)?, &[][..]),
+        };
+        let parameter = |index: usize| {
+            parameters.get(index).context(error::MissingParameter {
+                family: family.as_str(),
+                index,
+            })
+        };
+
+        let color_space = match family.as_str() {
+            "DeviceGray" | "G" => ColorSpace::DeviceGray,
+            "DeviceRGB" | "RGB" => ColorSpace::DeviceRGB,
+            "DeviceCMYK" | "CMYK" => ColorSpace::DeviceCMYK,
+            "CalGray" => ColorSpace::CalGray,
+            "CalRGB" => ColorSpace::CalRGB,
+            "Lab" => ColorSpace::Lab,
+            "ICCBased" => {
+                let stream = parameter(0)?.direct(objects).into_owned();
+                let stream = stream
+                    .as_stream()
+                    .context(error::InvalidParameter { field: "ICCBased" })?;
+                let n = stream
+                    .dictionary
+                    .get("N")
+                    .context(error::FieldNotFound { field: "N" })?
+                    .direct(objects)
+                    .as_integer()
+                    .context(error::InvalidParameter { field: "N" })?;
+                let alternate = stream
+                    .dictionary
+                    .get("Alternate")
+                    .map(|object| Self::resolve(object, objects, depth + 1))
+                    .transpose()?
+                    .map(Box::new);
+
+                ColorSpace::ICCBased { n, alternate }
+            }
+            "Indexed" | "I" => {
+                let base = Self::resolve(parameter(0)?, objects, depth + 1)?;
+                let hival = parameter(1)?
+                    .direct(objects)
+                    .as_integer()
+                    .context(error::InvalidParameter { field: "hival" })?;
+                let lookup = match parameter(2)?.direct(objects).into_owned() {
+                    Object::String(string) => string.as_bytes().to_vec(),
+                    object => {
+                        let mut stream: Stream = object
+                            .as_stream()
+                            .context(error::InvalidParameter { field: "lookup" })?
+                            .clone();
+                        objects
+                            .decode_budget()
+                            .process_filters(&mut stream)
+                            .context(error::Lookup)?;
+                        stream.data
+                    }
+                };
+
+                ColorSpace::Indexed {
+                    base: Box::new(base),
+                    hival,
+                    lookup,
+                }
+            }
+            "Separation" => {
+                let name = parameter(0)?
+                    .as_name()
+                    .context(error::InvalidParameter { field: "name" })?
+                    .to_string();
+                let alternate = Self::resolve(parameter(1)?, objects, depth + 1)?;
+
+                ColorSpace::Separation {
+                    name,
+                    alternate: Box::new(alternate),
+                }
+            }
+            "DeviceN" => {
+                let names = parameter(0)?
+                    .direct(objects)
+                    .as_array()
+                    .of(|object| object.as_name().map(|name| name.to_string()))
+                    .context(error::InvalidNames)?;
+                let alternate = Self::resolve(parameter(1)?, objects, depth + 1)?;
+
+                ColorSpace::DeviceN {
+                    names,
+                    alternate: Box::new(alternate),
+                }
+            }
+            "Pattern" => {
+                let base = parameters
+                    .first()
+                    .map(|object| Self::resolve(object, objects, depth + 1))
+                    .transpose()?
+                    .map(Box::new);
+
+                ColorSpace::Pattern { base }
+            }
+            family => {
+                return Err(error::Error::UnknownFamily {
+                    family: family.to_string(),
+                }
+                .into());
+            }
+        };
+
+        Ok(color_space)
+    }
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Color space array is empty"))]
+        Empty,
+
+        #[snafu(display("Color space family isn't a name"))]
+        InvalidFamily { source: crate::types::object::Error },
+
+        #[snafu(display("Unknown color space family `{family}`"))]
+        UnknownFamily { family: String },
+
+        #[snafu(display("Color space `{family}` has no parameter {index}"))]
+        MissingParameter { family: String, index: usize },
+
+        #[snafu(display("Required field `{field}` not found"))]
+        FieldNotFound { field: &'static str },
+
+        #[snafu(display("Invalid color space parameter `{field}`"))]
+        InvalidParameter {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Invalid colorant names of `DeviceN` color space"))]
+        InvalidNames { source: crate::types::array::Error },
+
+        #[snafu(display("Failed to decode `Indexed` lookup stream"))]
+        Lookup { source: crate::types::stream::Error },
+
+        #[snafu(display("Color space nesting is too deep"))]
+        TooDeep,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Document,
+        test_utils::{PdfBuilder, minimal_document},
+        types::{IndirectReference, Name},
+    };
+
+    #[test]
+    fn test_color_space_resolution() {
+        let path = minimal_document()
+            .stream(3, "/N 3 /Alternate /DeviceRGB", b"profile")
+            .stream(4, "", b"\x00\x00\x00\xff\xff\xff")
+            .section("/Root 1 0 R")
+            .write("color-spaces");
+        let mut document = Document::from_path(&path).unwrap();
+        let objects = document.objects_mut();
+
+        let reference = |id| Object::IndirectReference(IndirectReference { id, gen_id: 0 });
+        let indexed = |base: Object, lookup: Object| {
+            Object::Array(
+                vec![
+                    Object::Name(Name::from("Indexed")),
+                    base,
+                    Object::integer(1),
+                    lookup,
+                ]
+                .into(),
+            )
+        };
+        let rgb = Object::Name(Name::from("DeviceRGB"));
+        let iccbased =
+            Object::Array(vec![Object::Name(Name::from("ICCBased")), reference(3)].into());
+
+        let test_cases = [
+            ("DeviceRGB name", rgb.clone(), Some(ColorSpace::DeviceRGB)),
+            (
+                "Indexed with string lookup",
+                indexed(rgb.clone(), Object::string("\x00\x00\x00\x7f\x7f\x7f")),
+                Some(ColorSpace::Indexed {
+                    base: Box::new(ColorSpace::DeviceRGB),
+                    hival: 1,
+                    lookup: b"\x00\x00\x00\x7f\x7f\x7f".to_vec(),
+                }),
+            ),
+            (
+                "Indexed with stream lookup",
+                indexed(rgb.clone(), reference(4)),
+                Some(ColorSpace::Indexed {
+                    base: Box::new(ColorSpace::DeviceRGB),
+                    hival: 1,
+                    lookup: b"\x00\x00\x00\xff\xff\xff".to_vec(),
+                }),
+            ),
+            (
+                "ICCBased",
+                iccbased.clone(),
+                Some(ColorSpace::ICCBased {
+                    n: 3,
+                    alternate: Some(Box::new(ColorSpace::DeviceRGB)),
+                }),
+            ),
+            (
+                "Indexed over ICCBased",
+                indexed(iccbased, Object::string("abcdef")),
+                Some(ColorSpace::Indexed {
+                    base: Box::new(ColorSpace::ICCBased {
+                        n: 3,
+                        alternate: Some(Box::new(ColorSpace::DeviceRGB)),
+                    }),
+                    hival: 1,
+                    lookup: b"abcdef".to_vec(),
+                }),
+            ),
+            ("unknown family", Object::Name(Name::from("Foo")), None),
+            ("missing lookup", indexed(rgb, Object::Null), None),
+            ("empty array", Object::Array(Vec::new().into()), None),
+        ];
+
+        for (name, object, expected) in test_cases {
+            let result = ColorSpace::from_object(&object, objects).ok();
+            assert_eq!(result, expected, "Test '{name}' failed");
+        }
+    }
+
+    #[test]
+    fn test_page_color_space() {
+        let path = PdfBuilder::new()
+            .object(1, "<< /Type /Catalog /Pages 2 0 R >>")
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] \
+                 /Resources << /ColorSpace << /CS0 [/Indexed /DeviceGray 1 <00ff>] >> >> >>",
+            )
+            .section("/Root 1 0 R")
+            .write("page-color-space");
+        let mut document = Document::from_path(&path).unwrap();
+        let page = document.pages().next().unwrap().unwrap();
+        let objects = document.objects_mut();
+
+        assert_eq!(
+            page.color_space("CS0", objects).unwrap(),
+            Some(ColorSpace::Indexed {
+                base: Box::new(ColorSpace::DeviceGray),
+                hival: 1,
+                lookup: vec![0x00, 0xff],
+            })
+        );
+        assert_eq!(
+            page.color_space("DeviceCMYK", objects).unwrap(),
+            Some(ColorSpace::DeviceCMYK)
+        );
+        assert_eq!(page.color_space("CS1", objects).unwrap(), None);
+    }
+}
@@ -0,0 +1,177 @@
+use snafu::{OptionExt, ResultExt, Snafu};
+
+use crate::{
+    objects::Objects,
+    types::{IndirectReference, Object},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Explicit destination: a page and how to show it (ISO 32000-2, 12.3.2.2).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Destination {
+    /// Target page
+    pub page: DestinationPage,
+    /// Position and zoom of the page in the window
+    pub view: DestinationView,
+}
+
+/// Page a destination points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestinationPage {
+    /// Page object of this document
+    Reference(IndirectReference),
+    /// Zero-based page number, used by destinations into other documents
+    Number(usize),
+}
+
+/// How the target page is positioned and zoomed.
+///
+/// `None` coordinates and zoom keep the current value of the viewer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DestinationView {
+    /// `/XYZ`: upper-left corner at `left`, `top` with the given zoom
+    Xyz {
+        left: Option<f64>,
+        top: Option<f64>,
+        zoom: Option<f64>,
+    },
+    /// `/Fit`: whole page fits the window
+    Fit,
+    /// `/FitH`: page width fits the window, `top` at the top edge
+    FitH { top: Option<f64> },
+    /// `/FitV`: page height fits the window, `left` at the left edge
+    FitV { left: Option<f64> },
+    /// `/FitR`: the rectangle fits the window
+    FitR {
+        left: f64,
+        bottom: f64,
+        right: f64,
+        top: f64,
+    },
+    /// `/FitB`: bounding box of the page contents fits the window
+    FitB,
+    /// `/FitBH`: width of the bounding box fits the window
+    FitBH { top: Option<f64> },
+    /// `/FitBV`: height of the bounding box fits the window
+    FitBV { left: Option<f64> },
+}
+
+impl Destination {
+    /// Reads an explicit destination array, e.g. `[3 0 R /XYZ 0 792 null]`.
+    ///
+    /// Dictionaries with a `/D` entry, as stored in named destination trees,
+    /// are accepted too.
+    ///
+    /// # Arguments
+    /// * `object` - Destination array or dictionary, or a reference to it
+    /// * `objects` - Object store used to resolve indirect references
+    pub fn from_object(object: &Object, objects: &mut Objects) -> Result<Self> {
+        let mut object = object.direct(objects).into_owned();
+        if let Ok(dictionary) = object.as_dictionary() {
+            let destination = dictionary
+                .get("D")
+                .context(error::FieldNotFound { field: "D" })?;
+            object = destination.direct(objects).into_owned();
+        }
+
+        let array = object.as_array().generic().context(error::InvalidArray)?;
+
+        let page = match array.first().context(error::MissingPage)? {
+            Object::IndirectReference(reference) => DestinationPage::Reference(*reference),
+            object => DestinationPage::Number(
+                object
+                    .as_integer()
+                    .context(error::InvalidField { field: "page" })?,
+            ),
+        };
+
+        let kind = array
+            .get(1)
+            .context(error::MissingView)?
+            .as_name()
+            .context(error::InvalidField { field: "view" })?;
+
+        // Missing trailing parameters are treated like `null`.
+        let parameter = |index: usize| -> Result<Option<f64>> {
+            match array.get(index + 2) {
+                None | Some(Object::Null) => Ok(None),
+                Some(object) => Ok(Some(
+                    object
+                        .as_float()
+                        .context(error::InvalidField { field: "view" })?,
+                )),
+            }
+        };
+        let required = |index: usize| -> Result<f64> {
+            Ok(parameter(index)?.context(error::MissingParameter { index })?)
+        };
+
+        let view = match kind.as_str() {
+            "XYZ" => DestinationView::Xyz {
+                left: parameter(0)?,
+                top: parameter(1)?,
+                // Zoom of 0 means "keep the current zoom", same as `null`.
+                zoom: parameter(2)?.filter(|zoom| *zoom != 0.0),
+            },
+            "Fit" => DestinationView::Fit,
+            "FitH" => DestinationView::FitH { top: parameter(0)? },
+            "FitV" => DestinationView::FitV {
+                left: parameter(0)?,
+            },
+            "FitR" => DestinationView::FitR {
+                left: required(0)?,
+                bottom: required(1)?,
+                right: required(2)?,
+                top: required(3)?,
+            },
+            "FitB" => DestinationView::FitB,
+            "FitBH" => DestinationView::FitBH { top: parameter(0)? },
+            "FitBV" => DestinationView::FitBV {
+                left: parameter(0)?,
+            },
+            _ => {
+                return Err(error::Error::UnexpectedView {
+                    value: kind.to_string(),
+                }
+                .into());
+            }
+        };
+
+        Ok(Self { page, view })
+    }
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Required field `{field}` not found"))]
+        FieldNotFound { field: &'static str },
+
+        #[snafu(display("Destination is not an array"))]
+        InvalidArray { source: crate::types::array::Error },
+
+        #[snafu(display("Destination has no page"))]
+        MissingPage,
+
+        #[snafu(display("Destination has no view type"))]
+        MissingView,
+
+        #[snafu(display("Destination is missing parameter {index}"))]
+        MissingParameter { index: usize },
+
+        #[snafu(display("Invalid object type for destination {field}"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Unexpected destination view. Got = `{value}`"))]
+        UnexpectedView { value: String },
+    }
+}
@@ -0,0 +1,175 @@
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    objects::Objects,
+    types::{Dictionary, IndirectReference, Object, string::Date},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// File attached to the document, described by a file specification dictionary.
+///
+/// The same dictionary is used by the `/EmbeddedFiles` name tree and by the
+/// `/FS` entry of `FileAttachment` annotations.
+#[derive(Debug, Clone)]
+pub struct EmbeddedFile {
+    /// Key of the entry in the `/EmbeddedFiles` name tree
+    pub key: String,
+    /// File name for display, `/UF` if present and `/F` otherwise
+    pub filename: String,
+    /// Description of the file (`/Desc`)
+    pub description: Option<String>,
+    /// Uncompressed size in bytes (`/Params /Size` of the embedded file stream)
+    pub size: Option<usize>,
+    /// Creation date (`/Params /CreationDate` of the embedded file stream)
+    pub creation_date: Option<Date>,
+    /// Embedded file stream (`/EF /UF` or `/EF /F`)
+    pub stream: Option<IndirectReference>,
+}
+
+impl EmbeddedFile {
+    /// Reads a file specification dictionary.
+    ///
+    /// # Arguments
+    /// * `key` - Name under which the file is listed
+    /// * `file_spec` - File specification dictionary or a reference to it
+    /// * `objects` - Object store used to resolve indirect references
+    pub fn from_file_spec(key: String, file_spec: &Object, objects: &mut Objects) -> Result<Self> {
+        let file_spec = file_spec.direct(objects).into_owned();
+        let file_spec = file_spec
+            .as_dictionary()
+            .context(error::InvalidField { field: "FS" })?;
+
+        let filename = ["UF", "F"]
+            .into_iter()
+            .find_map(|field| Some((field, file_spec.get(field)?)));
+        let filename = match filename {
+            Some((field, object)) => object
+                .direct(objects)
+                .as_string()
+                .context(error::InvalidField { field })?
+                .to_text_string(),
+            None => key.clone(),
+        };
+
+        let description = file_spec
+            .get("Desc")
+            .map(|object| -> Result<String> {
+                let text = object
+                    .direct(objects)
+                    .as_string()
+                    .context(error::InvalidField { field: "Desc" })?
+                    .to_text_string();
+
+                Ok(text)
+            })
+            .transpose()?;
+
+        let stream = file_spec
+            .get("EF")
+            .map(|object| object.direct(objects).into_owned())
+            .map(|object| -> Result<Option<IndirectReference>> {
+                let files = object
+                    .as_dictionary()
+                    .context(error::InvalidField { field: "EF" })?;
+
+                Ok(files
+                    .get("UF")
+                    .or_else(|| files.get("F"))
+                    .and_then(|stream| stream.as_indirect_ref().ok())
+                    .copied())
+            })
+            .transpose()?
+            .flatten();
+
+        let params = match stream {
+            Some(stream) => read_params(&stream, objects)?,
+            None => None,
+        };
+
+        let size = params
+            .as_ref()
+            .and_then(|params| params.get("Size"))
+            .map(|object| object.as_integer())
+            .transpose()
+            .context(error::InvalidField { field: "Size" })?;
+
+        let creation_date = params
+            .as_ref()
+            .and_then(|params| params.get("CreationDate"))
+            .map(|object| -> Result<Date> {
+                let date = object
+                    .as_string()
+                    .context(error::InvalidField {
+                        field: "CreationDate",
+                    })?
+                    .to_date()
+                    .context(error::InvalidDate)?;
+
+                Ok(date)
+            })
+            .transpose()?;
+
+        Ok(Self {
+            key,
+            filename,
+            description,
+            size,
+            creation_date,
+            stream,
+        })
+    }
+}
+
+/// Reads the `/Params` dictionary of an embedded file stream.
+fn read_params(stream: &IndirectReference, objects: &mut Objects) -> Result<Option<Dictionary>> {
+    let object = objects
+        .get_object(stream)
+        .context(error::Object { object: *stream })?;
+    let stream = object
+        .as_stream()
+        .context(error::InvalidField { field: "EF" })?;
+
+    let params = stream
+        .dictionary
+        .get("Params")
+        .map(|params| -> Result<Dictionary> {
+            let params = params
+                .direct(objects)
+                .as_dictionary()
+                .cloned()
+                .context(error::InvalidField { field: "Params" })?;
+
+            Ok(params)
+        })
+        .transpose()?;
+
+    Ok(params)
+}
+
+mod error {
+    use snafu::Snafu;
+
+    use crate::types::IndirectReference;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Invalid creation date"))]
+        InvalidDate { source: crate::types::string::Error },
+
+        #[snafu(display("Failed to get embedded file stream {object}"))]
+        Object {
+            object: IndirectReference,
+            source: crate::objects::Error,
+        },
+    }
+}
@@ -0,0 +1,164 @@
+use snafu::{OptionExt, ResultExt, Snafu};
+
+use crate::types::Dictionary;
+
+pub mod crypt;
+mod password;
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Summary of a document's encryption dictionary (`/Encrypt`).
+///
+/// Only describes the encryption, nothing is decrypted. For the standard
+/// security handler the empty user password is tried against `/U` to find
+/// out whether opening the document requires a password.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncryptionInfo {
+    /// Security handler name (`/Filter`), usually `Standard`
+    pub filter: String,
+    /// Algorithm version (`/V`)
+    pub v: i64,
+    /// Standard security handler revision (`/R`), `0` for other handlers
+    pub r: i64,
+    /// Length of the encryption key in bits
+    pub key_length: usize,
+    /// Whether a non-empty user password is needed to open the document
+    pub has_user_password: bool,
+    /// User access permission flags (`/P`)
+    pub permissions: i64,
+}
+
+impl EncryptionInfo {
+    /// Reads encryption parameters from the `/Encrypt` dictionary.
+    ///
+    /// # Arguments
+    /// * `dictionary` - The encryption dictionary
+    /// * `file_id` - First element of the trailer `/ID` array, used by revisions 2-4
+    pub fn from_dictionary(dictionary: &Dictionary, file_id: Option<&[u8]>) -> Result<Self> {
+        let filter = dictionary
+            .get("Filter")
+            .context(error::FieldNotFound { field: "Filter" })?
+            .as_name()
+            .context(error::InvalidField { field: "Filter" })?
+            .to_string();
+
+        let v = dictionary
+            .get_integer("V")
+            .transpose()
+            .context(error::InvalidField { field: "V" })?
+            .unwrap_or(0);
+
+        let r = dictionary
+            .get_integer("R")
+            .transpose()
+            .context(error::InvalidField { field: "R" })?
+            .unwrap_or(0);
+
+        let permissions = dictionary
+            .get_integer("P")
+            .transpose()
+            .context(error::InvalidField { field: "P" })?
+            .unwrap_or(0);
+
+        let key_length = match v {
+            4 => crypt_filter_length(dictionary)?.unwrap_or(128),
+            5 => 256,
+            2 | 3 => dictionary
+                .get_integer("Length")
+                .transpose()
+                .context(error::InvalidField { field: "Length" })?
+                .unwrap_or(40),
+            _ => 40,
+        };
+
+        let has_user_password = if filter == "Standard" {
+            let owner = string_field(dictionary, "O")?;
+            let user = string_field(dictionary, "U")?;
+            let encrypt_metadata = dictionary
+                .get_bool("EncryptMetadata")
+                .transpose()
+                .context(error::InvalidField {
+                    field: "EncryptMetadata",
+                })?
+                .unwrap_or(true);
+
+            let parameters = password::Parameters {
+                revision: r,
+                key_length,
+                owner,
+                user,
+                permissions: permissions as i32,
+                file_id: file_id.unwrap_or_default(),
+                encrypt_metadata,
+            };
+
+            !parameters.authenticate_user(b"")
+        } else {
+            false
+        };
+
+        Ok(Self {
+            filter,
+            v,
+            r,
+            key_length,
+            has_user_password,
+            permissions,
+        })
+    }
+}
+
+/// Key length of the crypt filter used for streams (`/CF /<StmF> /Length`) in bits.
+fn crypt_filter_length(dictionary: &Dictionary) -> Result<Option<usize>> {
+    let Some(name) = dictionary
+        .get("StmF")
+        .map(|object| object.as_name())
+        .transpose()
+        .context(error::InvalidField { field: "StmF" })?
+    else {
+        return Ok(None);
+    };
+
+    let length = dictionary
+        .get("CF")
+        .and_then(|object| object.as_dictionary().ok())
+        .and_then(|filters| filters.get(name.as_str()))
+        .and_then(|object| object.as_dictionary().ok())
+        .and_then(|filter| filter.get("Length"))
+        .map(|object| object.as_integer::<usize>())
+        .transpose()
+        .context(error::InvalidField { field: "Length" })?;
+
+    // Writers disagree on whether this length is in bytes or in bits.
+    Ok(length.map(|length| if length <= 32 { length * 8 } else { length }))
+}
+
+fn string_field<'a>(dictionary: &'a Dictionary, field: &'static str) -> Result<&'a [u8]> {
+    let data = dictionary
+        .get(field)
+        .context(error::FieldNotFound { field })?
+        .as_string()
+        .context(error::InvalidField { field })?
+        .as_bytes();
+
+    Ok(data)
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Required field `{field}` not found"))]
+        FieldNotFound { field: &'static str },
+
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+    }
+}
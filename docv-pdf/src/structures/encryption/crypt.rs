@@ -0,0 +1,476 @@
+//! Crypt filters of the standard security handler (ISO 32000-2, 7.6.5).
+//!
+//! From `/V 4` on the encryption dictionary names its crypt filters in
+//! `/CF`, and `/StmF` and `/StrF` select the one used for streams and
+//! strings. `/Identity` leaves data as is.
+
+use std::collections::BTreeMap;
+
+use aes::{
+    Aes128, Aes256,
+    cipher::{BlockDecrypt, BlockSizeUser, KeyInit, consts::U16, generic_array::GenericArray},
+};
+use md5::{Digest, Md5};
+use snafu::{OptionExt, ResultExt, Snafu};
+
+use crate::types::{Dictionary, IndirectReference, Stream};
+
+use super::password::rc4;
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Method of a crypt filter (`/CFM`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptMethod {
+    /// Data isn't encrypted (`/Identity` filter or `/CFM /None`)
+    Identity,
+    /// RC4 with a per-object key (`/V2`)
+    Rc4,
+    /// AES-128 in CBC mode with a per-object key (`/AESV2`)
+    AesV2,
+    /// AES-256 in CBC mode with the file key (`/AESV3`)
+    AesV3,
+}
+
+/// Crypt filter selection of an encryption dictionary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CryptFilters {
+    /// Method used for streams (`/StmF`)
+    pub streams: CryptMethod,
+    /// Method used for strings (`/StrF`)
+    pub strings: CryptMethod,
+    /// Whether metadata streams are encrypted (`/EncryptMetadata`)
+    pub encrypt_metadata: bool,
+    /// Every filter of `/CF` by name, including `Identity`
+    pub named: BTreeMap<String, CryptMethod>,
+}
+
+impl CryptFilters {
+    /// Reads the crypt filters of the encryption dictionary.
+    ///
+    /// Before `/V 4` there are no crypt filters and everything uses RC4.
+    /// A missing `/StmF` or `/StrF` selects `Identity`.
+    pub fn from_dictionary(dictionary: &Dictionary) -> Result<Self> {
+        let v = dictionary
+            .get_integer::<i64>("V")
+            .transpose()
+            .context(error::InvalidField { field: "V" })?
+            .unwrap_or(0);
+        let encrypt_metadata = dictionary
+            .get_bool("EncryptMetadata")
+            .transpose()
+            .context(error::InvalidField {
+                field: "EncryptMetadata",
+            })?
+            .unwrap_or(true);
+
+        let mut named = BTreeMap::from([("Identity".to_string(), CryptMethod::Identity)]);
+        if v < 4 {
+            return Ok(Self {
+                streams: CryptMethod::Rc4,
+                strings: CryptMethod::Rc4,
+                encrypt_metadata,
+                named,
+            });
+        }
+
+        if let Some(filters) = dictionary.get_dictionary("CF") {
+            let filters = filters.context(error::InvalidField { field: "CF" })?;
+            for (name, filter) in filters.iter() {
+                let filter = filter
+                    .as_dictionary()
+                    .context(error::InvalidField { field: "CF" })?;
+                let method = match filter.get_name("CFM") {
+                    Some(method) => {
+                        let method = method.context(error::InvalidField { field: "CFM" })?;
+                        match method {
+                            "None" => CryptMethod::Identity,
+                            "V2" => CryptMethod::Rc4,
+                            "AESV2" => CryptMethod::AesV2,
+                            "AESV3" => CryptMethod::AesV3,
+                            _ => {
+                                return Err(error::Error::UnsupportedMethod {
+                                    method: method.to_string(),
+                                }
+                                .into());
+                            }
+                        }
+                    }
+                    None => CryptMethod::Identity,
+                };
+
+                // `Identity` is reserved and can't be redefined.
+                if name != "Identity" {
+                    named.insert(name.to_string(), method);
+                }
+            }
+        }
+
+        let select = |field: &'static str| -> Result<CryptMethod> {
+            let Some(name) = dictionary.get_name(field) else {
+                return Ok(CryptMethod::Identity);
+            };
+            let name = name.context(error::InvalidField { field })?;
+
+            let method = *named.get(name).context(error::UnknownFilter {
+                name: name.to_string(),
+            })?;
+
+            Ok(method)
+        };
+
+        Ok(Self {
+            streams: select("StmF")?,
+            strings: select("StrF")?,
+            encrypt_metadata,
+            named,
+        })
+    }
+
+    /// Method used to decrypt `stream`.
+    ///
+    /// A `/Crypt` filter of the stream selects a filter of `/CF` in place of
+    /// `/StmF`, falling back to `/StmF` if it can't be read or isn't defined.
+    /// Metadata streams are left as is when `/EncryptMetadata` is false.
+    pub fn stream_method(&self, stream: &Stream) -> CryptMethod {
+        if let Ok(Some(name)) = stream.crypt_filter()
+            && let Some(method) = self.named.get(&name)
+        {
+            return *method;
+        }
+
+        let is_metadata = stream
+            .dictionary
+            .get_name("Type")
+            .and_then(|name| name.ok())
+            .is_some_and(|name| name == "Metadata");
+        if is_metadata && !self.encrypt_metadata {
+            return CryptMethod::Identity;
+        }
+
+        self.streams
+    }
+
+    /// Decrypts the data of `stream`, defined as object `reference`, in place.
+    ///
+    /// A `/Crypt` filter is removed from `/Filter` afterwards, so the stream
+    /// can be decoded with [`Stream::process_filters`].
+    ///
+    /// # Arguments
+    /// * `key` - File encryption key
+    /// * `reference` - Object the stream is defined as, used for the object key
+    /// * `stream` - Stream to decrypt
+    pub fn decrypt_stream(
+        &self,
+        key: &[u8],
+        reference: IndirectReference,
+        stream: &mut Stream,
+    ) -> Result<()> {
+        let method = self.stream_method(stream);
+        stream.data = decrypt(method, key, reference, &stream.data)?;
+        stream.remove_crypt_filter();
+
+        Ok(())
+    }
+
+    /// Decrypts a string of object `reference` with the `/StrF` filter.
+    pub fn decrypt_string(
+        &self,
+        key: &[u8],
+        reference: IndirectReference,
+        data: &[u8],
+    ) -> Result<Vec<u8>> {
+        decrypt(self.strings, key, reference, data)
+    }
+}
+
+/// Decrypts `data` of object `reference` with `method`.
+fn decrypt(
+    method: CryptMethod,
+    key: &[u8],
+    reference: IndirectReference,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    match method {
+        CryptMethod::Identity => Ok(data.to_vec()),
+        CryptMethod::Rc4 => Ok(rc4(&object_key(key, reference, false), data)),
+        CryptMethod::AesV2 => aes_cbc_decrypt::<Aes128>(&object_key(key, reference, true), data),
+        CryptMethod::AesV3 => aes_cbc_decrypt::<Aes256>(key, data),
+    }
+}
+
+/// Algorithm 1: key of a single object for RC4 and AES-128.
+fn object_key(key: &[u8], reference: IndirectReference, aes: bool) -> Vec<u8> {
+    let mut hasher = Md5::new();
+    hasher.update(key);
+    hasher.update(&(reference.id as u32).to_le_bytes()[..3]);
+    hasher.update((reference.gen_id as u16).to_le_bytes());
+    if aes {
+        hasher.update(b"sAlT");
+    }
+
+    let length = (key.len() + 5).min(16);
+    hasher.finalize()[..length].to_vec()
+}
+
+/// AES in CBC mode, the first block of `data` is the initialization vector.
+///
+/// The PKCS#5 padding of the last block is removed. Some writers encrypt
+/// empty strings as the initialization vector alone, which gives an empty
+/// result.
+fn aes_cbc_decrypt<C>(key: &[u8], data: &[u8]) -> Result<Vec<u8>>
+where
+    C: BlockDecrypt + BlockSizeUser<BlockSize = U16> + KeyInit,
+{
+    if data.len() < 16 || !data.len().is_multiple_of(16) {
+        return Err(error::Error::InvalidCiphertext { length: data.len() }.into());
+    }
+
+    let cipher = C::new_from_slice(key)
+        .ok()
+        .context(error::InvalidKey { length: key.len() })?;
+
+    let (iv, data) = data.split_at(16);
+    let mut previous = GenericArray::<u8, U16>::clone_from_slice(iv);
+    let mut result = Vec::with_capacity(data.len());
+    for chunk in data.chunks_exact(16) {
+        let mut block = GenericArray::<u8, U16>::clone_from_slice(chunk);
+        cipher.decrypt_block(&mut block);
+        block
+            .iter_mut()
+            .zip(previous.iter())
+            .for_each(|(byte, prev)| *byte ^= prev);
+
+        result.extend_from_slice(&block);
+        previous = GenericArray::clone_from_slice(chunk);
+    }
+
+    let padding = *result.last().unwrap_or(&0) as usize;
+    if (1..=16).contains(&padding) {
+        result.truncate(result.len() - padding);
+    }
+
+    Ok(result)
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Unsupported crypt filter method `{method}`"))]
+        UnsupportedMethod { method: String },
+
+        #[snafu(display("Crypt filter `{name}` isn't defined in `CF`"))]
+        UnknownFilter { name: String },
+
+        #[snafu(display("Invalid AES key length {length}"))]
+        InvalidKey { length: usize },
+
+        #[snafu(display("Invalid AES encrypted data length {length}"))]
+        InvalidCiphertext { length: usize },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{Compression, write::ZlibEncoder};
+
+    use aes::cipher::BlockEncrypt;
+
+    use super::*;
+    use crate::{parser::read_object, types::Object};
+
+    fn dictionary(input: &str) -> Dictionary {
+        let Object::Dictionary(dictionary) = read_object(input.as_bytes()).unwrap() else {
+            panic!("not a dictionary: {input}");
+        };
+
+        dictionary
+    }
+
+    #[test]
+    fn test_crypt_filter_selection() {
+        struct TestCase {
+            name: &'static str,
+            encrypt: &'static str,
+            stream: &'static str,
+            expected: CryptMethod,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "RC4 before crypt filters",
+                encrypt: "<< /V 2 /R 3 >>",
+                stream: "<< >>",
+                expected: CryptMethod::Rc4,
+            },
+            TestCase {
+                name: "standard AES filter",
+                encrypt: "<< /V 4 /CF << /StdCF << /CFM /AESV2 >> >> /StmF /StdCF /StrF /StdCF >>",
+                stream: "<< >>",
+                expected: CryptMethod::AesV2,
+            },
+            TestCase {
+                name: "identity stream filter",
+                encrypt: "<< /V 4 /CF << /StdCF << /CFM /AESV2 >> >> /StmF /Identity /StrF /StdCF >>",
+                stream: "<< >>",
+                expected: CryptMethod::Identity,
+            },
+            TestCase {
+                name: "missing stream filter",
+                encrypt: "<< /V 5 /CF << /StdCF << /CFM /AESV3 >> >> /StrF /StdCF >>",
+                stream: "<< >>",
+                expected: CryptMethod::Identity,
+            },
+            TestCase {
+                name: "unencrypted metadata",
+                encrypt: "<< /V 4 /CF << /StdCF << /CFM /V2 >> >> /StmF /StdCF /EncryptMetadata false >>",
+                stream: "<< /Type /Metadata /Subtype /XML >>",
+                expected: CryptMethod::Identity,
+            },
+            TestCase {
+                name: "encrypted metadata",
+                encrypt: "<< /V 4 /CF << /StdCF << /CFM /V2 >> >> /StmF /StdCF >>",
+                stream: "<< /Type /Metadata /Subtype /XML >>",
+                expected: CryptMethod::Rc4,
+            },
+            TestCase {
+                name: "identity crypt filter of the stream",
+                encrypt: "<< /V 4 /CF << /StdCF << /CFM /AESV2 >> >> /StmF /StdCF /StrF /StdCF >>",
+                stream: "<< /Filter /Crypt /DecodeParms << /Name /Identity >> >>",
+                expected: CryptMethod::Identity,
+            },
+            TestCase {
+                name: "crypt filter without name",
+                encrypt: "<< /V 4 /CF << /StdCF << /CFM /AESV2 >> >> /StmF /StdCF >>",
+                stream: "<< /Filter [/Crypt /FlateDecode] >>",
+                expected: CryptMethod::Identity,
+            },
+            TestCase {
+                name: "named crypt filter of the stream",
+                encrypt: "<< /V 4 /CF << /StdCF << /CFM /AESV2 >> >> /StmF /Identity >>",
+                stream: "<< /Filter [/Crypt /FlateDecode] /DecodeParms [<< /Name /StdCF >> null] >>",
+                expected: CryptMethod::AesV2,
+            },
+            TestCase {
+                name: "undefined crypt filter of the stream",
+                encrypt: "<< /V 4 /CF << /StdCF << /CFM /V2 >> >> /StmF /StdCF >>",
+                stream: "<< /Filter /Crypt /DecodeParms << /Name /Missing >> >>",
+                expected: CryptMethod::Rc4,
+            },
+        ];
+
+        for case in test_cases {
+            let filters = CryptFilters::from_dictionary(&dictionary(case.encrypt)).unwrap();
+            let stream = Stream::new(dictionary(case.stream), Vec::new());
+            assert_eq!(
+                filters.stream_method(&stream),
+                case.expected,
+                "Test '{}' failed",
+                case.name
+            );
+        }
+
+        let unknown = dictionary("<< /V 4 /StmF /Missing >>");
+        assert!(CryptFilters::from_dictionary(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_identity_stream_untouched() {
+        let reference = IndirectReference { id: 7, gen_id: 0 };
+        let key = b"\x01\x02\x03\x04\x05";
+        let data = b"BT /F1 12 Tf (Hello) Tj ET".to_vec();
+
+        let identity = CryptFilters::from_dictionary(&dictionary(
+            "<< /V 4 /CF << /StdCF << /CFM /V2 >> >> /StmF /Identity /StrF /StdCF >>",
+        ))
+        .unwrap();
+        let mut stream = Stream::new(Dictionary::default(), data.clone());
+        identity
+            .decrypt_stream(key, reference, &mut stream)
+            .unwrap();
+        assert_eq!(stream.data, data);
+
+        // RC4 is symmetric, decrypting twice gives the original data back.
+        let rc4 = CryptFilters::from_dictionary(&dictionary(
+            "<< /V 4 /CF << /StdCF << /CFM /V2 >> >> /StmF /StdCF >>",
+        ))
+        .unwrap();
+        rc4.decrypt_stream(key, reference, &mut stream).unwrap();
+        assert_ne!(stream.data, data);
+        rc4.decrypt_stream(key, reference, &mut stream).unwrap();
+        assert_eq!(stream.data, data);
+    }
+
+    #[test]
+    fn test_named_crypt_filter() {
+        let reference = IndirectReference { id: 7, gen_id: 0 };
+        let key = b"\x01\x02\x03\x04\x05";
+        let filters = CryptFilters::from_dictionary(&dictionary(
+            "<< /V 4 /CF << /StdCF << /CFM /V2 >> >> /StmF /Identity >>",
+        ))
+        .unwrap();
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"BT (Hello) Tj ET").unwrap();
+        let encrypted =
+            decrypt(CryptMethod::Rc4, key, reference, &encoder.finish().unwrap()).unwrap();
+        let mut stream = Stream::new(
+            dictionary(
+                "<< /Filter [/Crypt /FlateDecode] /DecodeParms [<< /Name /StdCF >> null] >>",
+            ),
+            encrypted,
+        );
+
+        let error = format!("{:?}", stream.clone().process_filters().unwrap_err());
+        assert!(error.contains("UnsupportedCryptFilter"), "got {error}");
+
+        filters.decrypt_stream(key, reference, &mut stream).unwrap();
+        assert_eq!(stream.crypt_filter().unwrap(), None);
+        stream.process_filters().unwrap();
+        assert_eq!(stream.data, b"BT (Hello) Tj ET");
+    }
+
+    #[test]
+    fn test_aes_short_data() {
+        let key = [7u8; 16];
+        let iv = [3u8; 16];
+
+        // Empty string: a single block of padding, chained with the IV.
+        let mut padding = GenericArray::from([16u8 ^ 3; 16]);
+        Aes128::new_from_slice(&key)
+            .unwrap()
+            .encrypt_block(&mut padding);
+
+        let test_cases = [
+            ("initialization vector only", iv.to_vec(), true),
+            (
+                "empty string with padding block",
+                [iv.as_slice(), padding.as_slice()].concat(),
+                true,
+            ),
+            ("shorter than a block", vec![0; 15], false),
+            ("partial block", vec![0; 20], false),
+        ];
+
+        for (name, data, expected) in test_cases {
+            let result = aes_cbc_decrypt::<Aes128>(&key, &data);
+            assert_eq!(result.is_ok(), expected, "Test '{name}' failed: {result:?}");
+            if let Ok(result) = result {
+                assert!(result.is_empty(), "Test '{name}' failed: got {result:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! User password checks of the standard security handler (ISO 32000-2, 7.6.4).
+
+use aes::{
+    Aes128,
+    cipher::{BlockEncrypt, KeyInit, generic_array::GenericArray},
+};
+use md5::{Digest, Md5};
+use sha2::{Sha256, Sha384, Sha512};
+
+/// Padding string used to extend passwords to 32 bytes (revisions 2-4).
+const PADDING: [u8; 32] = [
+    0x28, 0xBF, 0x4E, 0x5E, 0x4E, 0x75, 0x8A, 0x41, 0x64, 0x00, 0x4E, 0x56, 0xFF, 0xFA, 0x01, 0x08,
+    0x2E, 0x2E, 0x00, 0xB6, 0xD0, 0x68, 0x3E, 0x80, 0x2F, 0x0C, 0xA9, 0xFE, 0x64, 0x53, 0x69, 0x7A,
+];
+
+/// Values of the encryption dictionary needed to authenticate a password.
+#[derive(Debug)]
+pub struct Parameters<'a> {
+    pub revision: i64,
+    /// Key length in bits
+    pub key_length: usize,
+    pub owner: &'a [u8],
+    pub user: &'a [u8],
+    pub permissions: i32,
+    pub file_id: &'a [u8],
+    pub encrypt_metadata: bool,
+}
+
+impl Parameters<'_> {
+    /// Checks whether `password` is a valid user password.
+    ///
+    /// Unknown revisions and malformed `/O` or `/U` values never authenticate.
+    pub fn authenticate_user(&self, password: &[u8]) -> bool {
+        match self.revision {
+            2..=4 => self.authenticate_user_legacy(password),
+            5 | 6 => self.authenticate_user_aes256(password),
+            _ => false,
+        }
+    }
+
+    /// Algorithms 4 and 5: compare `/U` with the value computed from the password.
+    fn authenticate_user_legacy(&self, password: &[u8]) -> bool {
+        if self.user.len() < 32 || self.owner.len() < 32 {
+            return false;
+        }
+
+        let key = self.file_key(password);
+
+        if self.revision == 2 {
+            return rc4(&key, &PADDING) == self.user[..32];
+        }
+
+        let mut hasher = Md5::new();
+        hasher.update(PADDING);
+        hasher.update(self.file_id);
+        let mut data = rc4(&key, &hasher.finalize());
+
+        for i in 1..=19u8 {
+            let round_key = key.iter().map(|byte| byte ^ i).collect::<Vec<_>>();
+            data = rc4(&round_key, &data);
+        }
+
+        data[..] == self.user[..16]
+    }
+
+    /// Algorithm 2: computes the file encryption key for revisions 2-4.
+    fn file_key(&self, password: &[u8]) -> Vec<u8> {
+        let length = if self.revision == 2 {
+            5
+        } else {
+            (self.key_length / 8).clamp(5, 16)
+        };
+
+        let mut padded = password.iter().copied().take(32).collect::<Vec<_>>();
+        padded.extend_from_slice(&PADDING[..32 - padded.len()]);
+
+        let mut hasher = Md5::new();
+        hasher.update(&padded);
+        hasher.update(&self.owner[..32]);
+        hasher.update(self.permissions.to_le_bytes());
+        hasher.update(self.file_id);
+        if self.revision >= 4 && !self.encrypt_metadata {
+            hasher.update([0xFF; 4]);
+        }
+        let mut digest = hasher.finalize();
+
+        if self.revision >= 3 {
+            for _ in 0..50 {
+                digest = Md5::digest(&digest[..length]);
+            }
+        }
+
+        digest[..length].to_vec()
+    }
+
+    /// Algorithm 11: hash of password and validation salt must match `/U`.
+    fn authenticate_user_aes256(&self, password: &[u8]) -> bool {
+        if self.user.len() < 48 {
+            return false;
+        }
+
+        let password = &password[..password.len().min(127)];
+        let salt = &self.user[32..40];
+
+        let hash = if self.revision == 5 {
+            Sha256::new()
+                .chain_update(password)
+                .chain_update(salt)
+                .finalize()
+                .to_vec()
+        } else {
+            hash_2b(password, salt, &[])
+        };
+
+        hash[..32] == self.user[..32]
+    }
+}
+
+/// Algorithm 2.B: hash used by revision 6.
+fn hash_2b(password: &[u8], salt: &[u8], user_data: &[u8]) -> Vec<u8> {
+    let mut key = Sha256::new()
+        .chain_update(password)
+        .chain_update(salt)
+        .chain_update(user_data)
+        .finalize()
+        .to_vec();
+
+    let mut round = 0usize;
+    loop {
+        let mut sequence = Vec::with_capacity(password.len() + key.len() + user_data.len());
+        sequence.extend_from_slice(password);
+        sequence.extend_from_slice(&key);
+        sequence.extend_from_slice(user_data);
+        let data = sequence.repeat(64);
+
+        let encrypted = aes128_cbc_encrypt(&key[..16], &key[16..32], &data);
+
+        let selector = encrypted[..16]
+            .iter()
+            .map(|byte| *byte as usize)
+            .sum::<usize>()
+            % 3;
+        key = match selector {
+            0 => Sha256::digest(&encrypted).to_vec(),
+            1 => Sha384::digest(&encrypted).to_vec(),
+            _ => Sha512::digest(&encrypted).to_vec(),
+        };
+
+        round += 1;
+        let last = *encrypted.last().unwrap_or(&0) as usize;
+        if round >= 64 && last <= round - 32 {
+            break;
+        }
+    }
+
+    key.truncate(32);
+    key
+}
+
+/// AES-128 in CBC mode without padding, `data` must be a multiple of 16 bytes.
+fn aes128_cbc_encrypt(key: &[u8], iv: &[u8], data: &[u8]) -> Vec<u8> {
+    let cipher = Aes128::new(GenericArray::from_slice(key));
+
+    let mut previous = GenericArray::clone_from_slice(iv);
+    let mut result = Vec::with_capacity(data.len());
+    for chunk in data.chunks_exact(16) {
+        let mut block = GenericArray::clone_from_slice(chunk);
+        block
+            .iter_mut()
+            .zip(previous.iter())
+            .for_each(|(byte, prev)| *byte ^= prev);
+
+        cipher.encrypt_block(&mut block);
+        result.extend_from_slice(&block);
+        previous = block;
+    }
+
+    result
+}
+
+/// RC4 stream cipher, encryption and decryption are the same operation.
+pub(super) fn rc4(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut state: [u8; 256] = std::array::from_fn(|i| i as u8);
+
+    let mut j = 0u8;
+    for i in 0..256 {
+        j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+        state.swap(i, j as usize);
+    }
+
+    let (mut i, mut j) = (0u8, 0u8);
+    data.iter()
+        .map(|byte| {
+            i = i.wrapping_add(1);
+            j = j.wrapping_add(state[i as usize]);
+            state.swap(i as usize, j as usize);
+
+            let k = state[(state[i as usize].wrapping_add(state[j as usize])) as usize];
+            byte ^ k
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rc4() {
+        // Well-known vectors for the original cipher description.
+        let test_cases: [(&[u8], &[u8], &[u8]); 2] = [
+            (
+                b"Key",
+                b"Plaintext",
+                &[0xBB, 0xF3, 0x16, 0xE8, 0xD9, 0x40, 0xAF, 0x0A, 0xD3],
+            ),
+            (b"Wiki", b"pedia", &[0x10, 0x21, 0xBF, 0x04, 0x20]),
+        ];
+
+        for (key, input, expected) in test_cases {
+            assert_eq!(rc4(key, input), expected);
+        }
+    }
+}
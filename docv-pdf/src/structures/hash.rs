@@ -1,6 +1,6 @@
 use snafu::{ResultExt, Snafu, ensure};
 
-use crate::types::Object;
+use crate::types::{Object, PdfString};
 
 #[derive(Debug, Snafu)]
 pub struct Error(error::Error);
@@ -24,6 +24,11 @@ pub struct Hash {
 }
 
 impl Hash {
+    /// Permanent identifier, the first element of the `/ID` array.
+    pub fn initial(&self) -> &[u8] {
+        &self.initial
+    }
+
     pub fn from_object(object: &Object) -> Result<Self> {
         let array = object.as_array().generic().context(error::Array)?;
 
@@ -48,6 +53,17 @@ impl Hash {
 
         Ok(Self { initial, current })
     }
+
+    /// Builds the `/ID` array of two hexadecimal strings.
+    pub fn to_object(&self) -> Object {
+        Object::Array(
+            [
+                Object::String(PdfString::Hexadecimal(self.initial.clone())),
+                Object::String(PdfString::Hexadecimal(self.current.clone())),
+            ]
+            .into(),
+        )
+    }
 }
 
 impl std::fmt::Display for Hash {
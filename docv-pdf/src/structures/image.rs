@@ -0,0 +1,324 @@
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
+
+use crate::{
+    objects::{DecodeBudget, Objects},
+    structures::color_space::ColorSpace,
+    types::{Dictionary, ImageFormat, IndirectReference, Object, Stream},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Image XObject used by one or more pages.
+#[derive(Debug, Clone)]
+pub struct ImageRef {
+    /// Indirect reference of the image stream
+    pub reference: IndirectReference,
+    /// Width in samples (`/Width`)
+    pub width: usize,
+    /// Height in samples (`/Height`)
+    pub height: usize,
+    /// Color space family, e.g. `DeviceRGB` or `ICCBased`, `None` for image masks
+    pub color_space: Option<String>,
+    /// Resolved `/ColorSpace` with its palette or profile parameters loaded
+    pub resolved_color_space: Option<ColorSpace>,
+    /// Bits per color component (`/BitsPerComponent`)
+    pub bits_per_component: Option<u8>,
+    /// Filter names in the order they are applied when decoding
+    pub filters: Vec<String>,
+    stream: Stream,
+    budget: DecodeBudget,
+}
+
+impl ImageRef {
+    /// Reads the image XObject `reference`.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if the object is a stream but not an image XObject
+    /// - `Ok(Some(ImageRef))` otherwise
+    pub fn from_reference(
+        reference: IndirectReference,
+        objects: &mut Objects,
+    ) -> Result<Option<Self>> {
+        let object = objects
+            .get_object(&reference)
+            .context(error::Object { reference })?;
+        let stream = object
+            .as_stream()
+            .context(error::InvalidField { field: "XObject" })?;
+
+        let is_image = stream
+            .dictionary
+            .get("Subtype")
+            .and_then(|object| object.as_name().ok())
+            .is_some_and(|name| name.as_str() == "Image");
+        if !is_image {
+            return Ok(None);
+        }
+
+        let dictionary = &stream.dictionary;
+        let ImageAttributes {
+            width,
+            height,
+            color_space: resolved_color_space,
+            bits_per_component,
+        } = ImageAttributes::read(dictionary, objects)?;
+
+        let color_space = dictionary
+            .get("ColorSpace")
+            .map(|object| object.direct(objects).into_owned())
+            .map(|object| -> Result<String> {
+                let family = match &object {
+                    Object::Array(array) => array.first().unwrap_or(&Object::Null),
+                    object => object,
+                };
+                let family = family
+                    .as_name()
+                    .context(error::InvalidField {
+                        field: "ColorSpace",
+                    })?
+                    .to_string();
+
+                Ok(family)
+            })
+            .transpose()?;
+
+        let filters = match dictionary.get("Filter") {
+            Some(Object::Null) | None => Vec::new(),
+            Some(Object::Array(array)) => array
+                .iter()
+                .map(|object| object.as_name().map(|name| name.to_string()))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .context(error::InvalidField { field: "Filter" })?,
+            Some(object) => vec![
+                object
+                    .as_name()
+                    .context(error::InvalidField { field: "Filter" })?
+                    .to_string(),
+            ],
+        };
+
+        Ok(Some(Self {
+            reference,
+            width,
+            height,
+            color_space,
+            resolved_color_space,
+            bits_per_component,
+            filters,
+            stream: stream.clone(),
+            budget: objects.decode_budget().clone(),
+        }))
+    }
+
+    /// Image data with every supported filter removed.
+    ///
+    /// JPEG and JBIG2 data stays encoded, see [`ImageRef::format`].
+    pub fn decoded_data(&self) -> Result<Vec<u8>> {
+        let mut stream = self.stream.clone();
+        self.budget
+            .process_filters(&mut stream)
+            .context(error::Decode)?;
+
+        Ok(stream.data)
+    }
+
+    /// Format of the data returned by [`ImageRef::decoded_data`], `None` for raw samples.
+    pub fn format(&self) -> Result<Option<ImageFormat>> {
+        let format = self.stream.image_format().context(error::Decode)?;
+
+        Ok(format)
+    }
+
+    /// Soft mask (`/SMask`) with the opacity of every sample, `None` if absent.
+    ///
+    /// The mask is a grayscale image and may have another size than the image.
+    pub fn soft_mask(&self, objects: &mut Objects) -> Result<Option<DecodedImage>> {
+        let Some(object) = self.stream.dictionary.get("SMask") else {
+            return Ok(None);
+        };
+
+        let object = object.direct(objects).into_owned();
+        let stream = object
+            .as_stream()
+            .context(error::InvalidField { field: "SMask" })?;
+
+        DecodedImage::from_stream(stream, objects).map(Some)
+    }
+
+    /// Mask (`/Mask`) selecting which samples are painted, `None` if absent.
+    pub fn image_mask(&self, objects: &mut Objects) -> Result<Option<ImageMask>> {
+        let Some(object) = self.stream.dictionary.get("Mask") else {
+            return Ok(None);
+        };
+
+        let object = match object.direct(objects).into_owned() {
+            Object::IndirectDefinition(definition) => (*definition).clone(),
+            object => object,
+        };
+        let mask = match &object {
+            Object::Array(array) => {
+                let values = array
+                    .iter()
+                    .map(|object| object.direct(objects).as_integer())
+                    .collect::<std::result::Result<Vec<i64>, _>>()
+                    .context(error::InvalidField { field: "Mask" })?;
+                ensure!(
+                    values.len() % 2 == 0,
+                    error::OddColorKey { len: values.len() }
+                );
+
+                ImageMask::ColorKey(values.chunks(2).map(|pair| (pair[0], pair[1])).collect())
+            }
+            object => {
+                let stream = object
+                    .as_stream()
+                    .context(error::InvalidField { field: "Mask" })?;
+
+                ImageMask::Stencil(DecodedImage::from_stream(stream, objects)?)
+            }
+        };
+
+        Ok(Some(mask))
+    }
+}
+
+/// Mask of an image XObject, see [`ImageRef::image_mask`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageMask {
+    /// Stencil mask, samples are painted where the 1-bit mask is 0
+    Stencil(DecodedImage),
+    /// Samples whose every component falls in the matching `(min, max)`
+    /// range are not painted
+    ColorKey(Vec<(i64, i64)>),
+}
+
+/// Image with its data decoded, ready to be displayed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedImage {
+    /// Width in samples (`/Width`)
+    pub width: usize,
+    /// Height in samples (`/Height`)
+    pub height: usize,
+    /// Resolved `/ColorSpace`, `None` for image masks
+    pub color_space: Option<ColorSpace>,
+    /// Bits per color component (`/BitsPerComponent`)
+    pub bits_per_component: Option<u8>,
+    /// Encoding of `data`, `None` for raw samples
+    pub format: Option<ImageFormat>,
+    /// Samples, or JPEG or JBIG2 data that stays encoded
+    pub data: Vec<u8>,
+}
+
+impl DecodedImage {
+    /// Decodes an image stream.
+    ///
+    /// `/Subtype` isn't checked, as thumbnails and inline images don't need one.
+    pub fn from_stream(stream: &Stream, objects: &mut Objects) -> Result<Self> {
+        let ImageAttributes {
+            width,
+            height,
+            color_space,
+            bits_per_component,
+        } = ImageAttributes::read(&stream.dictionary, objects)?;
+
+        let format = stream.image_format().context(error::Decode)?;
+        let mut stream = stream.clone();
+        objects
+            .decode_budget()
+            .process_filters(&mut stream)
+            .context(error::Decode)?;
+
+        Ok(Self {
+            width,
+            height,
+            color_space,
+            bits_per_component,
+            format,
+            data: stream.data,
+        })
+    }
+}
+
+/// Entries of an image dictionary that describe its samples.
+struct ImageAttributes {
+    width: usize,
+    height: usize,
+    color_space: Option<ColorSpace>,
+    bits_per_component: Option<u8>,
+}
+
+impl ImageAttributes {
+    fn read(dictionary: &Dictionary, objects: &mut Objects) -> Result<Self> {
+        let width = dictionary
+            .get("Width")
+            .context(error::FieldNotFound { field: "Width" })?
+            .direct(objects)
+            .as_integer()
+            .context(error::InvalidField { field: "Width" })?;
+        let height = dictionary
+            .get("Height")
+            .context(error::FieldNotFound { field: "Height" })?
+            .direct(objects)
+            .as_integer()
+            .context(error::InvalidField { field: "Height" })?;
+
+        let color_space = dictionary
+            .get("ColorSpace")
+            .map(|object| ColorSpace::from_object(object, objects))
+            .transpose()
+            .context(error::ColorSpace)?;
+
+        let bits_per_component = dictionary
+            .get("BitsPerComponent")
+            .map(|object| object.direct(objects).as_integer())
+            .transpose()
+            .context(error::InvalidField {
+                field: "BitsPerComponent",
+            })?;
+
+        Ok(Self {
+            width,
+            height,
+            color_space,
+            bits_per_component,
+        })
+    }
+}
+
+mod error {
+    use snafu::Snafu;
+
+    use crate::types::IndirectReference;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Failed to get object {reference}"))]
+        Object {
+            reference: IndirectReference,
+            source: crate::objects::Error,
+        },
+
+        #[snafu(display("Required field `{field}` not found"))]
+        FieldNotFound { field: &'static str },
+
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Invalid color space"))]
+        ColorSpace {
+            source: crate::structures::color_space::Error,
+        },
+
+        #[snafu(display("Color key mask has an odd number of values ({len})"))]
+        OddColorKey { len: usize },
+
+        #[snafu(display("Failed to decode image data"))]
+        Decode { source: crate::types::stream::Error },
+    }
+}
@@ -54,9 +54,7 @@ impl Info {
                         value
                             .as_string()
                             .with_context(|_| error::InvalidField { field: key.clone() })?
-                            .as_str()
-                            .context(error::PdfString)?
-                            .to_string(),
+                            .to_text_string(),
                     )
                 }
                 "Author" => {
@@ -64,9 +62,7 @@ impl Info {
                         value
                             .as_string()
                             .with_context(|_| error::InvalidField { field: key.clone() })?
-                            .as_str()
-                            .context(error::PdfString)?
-                            .to_string(),
+                            .to_text_string(),
                     )
                 }
                 "Subject" => {
@@ -74,9 +70,7 @@ impl Info {
                         value
                             .as_string()
                             .with_context(|_| error::InvalidField { field: key.clone() })?
-                            .as_str()
-                            .context(error::PdfString)?
-                            .to_string(),
+                            .to_text_string(),
                     )
                 }
                 "Keywords" => {
@@ -84,9 +78,7 @@ impl Info {
                         value
                             .as_string()
                             .with_context(|_| error::InvalidField { field: key.clone() })?
-                            .as_str()
-                            .context(error::PdfString)?
-                            .to_string(),
+                            .to_text_string(),
                     )
                 }
                 "Creator" => {
@@ -94,9 +86,7 @@ impl Info {
                         value
                             .as_string()
                             .with_context(|_| error::InvalidField { field: key.clone() })?
-                            .as_str()
-                            .context(error::PdfString)?
-                            .to_string(),
+                            .to_text_string(),
                     )
                 }
                 "Producer" => {
@@ -104,9 +94,7 @@ impl Info {
                         value
                             .as_string()
                             .with_context(|_| error::InvalidField { field: key.clone() })?
-                            .as_str()
-                            .context(error::PdfString)?
-                            .to_string(),
+                            .to_text_string(),
                     )
                 }
                 "CreationDate" => {
@@ -131,18 +119,14 @@ impl Info {
                     let value = value
                         .as_string()
                         .with_context(|_| error::InvalidField { field: key.clone() })?
-                        .as_str()
-                        .context(error::PdfString)?;
+                        .to_text_string();
 
-                    result.trapped = match value {
+                    result.trapped = match value.as_str() {
                         "True" => Trap::True,
                         "False" => Trap::False,
                         "Unknown" => Trap::Unknown,
                         _ => {
-                            return Err(error::Error::UnexpectedTrapValue {
-                                value: value.to_string(),
-                            }
-                            .into());
+                            return Err(error::Error::UnexpectedTrapValue { value }.into());
                         }
                     }
                 }
@@ -151,9 +135,7 @@ impl Info {
                     value
                         .as_string()
                         .with_context(|_| error::InvalidField { field: key.clone() })?
-                        .as_str()
-                        .context(error::PdfString)?
-                        .to_string(),
+                        .to_text_string(),
                 )),
             }
         }
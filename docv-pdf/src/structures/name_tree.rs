@@ -0,0 +1,108 @@
+use std::collections::BTreeSet;
+
+use snafu::{ResultExt, Snafu, ensure};
+
+use crate::{
+    objects::Objects,
+    types::{IndirectReference, Object},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Maximum depth of nested `/Kids`, deeper trees are treated as malformed.
+const MAX_DEPTH: usize = 64;
+
+/// Collects all key-value pairs of a name tree (ISO 32000-2, 7.9.6) in tree order.
+///
+/// Keys are decoded as text strings. Values are returned as stored, so they
+/// may still be indirect references. Nodes reached twice are skipped, which
+/// protects against `/Kids` cycles.
+///
+/// # Arguments
+/// * `root` - Root node of the tree, or a reference to it
+/// * `objects` - Object store used to resolve indirect references
+pub fn read_name_tree(root: &Object, objects: &mut Objects) -> Result<Vec<(String, Object)>> {
+    let mut entries = Vec::new();
+    let mut visited = BTreeSet::new();
+
+    read_node(root, objects, &mut entries, &mut visited, 0)?;
+
+    Ok(entries)
+}
+
+fn read_node(
+    node: &Object,
+    objects: &mut Objects,
+    entries: &mut Vec<(String, Object)>,
+    visited: &mut BTreeSet<IndirectReference>,
+    depth: usize,
+) -> Result<()> {
+    ensure!(depth < MAX_DEPTH, error::TooDeep);
+
+    if let Object::IndirectReference(reference) = node
+        && !visited.insert(*reference)
+    {
+        return Ok(());
+    }
+
+    let node = node.direct(objects).into_owned();
+    let node = node
+        .as_dictionary()
+        .context(error::InvalidField { field: "Kids" })?;
+
+    if let Some(names) = node.get("Names") {
+        let names = names
+            .direct(objects)
+            .as_array()
+            .generic()
+            .context(error::InvalidArray { field: "Names" })?;
+
+        for pair in names.chunks_exact(2) {
+            let key = pair[0]
+                .as_string()
+                .context(error::InvalidField { field: "Names" })?
+                .to_text_string();
+
+            entries.push((key, pair[1].clone()));
+        }
+    }
+
+    if let Some(kids) = node.get("Kids") {
+        let kids = kids
+            .direct(objects)
+            .as_array()
+            .generic()
+            .context(error::InvalidArray { field: "Kids" })?;
+
+        for kid in kids.iter() {
+            read_node(kid, objects, entries, visited, depth + 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Invalid array in field `{field}`"))]
+        InvalidArray {
+            field: &'static str,
+            source: crate::types::array::Error,
+        },
+
+        #[snafu(display("Name tree is nested too deeply"))]
+        TooDeep,
+    }
+}
@@ -1,8 +1,9 @@
 use snafu::{OptionExt, ResultExt, Snafu};
 
 use crate::{
-    parser::{read_object, read_object_stream_header},
-    types::{IndirectReference, Object, Stream},
+    objects::DecodeBudget,
+    parser::{read_object_stream_header, read_object_with_policy},
+    types::{DuplicateKeyPolicy, IndirectReference, Object, Stream},
 };
 
 #[derive(Debug, Snafu)]
@@ -25,13 +26,15 @@ pub struct ObjectStream {
 /// An entry in the object stream index mapping an object ID to its data offset.
 #[derive(Debug)]
 struct Entry {
-    _id: usize,
+    id: usize,
     offset: usize,
 }
 
 impl ObjectStream {
-    pub fn from_stream(mut stream: Stream) -> Result<Self> {
-        stream.process_filters().context(error::FiltersProcessing)?;
+    pub(crate) fn from_stream(mut stream: Stream, budget: &DecodeBudget) -> Result<Self> {
+        budget
+            .process_filters(&mut stream)
+            .context(error::FiltersProcessing)?;
 
         let n = stream
             .dictionary
@@ -59,7 +62,7 @@ impl ObjectStream {
             .context(error::ParseIds)?
             .iter()
             .map(|(id, offset)| Entry {
-                _id: *id,
+                id: *id,
                 offset: *offset,
             })
             .collect::<Vec<_>>();
@@ -74,20 +77,39 @@ impl ObjectStream {
 
     /// Retrieves an object from the stream by its index position.
     ///
+    /// The index comes from the xref stream entry of the object. If the entry
+    /// at that position belongs to another object, the stream index is
+    /// searched for `id` instead, as some writers store wrong positions.
+    ///
     /// # Arguments
     ///
+    /// * `id` - Object number of the requested object
     /// * `index` - The index of the object in the stream's internal index
+    /// * `policy` - How repeated dictionary keys are read
     ///
     /// # Errors
     ///
     /// Returns an error if:
-    /// - The index is out of bounds
+    /// - The object is not present in the stream
     /// - The object data at the calculated offset cannot be parsed
-    pub fn get_object_by_index(&self, index: usize) -> Result<Object> {
-        let offset = self.ids[index].offset;
-
-        let object = read_object(&self.data[(self.first_offset + offset)..])
-            .ok()
+    pub fn get_object_by_index(
+        &self,
+        id: usize,
+        index: usize,
+        policy: DuplicateKeyPolicy,
+    ) -> Result<Object> {
+        let offset = self
+            .ids
+            .get(index)
+            .filter(|entry| entry.id == id)
+            .or_else(|| self.ids.iter().find(|entry| entry.id == id))
+            .context(error::IdNotFound { id })?
+            .offset;
+
+        let object = self
+            .data
+            .get((self.first_offset + offset)..)
+            .and_then(|data| read_object_with_policy(data, policy).ok())
             .context(error::ParseObject)?;
 
         Ok(object)
@@ -122,3 +144,73 @@ mod error {
         ParseObject,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Dictionary, Numeric};
+
+    #[test]
+    fn test_get_object_by_index() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            id: usize,
+            index: usize,
+            expected: Option<Object>,
+        }
+
+        let stream = Stream {
+            dictionary: Dictionary::from([
+                ("Type", Object::Name("ObjStm".into())),
+                ("N", Object::Numeric(Numeric::Integer(2))),
+                ("First", Object::Numeric(Numeric::Integer(10))),
+                ("Length", Object::Numeric(Numeric::Integer(17))),
+            ]),
+            data: b"10 0 11 3 42 true".to_vec(),
+        };
+        let object_stream = ObjectStream::from_stream(stream, &DecodeBudget::default()).unwrap();
+
+        let test_cases = [
+            TestCase {
+                name: "first object",
+                id: 10,
+                index: 0,
+                expected: Some(Object::Numeric(Numeric::Integer(42))),
+            },
+            TestCase {
+                name: "second object",
+                id: 11,
+                index: 1,
+                expected: Some(Object::Boolean(true)),
+            },
+            TestCase {
+                name: "index points at another object",
+                id: 11,
+                index: 0,
+                expected: Some(Object::Boolean(true)),
+            },
+            TestCase {
+                name: "index out of bounds",
+                id: 10,
+                index: 5,
+                expected: Some(Object::Numeric(Numeric::Integer(42))),
+            },
+            TestCase {
+                name: "object not in stream",
+                id: 12,
+                index: 1,
+                expected: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let result = object_stream.get_object_by_index(
+                case.id,
+                case.index,
+                DuplicateKeyPolicy::default(),
+            );
+            assert_eq!(result.ok(), case.expected, "Test '{}' failed", case.name);
+        }
+    }
+}
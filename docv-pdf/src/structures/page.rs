@@ -1,26 +1,58 @@
-use snafu::{OptionExt, ResultExt, Snafu};
+use std::collections::BTreeSet;
+
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
 
 use crate::{
-    objects::Objects,
-    structures::{page::resources::Resources, root::pages_tree::InheritableAttributes},
-    types::{Array, Dictionary, Rectangle, Stream, string::Date},
+    objects::{DecodeBudget, Objects, Strictness},
+    parser::parse_content_stream,
+    structures::{
+        color_space::ColorSpace,
+        image::DecodedImage,
+        page::{
+            annotation::Annotation,
+            graphics_state::ContentOpsWithState,
+            group::TransparencyGroup,
+            resources::Resources,
+            svg::render_svg,
+            text::{TextOptions, TextRun, extract_text, text_runs},
+            transition::Transition,
+        },
+        root::pages_tree::{InheritableAttributes, normalize_rotation},
+    },
+    types::{
+        Array, ContentOp, Dictionary, IndirectReference, Object, Rectangle, Stream, string::Date,
+    },
 };
 
+pub mod annotation;
+pub mod graphics_state;
+pub mod group;
 mod resources;
+mod svg;
+pub mod text;
+pub mod transition;
 
 #[derive(Debug, Snafu)]
 #[snafu(source(from(error::Error, Box::new)))]
 pub struct Error(Box<error::Error>);
 type Result<T> = std::result::Result<T, Error>;
 
+/// Page of a document with its inherited attributes resolved.
+///
+/// Page boundaries ([`Page::media_box`], [`Page::crop_box`]) are given
+/// before `/Rotate` is applied, as they are written in the file.
+/// [`Page::effective_size`] is given after rotation, as the page is displayed.
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct Page {
     contents: Vec<Stream>,
     resources: Resources,
+    /// Own `/Resources` of the page followed by the inherited ones, nearest first
+    resource_chain: Vec<Object>,
     user_unit: f64,
     rotate: u16,
 
+    // Boundaries are in default user space, before `/Rotate` is applied.
     media_box: Rectangle,
     crop_box: Rectangle,
     bleed_box: Rectangle,
@@ -29,11 +61,11 @@ pub struct Page {
 
     last_modified: Option<Date>,
     box_color_info: Option<Dictionary>,
-    group: Option<Dictionary>,
+    group: Option<TransparencyGroup>,
     thumb: Option<Stream>,
     b: Option<Array>,
     dur: Option<f64>,
-    trans: Option<Dictionary>,
+    trans: Option<Transition>,
     annots: Option<Array>,
     aa: Option<Dictionary>,
     metadata: Option<Stream>,
@@ -44,8 +76,16 @@ pub struct Page {
     separation_info: Option<Dictionary>,
     tabs: TabOrder,
     template_instantiated: Option<String>,
+    lang: Option<String>,
     pres_steps: Option<Dictionary>,
     vp: Option<Dictionary>,
+
+    /// Page dictionary as read, for keys that aren't modeled above
+    dictionary: Dictionary,
+    /// Budget of the document the content streams are decoded from
+    budget: DecodeBudget,
+    /// How malformed numbers in the content streams are read
+    strictness: Strictness,
 }
 
 #[derive(Debug, Default)]
@@ -66,19 +106,23 @@ impl Page {
         let contents = dictionary
             .get("Contents")
             .map(|contents| {
-                let contents = contents.direct(objects);
-                contents
-                    .as_stream()
-                    .map(|stream| vec![stream.clone()])
-                    .or_else(|_| {
-                        contents
-                            .as_array()
-                            .with_objects(objects)
-                            .of(|obj| obj.as_stream().cloned())
-                    })
-                    .context(error::FailedResolveContents {
-                        object: contents.into_owned(),
-                    })
+                // A single stream or an array of streams, either can be
+                // indirect and so can every element of the array. `null`
+                // means the page has no content, like a missing entry.
+                let contents = match contents.direct(objects).into_owned() {
+                    Object::IndirectDefinition(definition) => (*definition).clone(),
+                    object => object,
+                };
+                let streams = match &contents {
+                    Object::Null => Ok(Vec::new()),
+                    Object::Array(array) => array
+                        .iter()
+                        .map(|object| object.direct(objects).as_stream().cloned())
+                        .collect::<std::result::Result<Vec<_>, _>>(),
+                    object => object.as_stream().map(|stream| vec![stream.clone()]),
+                };
+
+                streams.context(error::FailedResolveContents { object: contents })
             })
             .transpose()?
             .unwrap_or_else(Vec::new);
@@ -96,12 +140,24 @@ impl Page {
             Resources::from_dictionary(dictionary, objects).context(error::InvalidResources)?
         };
 
+        let resource_chain = dictionary
+            .get("Resources")
+            .into_iter()
+            .chain(&inheritable_attrs.resource_chain)
+            .cloned()
+            .collect();
+
         let media_box = dictionary
             .get("MediaBox")
             .map(|object| object.as_array().rectangle())
             .transpose()
             .context(error::InvalidArray { field: "MediaBox" })?
             .or_else(|| inheritable_attrs.media_box.clone())
+            .or_else(|| {
+                let fallback = objects.options().default_media_box.clone()?;
+                tracing::warn!("No `MediaBox` in the page tree, using {fallback:?}");
+                Some(fallback)
+            })
             .context(error::FieldNotFound { field: "MediaBox" })?;
 
         let crop_box = dictionary
@@ -138,6 +194,8 @@ impl Page {
             .map(|object| object.as_integer())
             .transpose()
             .context(error::InvalidType { field: "Rotate" })?
+            .map(|value| normalize_rotation(value).context(error::InvalidRotate { value }))
+            .transpose()?
             .or(inheritable_attrs.rotate)
             .unwrap_or(0);
 
@@ -173,13 +231,14 @@ impl Page {
 
         let group = dictionary
             .get("Group")
-            .map(|object| object.as_dictionary().cloned())
+            .map(|object| TransparencyGroup::from_object(object, objects))
             .transpose()
-            .context(error::InvalidType { field: "Group" })?;
+            .context(error::InvalidGroup)?
+            .flatten();
 
         let thumb = dictionary
             .get("Thumb")
-            .map(|object| object.as_stream().cloned())
+            .map(|object| object.direct(objects).as_stream().cloned())
             .transpose()
             .context(error::InvalidType { field: "Thumb" })?;
 
@@ -197,9 +256,9 @@ impl Page {
 
         let trans = dictionary
             .get("Trans")
-            .map(|object| object.as_dictionary().cloned())
+            .map(|object| Transition::from_object(object, objects))
             .transpose()
-            .context(error::InvalidType { field: "Trans" })?;
+            .context(error::InvalidTransition)?;
 
         let annots = dictionary
             .get("Annots")
@@ -235,10 +294,9 @@ impl Page {
 
         let id = dictionary
             .get("ID")
-            .map(|object| object.as_string())
+            .map(|object| object.as_bytes().map(<[u8]>::to_vec))
             .transpose()
-            .context(error::InvalidType { field: "ID" })?
-            .map(|s| s.as_bytes().to_vec());
+            .context(error::InvalidType { field: "ID" })?;
 
         let pz = dictionary
             .get("PZ")
@@ -276,6 +334,19 @@ impl Page {
             })?
             .map(|s| s.to_string());
 
+        let lang = dictionary
+            .get("Lang")
+            .map(|object| {
+                let object = match object.direct(objects).into_owned() {
+                    Object::IndirectDefinition(definition) => (*definition).clone(),
+                    object => object,
+                };
+
+                object.as_string().map(|string| string.to_text_string())
+            })
+            .transpose()
+            .context(error::InvalidType { field: "Lang" })?;
+
         let pres_steps = dictionary
             .get("PresSteps")
             .map(|object| object.as_dictionary().cloned())
@@ -291,6 +362,7 @@ impl Page {
         Ok(Self {
             contents,
             resources,
+            resource_chain,
             user_unit,
             rotate,
 
@@ -316,11 +388,481 @@ impl Page {
             separation_info,
             tabs,
             template_instantiated,
+            lang,
             pres_steps,
             vp,
             trans,
+
+            dictionary: dictionary.clone(),
+            budget: objects.decode_budget().clone(),
+            strictness: objects.options().strictness,
         })
     }
+
+    /// Boundaries of the physical medium the page is displayed or printed on.
+    ///
+    /// The value of the page or of its nearest ancestor that has one,
+    /// ordered so that `left <= right` and `bottom <= top`. Pre-rotation:
+    /// a rotated page has the same media box as an unrotated one.
+    ///
+    /// Coordinates are in unscaled default user space units, `/UserUnit`
+    /// isn't applied. See [`Page::size_inches`] for the physical size.
+    pub fn media_box(&self) -> &Rectangle {
+        &self.media_box
+    }
+
+    /// Visible region of the page, in the same unscaled units as [`Page::media_box`].
+    ///
+    /// Pre-rotation, like [`Page::media_box`].
+    pub fn crop_box(&self) -> &Rectangle {
+        &self.crop_box
+    }
+
+    /// Width and height of the crop box as displayed, after `/Rotate` is applied.
+    ///
+    /// Quarter turns swap the dimensions. Units are the same unscaled units
+    /// as [`Page::crop_box`].
+    pub fn effective_size(&self) -> (f64, f64) {
+        let (width, height) = (self.crop_box.width(), self.crop_box.height());
+
+        match self.rotate {
+            90 | 270 => (height, width),
+            _ => (width, height),
+        }
+    }
+
+    /// Size of a default user space unit in multiples of 1/72 inch (`/UserUnit`).
+    pub fn user_unit(&self) -> f64 {
+        self.user_unit
+    }
+
+    /// Physical width and height of the media box in inches, `/UserUnit` applied.
+    ///
+    /// Pre-rotation, see [`Page::effective_size`] for the displayed orientation.
+    pub fn size_inches(&self) -> (f64, f64) {
+        let scale = self.user_unit / 72.0;
+
+        (
+            self.media_box.width() * scale,
+            self.media_box.height() * scale,
+        )
+    }
+
+    /// Physical width and height of the media box in millimetres, see [`Page::size_inches`].
+    pub fn size_mm(&self) -> (f64, f64) {
+        let (width, height) = self.size_inches();
+
+        (width * 25.4, height * 25.4)
+    }
+
+    /// Clockwise rotation of the page when displayed, one of 0, 90, 180 or 270.
+    pub fn rotate(&self) -> u16 {
+        self.rotate
+    }
+
+    /// Natural language of the page's text (`/Lang`), `None` if the page
+    /// uses the default of the document.
+    pub fn language(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    /// Thumbnail image of the page (`/Thumb`), decoded like an image XObject.
+    ///
+    /// # Returns
+    /// `None` if the page has no thumbnail
+    pub fn thumbnail(&self, objects: &mut Objects) -> crate::Result<Option<DecodedImage>> {
+        let thumbnail = self.read_thumbnail(objects).context(crate::error::Page)?;
+
+        Ok(thumbnail)
+    }
+
+    /// Transition effect used when moving to the page in presentation mode.
+    pub fn transition(&self) -> Option<&Transition> {
+        self.trans.as_ref()
+    }
+
+    /// Attributes of the page as a transparency group, for compositing its content.
+    pub fn transparency_group(&self) -> Option<&TransparencyGroup> {
+        self.group.as_ref()
+    }
+
+    /// Value of `key` in the page or in the nearest ancestor that defines it.
+    ///
+    /// Walks up the `/Parent` chain like the inheritance of `/Resources`,
+    /// `/MediaBox`, `/CropBox` and `/Rotate`, but for any key. The value is
+    /// returned as stored, references in it aren't resolved.
+    ///
+    /// # Returns
+    /// `None` if neither the page nor any of its ancestors has `key`
+    pub fn inherited_attribute(
+        &self,
+        key: &str,
+        objects: &mut Objects,
+    ) -> crate::Result<Option<Object>> {
+        let value = self
+            .read_inherited_attribute(key, objects)
+            .context(crate::error::Page)?;
+
+        Ok(value)
+    }
+
+    /// Objects listed in the `/XObject` resources of the page.
+    pub(crate) fn x_objects(&self) -> impl Iterator<Item = &Object> {
+        self.resources.x_objects()
+    }
+
+    /// Color space selected by `name` in a `cs` or `CS` operator.
+    ///
+    /// Looks `name` up in the `/ColorSpace` resources first and falls back to
+    /// the families that can be named directly, like `/DeviceRGB`.
+    ///
+    /// # Returns
+    /// `None` if `name` is neither a resource nor such a family
+    pub fn color_space(
+        &self,
+        name: &str,
+        objects: &mut Objects,
+    ) -> crate::Result<Option<ColorSpace>> {
+        let color_space = self
+            .read_color_space(name, objects)
+            .context(crate::error::Page)?;
+
+        Ok(color_space)
+    }
+
+    /// `/Resources` of the page merged with those of every ancestor.
+    ///
+    /// A page only inherits `/Resources` as a whole when it has none of its
+    /// own, but some writers rely on the subdictionaries being merged. Here
+    /// `/Font`, `/XObject`, `/ColorSpace` and the other subdictionaries are
+    /// combined across all levels, entries closer to the page winning.
+    /// Other values, like `/ProcSet`, are taken from the nearest level.
+    pub fn flattened_resources(&self, objects: &mut Objects) -> crate::Result<Dictionary> {
+        let resources = self
+            .read_flattened_resources(objects)
+            .context(crate::error::Page)?;
+
+        Ok(resources)
+    }
+
+    /// XObjects drawn by the page content, in order of first use.
+    ///
+    /// `Do` operators inside form XObjects are followed as well, so images
+    /// and forms nested in other forms are included. A form without
+    /// `/Resources` uses those of the form or page drawing it.
+    ///
+    /// # Errors
+    /// Fails with `CyclicResource` if a form XObject draws itself, directly
+    /// or through other forms.
+    pub fn drawn_x_objects(&self, objects: &mut Objects) -> crate::Result<Vec<IndirectReference>> {
+        let x_objects = self
+            .read_drawn_x_objects(objects)
+            .context(crate::error::Page)?;
+
+        Ok(x_objects)
+    }
+
+    /// Annotations of the page (`/Annots`) in the order they are listed.
+    ///
+    /// Every subtype is returned, link targets are only read for `/Link`.
+    pub fn annotations(&self, objects: &mut Objects) -> crate::Result<Vec<Annotation>> {
+        let annotations = self.read_annotations(objects).context(crate::error::Page)?;
+
+        Ok(annotations)
+    }
+
+    /// Decoded page content as a single buffer.
+    ///
+    /// Every stream of `/Contents` is decoded and the results are joined with
+    /// a newline. Operands and their operator may be split between streams,
+    /// but a single token never is, so the separator keeps the boundary
+    /// tokens apart without changing the meaning of the content.
+    pub fn content_bytes(&self) -> crate::Result<Vec<u8>> {
+        let data = self.read_content_bytes().context(crate::error::Page)?;
+
+        Ok(data)
+    }
+
+    /// Operations of the page content stream in drawing order.
+    pub fn content_ops(&self) -> crate::Result<Vec<ContentOp>> {
+        let operations = self.read_content_ops().context(crate::error::Page)?;
+
+        Ok(operations)
+    }
+
+    /// Operations of the page content stream together with the graphics state.
+    ///
+    /// Each operation is reported with the current transformation matrix,
+    /// text state and text matrix in effect after the operator was applied.
+    pub fn content_ops_with_state(&self) -> crate::Result<ContentOpsWithState> {
+        let operations = self.read_content_ops().context(crate::error::Page)?;
+
+        Ok(ContentOpsWithState::new(operations))
+    }
+
+    /// Text shown on the page with its position, one run per text operator.
+    ///
+    /// Font metrics are not read yet, glyphs are assumed to be half an em
+    /// wide, so bounding boxes are only approximate horizontally.
+    pub fn text_runs(&self) -> crate::Result<Vec<TextRun>> {
+        let operations = self.read_content_ops().context(crate::error::Page)?;
+
+        Ok(text_runs(ContentOpsWithState::new(operations)))
+    }
+
+    /// SVG document drawing the vector paths and text of the page.
+    ///
+    /// The document is sized to the crop box, `/Rotate` isn't applied.
+    /// Only paths and positioned text are drawn, images and shadings are
+    /// left out, and text uses a generic font with estimated glyph widths.
+    pub fn to_svg(&self) -> crate::Result<String> {
+        let operations = self.read_content_ops().context(crate::error::Page)?;
+
+        Ok(render_svg(
+            &self.crop_box,
+            ContentOpsWithState::new(operations),
+        ))
+    }
+
+    /// Plain text shown on the page, see [`Page::extract_text_with`].
+    pub fn extract_text(&self) -> crate::Result<String> {
+        self.extract_text_with(&TextOptions::default())
+    }
+
+    /// Plain text shown on the page in content stream order.
+    ///
+    /// Large negative `TJ` adjustments are turned into spaces according to
+    /// [`TextOptions::word_gap`], text positioning to a new line into newlines.
+    pub fn extract_text_with(&self, options: &TextOptions) -> crate::Result<String> {
+        let operations = self.read_content_ops().context(crate::error::Page)?;
+
+        Ok(extract_text(&operations, options))
+    }
+
+    fn read_flattened_resources(&self, objects: &mut Objects) -> Result<Dictionary> {
+        let mut flattened = Dictionary::default();
+
+        for resources in &self.resource_chain {
+            let resources = resources.direct(objects).into_owned();
+            let resources = resources
+                .as_dictionary()
+                .context(error::InvalidType { field: "Resources" })?;
+
+            for (category, value) in resources.iter() {
+                let value = match value.direct(objects).into_owned() {
+                    Object::IndirectDefinition(definition) => (*definition).clone(),
+                    object => object,
+                };
+
+                match (flattened.get(category), value) {
+                    (None, value) => {
+                        flattened.insert(category.clone(), value);
+                    }
+                    (Some(Object::Dictionary(nearer)), Object::Dictionary(inherited)) => {
+                        let mut merged = inherited;
+                        for (name, object) in nearer.iter() {
+                            merged.insert(name.clone(), object.clone());
+                        }
+                        flattened.insert(category.clone(), Object::Dictionary(merged));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(flattened)
+    }
+
+    fn read_drawn_x_objects(&self, objects: &mut Objects) -> Result<Vec<IndirectReference>> {
+        let operations = self.read_content_ops()?;
+
+        let mut drawn = Vec::new();
+        collect_drawn_x_objects(
+            &operations,
+            &self.resources,
+            objects,
+            &mut Vec::new(),
+            &mut drawn,
+        )?;
+
+        Ok(drawn)
+    }
+
+    fn read_thumbnail(&self, objects: &mut Objects) -> Result<Option<DecodedImage>> {
+        let thumbnail = self
+            .thumb
+            .as_ref()
+            .map(|stream| DecodedImage::from_stream(stream, objects))
+            .transpose()
+            .context(error::InvalidThumbnail)?;
+
+        Ok(thumbnail)
+    }
+
+    fn read_inherited_attribute(&self, key: &str, objects: &mut Objects) -> Result<Option<Object>> {
+        let mut node = self.dictionary.clone();
+        let mut visited = BTreeSet::new();
+
+        loop {
+            if let Some(value) = node.get(key) {
+                return Ok(Some(value.clone()));
+            }
+
+            let Some(parent) = node.get("Parent") else {
+                return Ok(None);
+            };
+            let reference = *parent
+                .as_indirect_ref()
+                .context(error::InvalidType { field: "Parent" })?;
+            ensure!(visited.insert(reference), error::ParentCycle { reference });
+
+            node = objects
+                .get_object(&reference)
+                .context(error::ParentNotFound { reference })?
+                .as_dictionary()
+                .context(error::InvalidType { field: "Parent" })?
+                .clone();
+        }
+    }
+
+    fn read_annotations(&self, objects: &mut Objects) -> Result<Vec<Annotation>> {
+        self.annots
+            .iter()
+            .flat_map(|annots| annots.iter())
+            .enumerate()
+            .map(|(index, object)| {
+                let annotation = Annotation::from_object(object, objects)
+                    .context(error::InvalidAnnotation { index })?;
+
+                Ok(annotation)
+            })
+            .collect()
+    }
+
+    fn read_color_space(&self, name: &str, objects: &mut Objects) -> Result<Option<ColorSpace>> {
+        let color_space = match self.resources.color_space(name) {
+            Some(object) => Some(
+                ColorSpace::from_object(object, objects)
+                    .context(error::InvalidColorSpace { name })?,
+            ),
+            None => ColorSpace::from_name(name),
+        };
+
+        Ok(color_space)
+    }
+
+    fn read_content_bytes(&self) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+
+        for (index, stream) in self.contents.iter().enumerate() {
+            let mut stream = stream.clone();
+            self.budget
+                .process_filters(&mut stream)
+                .context(error::ProcessContents { index })?;
+
+            if index > 0 {
+                data.push(b'\n');
+            }
+            data.extend_from_slice(&stream.data);
+        }
+
+        Ok(data)
+    }
+
+    fn read_content_ops(&self) -> Result<Vec<ContentOp>> {
+        let data = self.read_content_bytes()?;
+
+        let operations = parse_content_stream(&data, self.strictness)
+            .ok()
+            .context(error::ParseContents)?;
+
+        Ok(operations)
+    }
+}
+
+/// Adds the XObjects drawn by `operations` to `drawn`, descending into form XObjects.
+///
+/// `path` holds the forms currently being walked. A form that is already on
+/// it would be drawn from inside itself, which never terminates.
+fn collect_drawn_x_objects(
+    operations: &[ContentOp],
+    resources: &Resources,
+    objects: &mut Objects,
+    path: &mut Vec<IndirectReference>,
+    drawn: &mut Vec<IndirectReference>,
+) -> Result<()> {
+    let names = operations
+        .iter()
+        .filter(|operation| operation.operator == "Do")
+        .filter_map(|operation| operation.operands.first()?.as_name().ok());
+
+    for name in names {
+        let Some(reference) = resources
+            .x_object(name)
+            .and_then(|object| object.as_indirect_ref().ok())
+            .copied()
+        else {
+            continue;
+        };
+
+        ensure!(
+            !path.contains(&reference),
+            error::CyclicResource { reference }
+        );
+        if drawn.contains(&reference) {
+            continue;
+        }
+        drawn.push(reference);
+
+        let object = Object::IndirectReference(reference)
+            .direct(objects)
+            .into_owned();
+        let Ok(stream) = object.as_stream() else {
+            continue;
+        };
+        if stream
+            .dictionary
+            .get_name("Subtype")
+            .and_then(|name| name.ok())
+            != Some("Form")
+        {
+            continue;
+        }
+
+        let own_resources = stream
+            .dictionary
+            .get("Resources")
+            .map(|dictionary| {
+                let dictionary = dictionary.direct(objects).into_owned();
+                let dictionary = dictionary
+                    .as_dictionary()
+                    .context(error::InvalidType { field: "Resources" })?;
+
+                Resources::from_dictionary(dictionary, objects).context(error::InvalidResources)
+            })
+            .transpose()?;
+
+        let mut form = stream.clone();
+        objects
+            .decode_budget()
+            .process_filters(&mut form)
+            .context(error::ProcessXObject { reference })?;
+        let form_operations = parse_content_stream(&form.data, objects.options().strictness)
+            .ok()
+            .context(error::ParseContents)?;
+
+        path.push(reference);
+        collect_drawn_x_objects(
+            &form_operations,
+            own_resources.as_ref().unwrap_or(resources),
+            objects,
+            path,
+            drawn,
+        )?;
+        path.pop();
+    }
+
+    Ok(())
 }
 
 impl std::fmt::Display for Page {
@@ -386,7 +928,7 @@ impl std::fmt::Display for TabOrder {
 mod error {
     use snafu::Snafu;
 
-    use crate::types::Object;
+    use crate::types::{IndirectReference, Object};
 
     #[derive(Debug, Snafu)]
     #[snafu(visibility(pub(super)), context(suffix(false)))]
@@ -420,10 +962,67 @@ mod error {
         #[snafu(display("Unexpected node type. Got = `{got}`. Expected `Page` or `Pages`]"))]
         UnexpectedNodeType { got: String },
 
+        #[snafu(display("Failed to decode content stream #{index}"))]
+        ProcessContents {
+            index: usize,
+            source: crate::types::stream::Error,
+        },
+
+        #[snafu(display("Failed to parse page content"))]
+        ParseContents,
+
+        #[snafu(display("Failed to decode form XObject {reference}"))]
+        ProcessXObject {
+            reference: IndirectReference,
+            source: crate::types::stream::Error,
+        },
+
+        #[snafu(display("Form XObject {reference} draws itself"))]
+        CyclicResource { reference: IndirectReference },
+
+        #[snafu(display("`Rotate` must be a multiple of 90. Got = {value}"))]
+        InvalidRotate { value: i64 },
+
+        #[snafu(display("Invalid annotation #{index}"))]
+        InvalidAnnotation {
+            index: usize,
+            source: crate::structures::page::annotation::Error,
+        },
+
+        #[snafu(display("Failed to decode thumbnail image"))]
+        InvalidThumbnail {
+            source: crate::structures::image::Error,
+        },
+
+        #[snafu(display("Invalid group attributes"))]
+        InvalidGroup {
+            source: crate::structures::page::group::Error,
+        },
+
+        #[snafu(display("Invalid page transition"))]
+        InvalidTransition {
+            source: crate::structures::page::transition::Error,
+        },
+
+        #[snafu(display("Invalid color space `{name}`"))]
+        InvalidColorSpace {
+            name: String,
+            source: crate::structures::color_space::Error,
+        },
+
+        #[snafu(display("Failed to get parent node {reference}"))]
+        ParentNotFound {
+            reference: IndirectReference,
+            source: crate::objects::Error,
+        },
+
+        #[snafu(display("Page tree node {reference} is its own ancestor"))]
+        ParentCycle { reference: IndirectReference },
+
         #[snafu(display("Failed to resolve contents: unexpected object `{object:?}`"))]
         FailedResolveContents {
-            object: Object,
-            source: crate::types::array::Error,
+            object: Box<Object>,
+            source: crate::types::object::Error,
         },
     }
 }
@@ -432,7 +1031,15 @@ mod error {
 mod test {
     use snafu::Whatever;
 
-    use crate::Document;
+    use crate::{
+        ColorSpace, Document, DocumentLimits, ParseOptions,
+        structures::page::{
+            annotation::LinkTarget,
+            transition::{Transition, TransitionDimension, TransitionStyle},
+        },
+        test_utils::{CATALOG, PdfBuilder},
+        types::{IndirectReference, Numeric, Object},
+    };
 
     use super::*;
     use std::{fs, path::PathBuf, sync::LazyLock};
@@ -469,4 +1076,806 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_contents_forms() {
+        let test_cases = [
+            ("single stream", "4 0 R", &b"0 0 m"[..]),
+            ("array of references", "[4 0 R 5 0 R]", b"0 0 m\n10 10 l S"),
+            ("indirect array", "6 0 R", b"0 0 m\n10 10 l S"),
+            ("empty array", "[]", b""),
+            ("null", "null", b""),
+            ("indirect null", "7 0 R", b""),
+        ];
+
+        for (name, contents, expected) in test_cases {
+            let path = PdfBuilder::new()
+                .object(1, CATALOG)
+                .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                .object(
+                    3,
+                    &format!(
+                        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] /Resources << >> /Contents {contents} >>"
+                    ),
+                )
+                .stream(4, "", b"0 0 m")
+                .stream(5, "", b"10 10 l S")
+                .object(6, "[4 0 R 5 0 R]")
+                .object(7, "null")
+                .section("/Root 1 0 R")
+                .write("contents-forms");
+            let mut document = Document::from_path(&path).unwrap();
+            let page = document.pages().next().unwrap().unwrap();
+
+            assert_eq!(
+                page.content_bytes().unwrap(),
+                expected,
+                "Test '{name}' failed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_content_split_across_streams() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 200 200] /Resources << >> /Contents [4 0 R 5 0 R] >>",
+            )
+            .stream(4, "", b"0 0 m 100")
+            .stream(5, "", b"100 l S")
+            .section("/Root 1 0 R")
+            .write("content-split");
+
+        let mut document = Document::from_path(&path).unwrap();
+        let page = document.pages().next().unwrap().unwrap();
+
+        assert_eq!(page.content_bytes().unwrap(), b"0 0 m 100\n100 l S");
+        assert_eq!(
+            page.content_ops().unwrap(),
+            vec![
+                ContentOp::new(
+                    "m",
+                    vec![
+                        Object::Numeric(Numeric::Integer(0)),
+                        Object::Numeric(Numeric::Integer(0)),
+                    ],
+                ),
+                ContentOp::new(
+                    "l",
+                    vec![
+                        Object::Numeric(Numeric::Integer(100)),
+                        Object::Numeric(Numeric::Integer(100)),
+                    ],
+                ),
+                ContentOp::new("S", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        struct TestCase {
+            name: &'static str,
+            pages: &'static str,
+            page: &'static str,
+            expected: Option<u16>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "negative quarter turn",
+                pages: "",
+                page: "/Rotate -90",
+                expected: Some(270),
+            },
+            TestCase {
+                name: "more than a full turn",
+                pages: "",
+                page: "/Rotate 450",
+                expected: Some(90),
+            },
+            TestCase {
+                name: "already normalized",
+                pages: "",
+                page: "/Rotate 270",
+                expected: Some(270),
+            },
+            TestCase {
+                name: "inherited negative value",
+                pages: "/Rotate -180",
+                page: "",
+                expected: Some(180),
+            },
+            TestCase {
+                name: "not a multiple of 90",
+                pages: "",
+                page: "/Rotate 45",
+                expected: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let path = PdfBuilder::new()
+                .object(1, CATALOG)
+                .object(
+                    2,
+                    &format!("<< /Type /Pages /Kids [3 0 R] /Count 1 {} >>", case.pages),
+                )
+                .object(
+                    3,
+                    &format!(
+                        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 100 100] /Resources << >> {} >>",
+                        case.page
+                    ),
+                )
+                .section("/Root 1 0 R")
+                .write("rotate");
+            let mut document = Document::from_path(&path).unwrap();
+
+            let page = document.pages().next().unwrap();
+            assert_eq!(
+                page.as_ref().ok().map(Page::rotate),
+                case.expected,
+                "Test '{}' failed: got {:?}",
+                case.name,
+                page
+            );
+        }
+    }
+
+    #[test]
+    fn test_user_unit_size() {
+        struct TestCase {
+            name: &'static str,
+            page: &'static str,
+            expected_inches: (f64, f64),
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "default user unit",
+                page: "",
+                expected_inches: (8.5, 11.0),
+            },
+            TestCase {
+                name: "doubled user unit",
+                page: "/UserUnit 2.0",
+                expected_inches: (17.0, 22.0),
+            },
+        ];
+
+        for case in &test_cases {
+            let path = PdfBuilder::new()
+                .object(1, CATALOG)
+                .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                .object(
+                    3,
+                    &format!(
+                        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Resources << >> {} >>",
+                        case.page
+                    ),
+                )
+                .section("/Root 1 0 R")
+                .write("user-unit");
+            let mut document = Document::from_path(&path).unwrap();
+            let page = document.pages().next().unwrap().unwrap();
+
+            assert_eq!(
+                page.media_box().width(),
+                612.0,
+                "Test '{}' failed",
+                case.name
+            );
+            assert_eq!(
+                page.size_inches(),
+                case.expected_inches,
+                "Test '{}' failed",
+                case.name
+            );
+
+            let (width, height) = page.size_mm();
+            let (expected_width, expected_height) = case.expected_inches;
+            assert!(
+                (width - expected_width * 25.4).abs() < 1e-9
+                    && (height - expected_height * 25.4).abs() < 1e-9,
+                "Test '{}' failed: got {width}x{height} mm",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_media_box_rotation() {
+        struct TestCase {
+            name: &'static str,
+            pages: &'static str,
+            page: &'static str,
+            expected_size: (f64, f64),
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "inherited and rotated",
+                pages: "/MediaBox [0 0 612 792] /Rotate 90",
+                page: "",
+                expected_size: (792.0, 612.0),
+            },
+            TestCase {
+                name: "unnormalized and rotated by the page",
+                pages: "/MediaBox [612 792 0 0]",
+                page: "/Rotate 270",
+                expected_size: (792.0, 612.0),
+            },
+            TestCase {
+                name: "upside down",
+                pages: "/MediaBox [0 0 612 792]",
+                page: "/Rotate 180",
+                expected_size: (612.0, 792.0),
+            },
+        ];
+
+        for case in &test_cases {
+            let path = PdfBuilder::new()
+                .object(1, CATALOG)
+                .object(
+                    2,
+                    &format!("<< /Type /Pages /Kids [3 0 R] /Count 1 {} >>", case.pages),
+                )
+                .object(
+                    3,
+                    &format!(
+                        "<< /Type /Page /Parent 2 0 R /Resources << >> {} >>",
+                        case.page
+                    ),
+                )
+                .section("/Root 1 0 R")
+                .write("media-box-rotation");
+            let mut document = Document::from_path(&path).unwrap();
+            let page = document.pages().next().unwrap().unwrap();
+
+            assert_eq!(
+                page.media_box(),
+                &Rectangle::new(0.0, 0.0, 612.0, 792.0),
+                "Test '{}' failed",
+                case.name
+            );
+            assert_eq!(
+                page.effective_size(),
+                case.expected_size,
+                "Test '{}' failed",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_media_box() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 /Rotate 90 >>")
+            .object(3, "<< /Type /Page /Parent 2 0 R /Resources << >> >>")
+            .section("/Root 1 0 R")
+            .write("default-media-box");
+
+        let mut strict = Document::from_path(&path).unwrap();
+        let message = strict.pages().next().unwrap().unwrap_err().full_message();
+        assert!(
+            message.contains("Required field `MediaBox` not found"),
+            "{message}"
+        );
+
+        let options = ParseOptions {
+            default_media_box: Some(Rectangle::LETTER),
+            ..Default::default()
+        };
+        let mut lenient =
+            Document::from_path_with_options(&path, DocumentLimits::default(), options).unwrap();
+        let page = lenient.pages().next().unwrap().unwrap();
+        assert_eq!(page.media_box(), &Rectangle::new(0.0, 0.0, 612.0, 792.0));
+        assert_eq!(page.effective_size(), (792.0, 612.0));
+    }
+
+    #[test]
+    fn test_text_runs_within_page() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Resources << >> /Contents 4 0 R >>",
+            )
+            .stream(4, "", b"BT /F1 12 Tf 72 720 Td (Hello) Tj ET")
+            .section("/Root 1 0 R")
+            .write("text-runs");
+        let mut document = Document::from_path(&path).unwrap();
+        let page = document.pages().next().unwrap().unwrap();
+
+        let runs = page.text_runs().unwrap();
+        let run = runs.iter().find(|run| run.text == "Hello").unwrap();
+        let media_box = page.media_box();
+
+        assert_eq!(run.font, "F1");
+        assert_eq!(run.size, 12.0);
+        assert!(run.bbox.left() >= media_box.left() && run.bbox.right() <= media_box.right());
+        assert!(run.bbox.bottom() >= media_box.bottom() && run.bbox.top() <= media_box.top());
+        assert!(run.bbox.left() <= 72.0 && run.bbox.bottom() <= 720.0);
+    }
+
+    #[test]
+    fn test_annotations() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Resources << >> \
+                 /Annots [4 0 R 5 0 R << /Subtype /Widget /Rect [10 10 60 30] >> 6 0 R] >>",
+            )
+            .object(
+                4,
+                "<< /Type /Annot /Subtype /Text /Rect [100 700 120 720] \
+                 /Contents (Check this figure) /Popup 5 0 R /Name /Comment >>",
+            )
+            .object(
+                5,
+                "<< /Type /Annot /Subtype /Popup /Rect [120 600 300 700] /Parent 4 0 R >>",
+            )
+            .object(
+                6,
+                "<< /Type /Annot /Subtype /Link /Rect [0 0 50 10] \
+                 /A << /S /URI /URI (https://example.com) >> >>",
+            )
+            .section("/Root 1 0 R")
+            .write("annotations");
+        let mut document = Document::from_path(&path).unwrap();
+        let page = document.pages().next().unwrap().unwrap();
+        let annotations = page.annotations(document.objects_mut()).unwrap();
+
+        let subtypes = annotations
+            .iter()
+            .map(|annotation| annotation.subtype.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(subtypes, ["Text", "Popup", "Widget", "Link"]);
+
+        let note = &annotations[0];
+        assert_eq!(note.reference, Some(IndirectReference { id: 4, gen_id: 0 }));
+        assert_eq!(note.rect, Rectangle::new(100.0, 700.0, 120.0, 720.0));
+        assert_eq!(note.contents.as_deref(), Some("Check this figure"));
+        assert_eq!(note.link, None);
+
+        assert_eq!(annotations[2].reference, None);
+        assert_eq!(
+            annotations[3].link,
+            Some(LinkTarget::Uri("https://example.com".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_extract_text_with_vendor_operators() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+                 /Resources << /ProcSet [/PDF /Text] >> /Contents 4 0 R >>",
+            )
+            .stream(
+                4,
+                "",
+                b"/Acme:Span << /Id 1 >> BDC BT /F1 12 Tf (Hello) Tj ET EMC\n\
+                  0.5 acmeOverprint } BT ( world) Tj ET",
+            )
+            .section("/Root 1 0 R")
+            .write("vendor-operators");
+        let mut document = Document::from_path(&path).unwrap();
+        let page = document.pages().next().unwrap().unwrap();
+
+        let operators = page
+            .content_ops()
+            .unwrap()
+            .into_iter()
+            .map(|op| op.operator.to_string())
+            .collect::<Vec<_>>();
+        assert!(operators.contains(&"acmeOverprint".to_string()));
+        assert_eq!(page.extract_text().unwrap(), "Hello world");
+    }
+
+    #[test]
+    fn test_flattened_resources() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(
+                2,
+                "<< /Type /Pages /Kids [3 0 R] /Count 2 /MediaBox [0 0 612 792] \
+                 /Resources << /Font << /F1 10 0 R /F2 11 0 R >> /ProcSet [/PDF] >> >>",
+            )
+            .object(
+                3,
+                "<< /Type /Pages /Parent 2 0 R /Kids [4 0 R 5 0 R] /Count 2 \
+                 /Resources 6 0 R >>",
+            )
+            .object(4, "<< /Type /Page /Parent 3 0 R >>")
+            .object(
+                5,
+                "<< /Type /Page /Parent 3 0 R \
+                 /Resources << /Font << /F2 12 0 R >> /ProcSet [/PDF /Text] >> >>",
+            )
+            .object(6, "<< /XObject << /Im1 13 0 R >> /Font 7 0 R >>")
+            .object(7, "<< /F3 14 0 R >>")
+            .section("/Root 1 0 R")
+            .write("flattened-resources");
+        let mut document = Document::from_path(&path).unwrap();
+        let pages = document.pages().collect::<crate::Result<Vec<_>>>().unwrap();
+        let objects = document.objects_mut();
+
+        let reference = |id| Object::IndirectReference(IndirectReference { id, gen_id: 0 });
+        let names = |dictionary: &Dictionary, category: &str| {
+            dictionary
+                .get_dictionary(category)
+                .unwrap()
+                .unwrap()
+                .iter()
+                .map(|(name, object)| (name.to_string(), object.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let inherited = pages[0].flattened_resources(objects).unwrap();
+        assert_eq!(
+            names(&inherited, "Font"),
+            [
+                ("F1".to_string(), reference(10)),
+                ("F2".to_string(), reference(11)),
+                ("F3".to_string(), reference(14)),
+            ]
+        );
+        assert_eq!(
+            names(&inherited, "XObject"),
+            [("Im1".to_string(), reference(13))]
+        );
+        assert_eq!(
+            inherited.get("ProcSet"),
+            Some(&Object::Array([Object::Name("PDF".into())].into()))
+        );
+
+        let own = pages[1].flattened_resources(objects).unwrap();
+        assert_eq!(
+            names(&own, "Font"),
+            [
+                ("F1".to_string(), reference(10)),
+                ("F2".to_string(), reference(12)),
+                ("F3".to_string(), reference(14)),
+            ]
+        );
+        assert_eq!(
+            own.get("ProcSet"),
+            Some(&Object::Array(
+                [Object::Name("PDF".into()), Object::Name("Text".into())].into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_drawn_x_objects() {
+        let form = "/Type /XObject /Subtype /Form /BBox [0 0 1 1]";
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(
+                2,
+                "<< /Type /Pages /Kids [3 0 R 4 0 R 5 0 R] /Count 3 /MediaBox [0 0 10 10] \
+                 /Resources 6 0 R >>",
+            )
+            .object(3, "<< /Type /Page /Parent 2 0 R /Contents 10 0 R >>")
+            .object(4, "<< /Type /Page /Parent 2 0 R /Contents 11 0 R >>")
+            .object(5, "<< /Type /Page /Parent 2 0 R /Contents 12 0 R >>")
+            .object(
+                6,
+                "<< /XObject << /Fm1 7 0 R /Fm2 8 0 R /Fm3 9 0 R /Im1 13 0 R >> >>",
+            )
+            .stream(7, &format!("{form} /Resources 6 0 R"), b"/Im1 Do")
+            .stream(8, form, b"/Fm3 Do")
+            .stream(
+                9,
+                &format!("{form} /Resources << /XObject << /Self 9 0 R >> >>"),
+                b"/Self Do",
+            )
+            .stream(10, "", b"/Fm1 Do /Im1 Do /Missing Do")
+            .stream(11, "", b"/Fm2 Do")
+            .stream(12, "", b"/Fm3 Do")
+            .stream(
+                13,
+                "/Type /XObject /Subtype /Image /Width 1 /Height 1 \
+                 /ColorSpace /DeviceGray /BitsPerComponent 8",
+                b"\x00",
+            )
+            .section("/Root 1 0 R")
+            .write("drawn-x-objects");
+        let mut document = Document::from_path(&path).unwrap();
+        let pages = document.pages().collect::<crate::Result<Vec<_>>>().unwrap();
+        let objects = document.objects_mut();
+
+        let reference = |id| IndirectReference { id, gen_id: 0 };
+        let test_cases = [
+            (
+                "shared resources",
+                &pages[0],
+                Ok(vec![reference(7), reference(13)]),
+            ),
+            ("nested form drawing itself", &pages[1], Err(9)),
+            ("form drawing itself", &pages[2], Err(9)),
+        ];
+
+        for (name, page, expected) in test_cases {
+            let result = page.drawn_x_objects(objects);
+            match expected {
+                Ok(expected) => assert_eq!(result.unwrap(), expected, "Test '{name}' failed"),
+                Err(id) => {
+                    let message = result.unwrap_err().full_message();
+                    assert!(
+                        message.contains(&format!("Form XObject {} draws itself", reference(id))),
+                        "Test '{name}' failed: {message}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+            .object(
+                3,
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+                 /CropBox [36 36 576 756] /Resources << >> /Contents 4 0 R >>",
+            )
+            .stream(
+                4,
+                "",
+                b"0 0 1 rg 72 600 200 100 re f BT /F1 24 Tf 72 720 Td (Hello) Tj ET",
+            )
+            .section("/Root 1 0 R")
+            .write("page-svg");
+        let mut document = Document::from_path(&path).unwrap();
+        let page = document.pages().next().unwrap().unwrap();
+
+        let svg = page.to_svg().unwrap();
+
+        assert!(svg.contains("width=\"540\" height=\"720\""));
+        assert!(
+            svg.contains("<rect x=\"36\" y=\"56\" width=\"200\" height=\"100\" fill=\"#0000ff\"/>")
+        );
+        assert!(svg.contains(">Hello</text>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_transition() {
+        let test_cases = [
+            (
+                "wipe",
+                "/Trans << /S /Wipe /D 2 /Di 90 >>",
+                Some(Transition {
+                    style: TransitionStyle::Wipe,
+                    duration: 2.0,
+                    dimension: None,
+                    direction: Some(90),
+                }),
+            ),
+            (
+                "defaults",
+                "/Trans << >>",
+                Some(Transition {
+                    style: TransitionStyle::Replace,
+                    duration: 1.0,
+                    dimension: None,
+                    direction: None,
+                }),
+            ),
+            (
+                "indirect split without direction",
+                "/Trans 4 0 R",
+                Some(Transition {
+                    style: TransitionStyle::Split,
+                    duration: 0.5,
+                    dimension: Some(TransitionDimension::Vertical),
+                    direction: None,
+                }),
+            ),
+            ("absent", "", None),
+        ];
+
+        for (name, trans, expected) in test_cases {
+            let path = PdfBuilder::new()
+                .object(1, CATALOG)
+                .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                .object(
+                    3,
+                    &format!(
+                        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] /Resources << >> {trans} >>"
+                    ),
+                )
+                .object(4, "<< /S /Split /D 0.5 /Dm /V /Di /None >>")
+                .section("/Root 1 0 R")
+                .write("page-transition");
+            let mut document = Document::from_path(&path).unwrap();
+            let page = document.pages().next().unwrap().unwrap();
+
+            assert_eq!(page.transition(), expected.as_ref(), "Test '{name}' failed");
+        }
+    }
+
+    #[test]
+    fn test_transparency_group() {
+        let test_cases = [
+            (
+                "isolated RGB group",
+                "/Group << /S /Transparency /I true /CS /DeviceRGB >>",
+                Some(TransparencyGroup {
+                    color_space: Some(ColorSpace::DeviceRGB),
+                    isolated: true,
+                    knockout: false,
+                }),
+            ),
+            (
+                "indirect knockout group",
+                "/Group 4 0 R",
+                Some(TransparencyGroup {
+                    color_space: None,
+                    isolated: false,
+                    knockout: true,
+                }),
+            ),
+            ("other subtype", "/Group << /S /Other /I true >>", None),
+            ("absent", "", None),
+        ];
+
+        for (name, group, expected) in test_cases {
+            let path = PdfBuilder::new()
+                .object(1, CATALOG)
+                .object(2, "<< /Type /Pages /Kids [3 0 R] /Count 1 >>")
+                .object(
+                    3,
+                    &format!(
+                        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] /Resources << >> {group} >>"
+                    ),
+                )
+                .object(4, "<< /Type /Group /S /Transparency /K true >>")
+                .section("/Root 1 0 R")
+                .write("transparency-group");
+            let mut document = Document::from_path(&path).unwrap();
+            let page = document.pages().next().unwrap().unwrap();
+
+            assert_eq!(
+                page.transparency_group(),
+                expected.as_ref(),
+                "Test '{name}' failed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_thumbnail() {
+        let page = "/Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] /Resources << >>";
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(2, "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>")
+            .object(3, &format!("<< {page} /Thumb 5 0 R >>"))
+            .object(4, &format!("<< {page} >>"))
+            .stream(
+                5,
+                "/Width 2 /Height 3 /ColorSpace /DeviceRGB /BitsPerComponent 8",
+                b"\xff\x00\x00\x00\xff\x00\x00\x00\xff\xff\xff\xff\x00\x00\x00\x80\x80\x80",
+            )
+            .section("/Root 1 0 R")
+            .write("page-thumbnail");
+        let mut document = Document::from_path(&path).unwrap();
+        let pages = document
+            .pages()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        let objects = document.objects_mut();
+
+        let thumbnail = pages[0].thumbnail(objects).unwrap().unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (2, 3));
+        assert_eq!(thumbnail.color_space, Some(ColorSpace::DeviceRGB));
+        assert_eq!(thumbnail.bits_per_component, Some(8));
+        assert_eq!(thumbnail.format, None);
+        assert_eq!(thumbnail.data.len(), thumbnail.width * thumbnail.height * 3);
+        assert_eq!(&thumbnail.data[..3], b"\xff\x00\x00");
+
+        assert!(pages[1].thumbnail(objects).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_inherited_attribute() {
+        let path = PdfBuilder::new()
+            .object(1, CATALOG)
+            .object(
+                2,
+                "<< /Type /Pages /Kids [3 0 R] /Count 2 /MediaBox [0 0 10 10] \
+                 /Resources << >> /Custom (root) /Shared 7 0 R >>",
+            )
+            .object(
+                3,
+                "<< /Type /Pages /Parent 2 0 R /Kids [4 0 R 5 0 R] /Count 2 /Level 1 >>",
+            )
+            .object(4, "<< /Type /Page /Parent 3 0 R >>")
+            .object(5, "<< /Type /Page /Parent 3 0 R /Custom (own) /Level 2 >>")
+            .object(7, "(indirect)")
+            .section("/Root 1 0 R")
+            .write("page-inherited-attribute");
+        let mut document = Document::from_path(&path).unwrap();
+        let pages = document
+            .pages()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        let objects = document.objects_mut();
+
+        struct TestCase {
+            name: &'static str,
+            page: usize,
+            key: &'static str,
+            expected: Option<Object>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "from grandparent",
+                page: 0,
+                key: "Custom",
+                expected: Some(Object::string("root")),
+            },
+            TestCase {
+                name: "from parent",
+                page: 0,
+                key: "Level",
+                expected: Some(Object::integer(1)),
+            },
+            TestCase {
+                name: "own value wins",
+                page: 1,
+                key: "Custom",
+                expected: Some(Object::string("own")),
+            },
+            TestCase {
+                name: "own value wins over parent",
+                page: 1,
+                key: "Level",
+                expected: Some(Object::integer(2)),
+            },
+            TestCase {
+                name: "reference kept",
+                page: 1,
+                key: "Shared",
+                expected: Some(Object::IndirectReference(IndirectReference {
+                    id: 7,
+                    gen_id: 0,
+                })),
+            },
+            TestCase {
+                name: "modeled key",
+                page: 0,
+                key: "MediaBox",
+                expected: Some(Object::Array([0, 0, 10, 10].map(Object::integer).into())),
+            },
+            TestCase {
+                name: "absent",
+                page: 0,
+                key: "Missing",
+                expected: None,
+            },
+        ];
+
+        for case in test_cases {
+            let value = pages[case.page]
+                .inherited_attribute(case.key, objects)
+                .unwrap();
+            assert_eq!(value, case.expected, "Test '{}' failed", case.name);
+        }
+    }
 }
@@ -0,0 +1,170 @@
+use snafu::{OptionExt, ResultExt, Snafu};
+
+use crate::{
+    objects::Objects,
+    structures::destination::Destination,
+    types::{Dictionary, IndirectReference, Object, Rectangle},
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(source(from(error::Error, Box::new)))]
+pub struct Error(Box<error::Error>);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Annotation of a page (`/Annots`), of any subtype.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// Indirect reference of the annotation, `None` if it's stored inline
+    pub reference: Option<IndirectReference>,
+    /// Annotation type (`/Subtype`), e.g. `Link`, `Text`, `Popup` or `Widget`
+    pub subtype: String,
+    /// Location on the page in default user space (`/Rect`)
+    pub rect: Rectangle,
+    /// Text shown for the annotation or its alternate description (`/Contents`)
+    pub contents: Option<String>,
+    /// Target of a `/Link` annotation, `None` for every other subtype
+    pub link: Option<LinkTarget>,
+}
+
+/// Where activating a link annotation leads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkTarget {
+    /// Explicit destination in this document
+    Destination(Destination),
+    /// Named destination, see [`crate::Document::named_destination`]
+    Named(String),
+    /// `/URI` action
+    Uri(String),
+    /// Any other action, by its type (`/S`)
+    Action(String),
+}
+
+impl Annotation {
+    /// Reads an annotation dictionary or a reference to it.
+    pub fn from_object(object: &Object, objects: &mut Objects) -> Result<Self> {
+        let reference = object.as_indirect_ref().ok().copied();
+        let object = object.direct(objects).into_owned();
+        let dictionary = object
+            .as_dictionary()
+            .context(error::InvalidType { field: "Annots" })?;
+
+        let subtype = dictionary
+            .get_name("Subtype")
+            .context(error::FieldNotFound { field: "Subtype" })?
+            .context(error::InvalidType { field: "Subtype" })?
+            .to_string();
+
+        let rect = dictionary
+            .get("Rect")
+            .context(error::FieldNotFound { field: "Rect" })?
+            .direct(objects)
+            .as_array()
+            .rectangle()
+            .context(error::InvalidArray { field: "Rect" })?;
+
+        let contents = dictionary
+            .get("Contents")
+            .map(|object| {
+                object
+                    .direct(objects)
+                    .as_string()
+                    .map(|string| string.to_text_string())
+            })
+            .transpose()
+            .context(error::InvalidType { field: "Contents" })?;
+
+        let link = if subtype == "Link" {
+            read_link(dictionary, objects)?
+        } else {
+            None
+        };
+
+        Ok(Self {
+            reference,
+            subtype,
+            rect,
+            contents,
+            link,
+        })
+    }
+}
+
+/// Target of a link from its `/Dest` entry or its `/A` action.
+fn read_link(dictionary: &Dictionary, objects: &mut Objects) -> Result<Option<LinkTarget>> {
+    if let Some(destination) = dictionary.get("Dest") {
+        return read_destination(destination, objects).map(Some);
+    }
+
+    let Some(action) = dictionary.get("A") else {
+        return Ok(None);
+    };
+    let action = action.direct(objects).into_owned();
+    let action = action
+        .as_dictionary()
+        .context(error::InvalidType { field: "A" })?;
+    let kind = action
+        .get_name("S")
+        .context(error::FieldNotFound { field: "S" })?
+        .context(error::InvalidType { field: "S" })?;
+
+    let target = match kind {
+        "GoTo" => read_destination(
+            action
+                .get("D")
+                .context(error::FieldNotFound { field: "D" })?,
+            objects,
+        )?,
+        "URI" => LinkTarget::Uri(
+            action
+                .get("URI")
+                .context(error::FieldNotFound { field: "URI" })?
+                .direct(objects)
+                .as_string()
+                .context(error::InvalidType { field: "URI" })?
+                .to_text_string(),
+        ),
+        kind => LinkTarget::Action(kind.to_string()),
+    };
+
+    Ok(Some(target))
+}
+
+fn read_destination(object: &Object, objects: &mut Objects) -> Result<LinkTarget> {
+    let target = match object.direct(objects).into_owned() {
+        Object::Name(name) => LinkTarget::Named(name.to_string()),
+        Object::String(string) => LinkTarget::Named(string.to_text_string()),
+        object => LinkTarget::Destination(
+            Destination::from_object(&object, objects).context(error::InvalidDestination)?,
+        ),
+    };
+
+    Ok(target)
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Required field `{field}` not found"))]
+        FieldNotFound { field: &'static str },
+
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidType {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Invalid array data for field `{field}`"))]
+        InvalidArray {
+            field: &'static str,
+            source: crate::types::array::Error,
+        },
+
+        #[snafu(display("Invalid link destination"))]
+        InvalidDestination {
+            source: crate::structures::destination::Error,
+        },
+    }
+}
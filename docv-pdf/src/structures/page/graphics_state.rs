@@ -0,0 +1,218 @@
+use crate::types::{ContentOp, Matrix, Name, Object};
+
+/// Graphics state parameters tracked while walking a content stream.
+///
+/// Only the parameters needed to position text and images are tracked.
+/// They are saved by `q` and restored by `Q`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GraphicsState {
+    /// Current transformation matrix, maps user space to default user space
+    pub ctm: Matrix,
+    /// Font resource name selected with `Tf`
+    pub font: Option<Name>,
+    /// Font size selected with `Tf`
+    pub font_size: f64,
+    /// Text leading set by `TL` or `TD`
+    pub leading: f64,
+}
+
+/// Content stream operation with the state in effect after it was applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatefulOp {
+    pub op: ContentOp,
+    pub state: GraphicsState,
+    /// Text matrix (`Tm`), not part of the graphics state and reset by `BT`
+    pub text_matrix: Matrix,
+}
+
+/// Iterator over content operations that interprets state operators.
+///
+/// Follows the imaging model: `q` pushes a copy of the graphics state, `Q`
+/// pops it, `cm` premultiplies the CTM and text positioning operators update
+/// the text and line matrices. Operators with malformed operands and an
+/// unbalanced `Q` leave the state unchanged.
+#[derive(Debug)]
+pub struct ContentOpsWithState {
+    operations: std::vec::IntoIter<ContentOp>,
+    state: GraphicsState,
+    stack: Vec<GraphicsState>,
+    text_matrix: Matrix,
+    line_matrix: Matrix,
+}
+
+impl ContentOpsWithState {
+    pub fn new(operations: Vec<ContentOp>) -> Self {
+        Self {
+            operations: operations.into_iter(),
+            state: GraphicsState::default(),
+            stack: Vec::new(),
+            text_matrix: Matrix::IDENTITY,
+            line_matrix: Matrix::IDENTITY,
+        }
+    }
+
+    fn apply(&mut self, op: &ContentOp) {
+        if let ("Tf", [Object::Name(font), size]) = (op.operator.as_str(), op.operands.as_slice())
+            && let Ok(size) = size.as_float()
+        {
+            self.state.font = Some(font.clone());
+            self.state.font_size = size;
+            return;
+        }
+
+        let numbers = op
+            .operands
+            .iter()
+            .map(|operand| operand.as_float().ok())
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+
+        match (op.operator.as_str(), numbers.as_slice()) {
+            ("q", _) => self.stack.push(self.state.clone()),
+            ("Q", _) => {
+                if let Some(state) = self.stack.pop() {
+                    self.state = state;
+                }
+            }
+            ("cm", &[a, b, c, d, e, f]) => {
+                self.state.ctm = Matrix::new(a, b, c, d, e, f).multiply(&self.state.ctm);
+            }
+            ("BT", _) => {
+                self.text_matrix = Matrix::IDENTITY;
+                self.line_matrix = Matrix::IDENTITY;
+            }
+            ("Tm", &[a, b, c, d, e, f]) => {
+                self.text_matrix = Matrix::new(a, b, c, d, e, f);
+                self.line_matrix = self.text_matrix;
+            }
+            ("Td", &[tx, ty]) => self.next_line(tx, ty),
+            ("TD", &[tx, ty]) => {
+                self.state.leading = -ty;
+                self.next_line(tx, ty);
+            }
+            ("TL", &[leading]) => self.state.leading = leading,
+            ("T*", _) | ("'", _) | ("\"", _) => self.next_line(0.0, -self.state.leading),
+            _ => {}
+        }
+    }
+
+    /// Moves to the start of the next line, offset from the start of the current one.
+    fn next_line(&mut self, tx: f64, ty: f64) {
+        self.line_matrix = Matrix::translation(tx, ty).multiply(&self.line_matrix);
+        self.text_matrix = self.line_matrix;
+    }
+}
+
+impl Iterator for ContentOpsWithState {
+    type Item = StatefulOp;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let op = self.operations.next()?;
+        self.apply(&op);
+
+        Some(StatefulOp {
+            op,
+            state: self.state.clone(),
+            text_matrix: self.text_matrix,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.operations.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{objects::Strictness, parser::parse_content_stream};
+
+    #[test]
+    fn test_content_ops_with_state() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            expected_ctm: Matrix,
+            expected_text_matrix: Matrix,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "no state operators",
+                input: b"0 0 m 10 10 l S",
+                expected_ctm: Matrix::IDENTITY,
+                expected_text_matrix: Matrix::IDENTITY,
+            },
+            TestCase {
+                name: "concatenated cm",
+                input: b"1 0 0 1 10 20 cm 2 0 0 2 0 0 cm 0 0 m",
+                expected_ctm: Matrix::new(2.0, 0.0, 0.0, 2.0, 10.0, 20.0),
+                expected_text_matrix: Matrix::IDENTITY,
+            },
+            TestCase {
+                name: "q and Q restore ctm",
+                input: b"1 0 0 1 5 5 cm q 3 0 0 3 0 0 cm q 1 0 0 1 7 7 cm Q Q /Im1 Do",
+                expected_ctm: Matrix::translation(5.0, 5.0),
+                expected_text_matrix: Matrix::IDENTITY,
+            },
+            TestCase {
+                name: "nested state inside q",
+                input: b"q 1 0 0 1 5 5 cm q 3 0 0 3 0 0 cm Q /Im1 Do Q",
+                expected_ctm: Matrix::IDENTITY,
+                expected_text_matrix: Matrix::IDENTITY,
+            },
+            TestCase {
+                name: "unbalanced Q is ignored",
+                input: b"1 0 0 1 5 5 cm Q",
+                expected_ctm: Matrix::translation(5.0, 5.0),
+                expected_text_matrix: Matrix::IDENTITY,
+            },
+            TestCase {
+                name: "text positioning",
+                input: b"BT /F1 12 Tf 14 TL 72 700 Td (a) Tj T* (b) Tj 10 -2 TD ET",
+                expected_ctm: Matrix::IDENTITY,
+                expected_text_matrix: Matrix::translation(82.0, 684.0),
+            },
+            TestCase {
+                name: "malformed cm is ignored",
+                input: b"1 0 0 1 cm",
+                expected_ctm: Matrix::IDENTITY,
+                expected_text_matrix: Matrix::IDENTITY,
+            },
+        ];
+
+        for case in &test_cases {
+            let operations = parse_content_stream(case.input, Strictness::default()).unwrap();
+            let last = ContentOpsWithState::new(operations).last().unwrap();
+
+            assert_eq!(
+                last.state.ctm, case.expected_ctm,
+                "Test '{}' failed: wrong ctm",
+                case.name
+            );
+            assert_eq!(
+                last.text_matrix, case.expected_text_matrix,
+                "Test '{}' failed: wrong text matrix",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_text_state_is_saved() {
+        let operations = parse_content_stream(
+            b"BT /F1 12 Tf q /F2 8 Tf 16 TL Q (a) Tj ET",
+            Strictness::default(),
+        )
+        .unwrap();
+        let states = ContentOpsWithState::new(operations)
+            .map(|op| op.state)
+            .collect::<Vec<_>>();
+
+        assert_eq!(states[3].font, Some("F2".into()));
+        assert_eq!(states[4].leading, 16.0);
+        assert_eq!(states[6].font, Some("F1".into()));
+        assert_eq!(states[6].font_size, 12.0);
+        assert_eq!(states[6].leading, 0.0);
+    }
+}
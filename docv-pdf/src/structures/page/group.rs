@@ -0,0 +1,95 @@
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    objects::Objects,
+    structures::color_space::ColorSpace,
+    types::{Dictionary, Object},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Attributes of the page as a transparency group (`/Group` with `/S /Transparency`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransparencyGroup {
+    /// Blending color space of the group (`/CS`)
+    pub color_space: Option<ColorSpace>,
+    /// Whether the group is composited on a fully transparent backdrop (`/I`)
+    pub isolated: bool,
+    /// Whether objects of the group are composited with the backdrop only,
+    /// not with earlier objects of the group (`/K`)
+    pub knockout: bool,
+}
+
+impl TransparencyGroup {
+    /// Reads a group attributes dictionary or a reference to it.
+    ///
+    /// # Returns
+    /// `None` for groups of another subtype than `/Transparency`, the only
+    /// one the specification defines
+    pub fn from_object(object: &Object, objects: &mut Objects) -> Result<Option<Self>> {
+        let object = object.direct(objects).into_owned();
+        let dictionary = object
+            .as_dictionary()
+            .context(error::InvalidType { field: "Group" })?;
+
+        Self::from_dictionary(dictionary, objects)
+    }
+
+    fn from_dictionary(dictionary: &Dictionary, objects: &mut Objects) -> Result<Option<Self>> {
+        let subtype = dictionary
+            .get("S")
+            .map(|object| object.direct(objects).as_name().cloned())
+            .transpose()
+            .context(error::InvalidType { field: "S" })?;
+        if subtype.as_ref().map(|name| name.as_str()) != Some("Transparency") {
+            tracing::warn!("Ignoring group of subtype {subtype:?}");
+            return Ok(None);
+        }
+
+        let color_space = dictionary
+            .get("CS")
+            .map(|object| ColorSpace::from_object(object, objects))
+            .transpose()
+            .context(error::InvalidColorSpace)?;
+
+        let mut flag = |key: &'static str| -> Result<bool> {
+            let value = dictionary
+                .get(key)
+                .map(|object| object.direct(objects).as_bool())
+                .transpose()
+                .context(error::InvalidType { field: key })?
+                .unwrap_or(false);
+
+            Ok(value)
+        };
+        let isolated = flag("I")?;
+        let knockout = flag("K")?;
+
+        Ok(Some(Self {
+            color_space,
+            isolated,
+            knockout,
+        }))
+    }
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidType {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Invalid blending color space"))]
+        InvalidColorSpace {
+            source: crate::structures::color_space::Error,
+        },
+    }
+}
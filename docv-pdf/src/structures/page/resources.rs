@@ -136,7 +136,7 @@ impl Resources {
                 )
             });
 
-        let font = dictionary.get("Font");
+        let font = dictionary.get("Font").map(|object| object.direct(objects));
         let font = font
             .as_ref()
             .map(|object| object.as_dictionary())
@@ -204,6 +204,30 @@ impl Resources {
             ),
         })
     }
+
+    /// Entry `name` of the `/ColorSpace` subdictionary.
+    pub(crate) fn color_space(&self, name: &str) -> Option<&Object> {
+        match self.data.get(name)? {
+            Resource::ColorSpace { object } => Some(object),
+            _ => None,
+        }
+    }
+
+    /// Entry `name` of the `/XObject` subdictionary.
+    pub(crate) fn x_object(&self, name: &str) -> Option<&Object> {
+        match self.data.get(name)? {
+            Resource::XObject { object } => Some(object),
+            _ => None,
+        }
+    }
+
+    /// Entries of the `/XObject` subdictionary.
+    pub(crate) fn x_objects(&self) -> impl Iterator<Item = &Object> {
+        self.data.values().filter_map(|resource| match resource {
+            Resource::XObject { object } => Some(object),
+            _ => None,
+        })
+    }
 }
 
 impl std::fmt::Display for Resource {
@@ -0,0 +1,361 @@
+use std::fmt::Write;
+
+use crate::{
+    structures::page::{graphics_state::StatefulOp, text::GLYPH_WIDTH},
+    types::{Matrix, Object, Rectangle},
+};
+
+/// Paint parameters the SVG output needs but the graphics state doesn't track.
+#[derive(Debug, Clone)]
+struct Paint {
+    fill: String,
+    stroke: String,
+    line_width: f64,
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Self {
+            fill: "#000000".to_string(),
+            stroke: "#000000".to_string(),
+            line_width: 1.0,
+        }
+    }
+}
+
+/// Subpath of the current path, in SVG coordinates.
+#[derive(Debug)]
+enum Shape {
+    /// Axis-aligned rectangle from `re`
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    /// Path data of `m`, `l`, `c`, `v`, `y`, `h` and rotated `re`
+    Path(String),
+}
+
+/// Renders content operations as an SVG document covering `crop_box`.
+///
+/// Paths built with `m`, `l`, `c`, `v`, `y`, `h` and `re` are drawn by the
+/// painting operators with the gray, RGB or CMYK color and the line width
+/// currently set. Text is drawn with a generic font at the position given
+/// by the text matrix, advancing half an em per glyph as font metrics
+/// are not read. Images, shadings, clipping and patterns are skipped.
+pub fn render_svg(crop_box: &Rectangle, operations: impl Iterator<Item = StatefulOp>) -> String {
+    let width = crop_box.right() - crop_box.left();
+    let height = crop_box.top() - crop_box.bottom();
+    // PDF user space grows upwards from the bottom left of the crop box,
+    // SVG space grows downwards from the top left.
+    let to_svg = Matrix::new(1.0, 0.0, 0.0, -1.0, -crop_box.left(), crop_box.top());
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = number(width),
+        h = number(height),
+    );
+
+    let mut paint = Paint::default();
+    let mut stack = Vec::new();
+    let mut shapes = Vec::new();
+    let mut line_start = None;
+    let mut advance = 0.0;
+
+    for StatefulOp {
+        op,
+        state,
+        text_matrix,
+    } in operations
+    {
+        let matrix = state.ctm.multiply(&to_svg);
+        let point = |x: f64, y: f64| {
+            let (x, y) = matrix.transform_point(x, y);
+            format!("{} {}", number(x), number(y))
+        };
+        let numbers = op
+            .operands
+            .iter()
+            .map(|operand| operand.as_float().ok())
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+
+        match (op.operator.as_str(), numbers.as_slice()) {
+            ("q", _) => stack.push(paint.clone()),
+            ("Q", _) => {
+                if let Some(saved) = stack.pop() {
+                    paint = saved;
+                }
+            }
+            ("w", &[line_width]) => paint.line_width = line_width,
+            ("g", &[gray]) => paint.fill = rgb(gray, gray, gray),
+            ("G", &[gray]) => paint.stroke = rgb(gray, gray, gray),
+            ("rg", &[r, g, b]) => paint.fill = rgb(r, g, b),
+            ("RG", &[r, g, b]) => paint.stroke = rgb(r, g, b),
+            ("k", &[c, m, y, k]) => paint.fill = cmyk(c, m, y, k),
+            ("K", &[c, m, y, k]) => paint.stroke = cmyk(c, m, y, k),
+            ("m", &[x, y]) => shapes.push(Shape::Path(format!("M{}", point(x, y)))),
+            ("l", &[x, y]) => extend_path(&mut shapes, format!(" L{}", point(x, y))),
+            ("c", &[x1, y1, x2, y2, x3, y3]) => extend_path(
+                &mut shapes,
+                format!(" C{} {} {}", point(x1, y1), point(x2, y2), point(x3, y3)),
+            ),
+            // `v` and `y` repeat the current point as a control point, which
+            // isn't known here, so they are drawn as quadratic curves.
+            ("v" | "y", &[x1, y1, x2, y2]) => extend_path(
+                &mut shapes,
+                format!(" Q{} {}", point(x1, y1), point(x2, y2)),
+            ),
+            ("h", _) => extend_path(&mut shapes, " Z".to_string()),
+            ("re", &[x, y, w, h]) if matrix.b == 0.0 && matrix.c == 0.0 => {
+                let (x1, y1) = matrix.transform_point(x, y);
+                let (x2, y2) = matrix.transform_point(x + w, y + h);
+                shapes.push(Shape::Rect {
+                    x: x1.min(x2),
+                    y: y1.min(y2),
+                    width: (x2 - x1).abs(),
+                    height: (y2 - y1).abs(),
+                });
+            }
+            ("re", &[x, y, w, h]) => shapes.push(Shape::Path(format!(
+                "M{} L{} L{} L{} Z",
+                point(x, y),
+                point(x + w, y),
+                point(x + w, y + h),
+                point(x, y + h)
+            ))),
+            ("f" | "F" | "f*" | "S" | "s" | "B" | "B*" | "b" | "b*", _) => {
+                let operator = op.operator.as_str();
+                let fill = !matches!(operator, "S" | "s");
+                let stroke = !matches!(operator, "f" | "F" | "f*");
+                let even_odd = operator.ends_with('*');
+                let close = matches!(operator, "s" | "b" | "b*");
+
+                for shape in shapes.drain(..) {
+                    write_shape(&mut svg, shape, &paint, fill, stroke, even_odd, close);
+                }
+            }
+            ("n", _) => shapes.clear(),
+            _ => {}
+        }
+
+        let text = match (op.operator.as_str(), op.operands.as_slice()) {
+            ("Tj" | "'", [Object::String(string)]) | ("\"", [_, _, Object::String(string)]) => {
+                vec![Object::String(string.clone())]
+            }
+            ("TJ", [Object::Array(array)]) => array.to_vec(),
+            _ => continue,
+        };
+
+        if line_start != Some(text_matrix) {
+            line_start = Some(text_matrix);
+            advance = 0.0;
+        }
+
+        let text_to_svg = text_matrix.multiply(&matrix);
+        for part in text {
+            match part {
+                Object::String(string) => {
+                    let part = string.to_text_string();
+                    if part.is_empty() {
+                        continue;
+                    }
+
+                    let (x, y) = text_to_svg.transform_point(advance, 0.0);
+                    let size = state.font_size * text_to_svg.c.hypot(text_to_svg.d);
+                    let _ = writeln!(
+                        svg,
+                        "<text x=\"{}\" y=\"{}\" font-size=\"{}\" fill=\"{}\">{}</text>",
+                        number(x),
+                        number(y),
+                        number(size),
+                        paint.fill,
+                        escape(&part)
+                    );
+
+                    advance += part.chars().count() as f64 * GLYPH_WIDTH * state.font_size;
+                }
+                Object::Numeric(_) => {
+                    let adjustment = part.as_float().unwrap_or_default();
+                    advance -= adjustment / 1000.0 * state.font_size;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Appends to the last subpath, starting one at the origin if there is none.
+fn extend_path(shapes: &mut Vec<Shape>, data: String) {
+    match shapes.last_mut() {
+        Some(Shape::Path(path)) => path.push_str(&data),
+        _ => shapes.push(Shape::Path(format!("M0 0{data}"))),
+    }
+}
+
+fn write_shape(
+    svg: &mut String,
+    shape: Shape,
+    paint: &Paint,
+    fill: bool,
+    stroke: bool,
+    even_odd: bool,
+    close: bool,
+) {
+    let mut attributes = format!(
+        "fill=\"{}\"",
+        if fill { paint.fill.as_str() } else { "none" }
+    );
+    if fill && even_odd {
+        attributes.push_str(" fill-rule=\"evenodd\"");
+    }
+    if stroke {
+        let _ = write!(
+            attributes,
+            " stroke=\"{}\" stroke-width=\"{}\"",
+            paint.stroke,
+            number(paint.line_width)
+        );
+    }
+
+    let _ = match shape {
+        Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+        } => writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {attributes}/>",
+            number(x),
+            number(y),
+            number(width),
+            number(height)
+        ),
+        Shape::Path(mut data) => {
+            if close && !data.ends_with('Z') {
+                data.push_str(" Z");
+            }
+            writeln!(svg, "<path d=\"{data}\" {attributes}/>")
+        }
+    };
+}
+
+fn rgb(r: f64, g: f64, b: f64) -> String {
+    let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    format!("#{:02x}{:02x}{:02x}", channel(r), channel(g), channel(b))
+}
+
+fn cmyk(c: f64, m: f64, y: f64, k: f64) -> String {
+    rgb(
+        (1.0 - c) * (1.0 - k),
+        (1.0 - m) * (1.0 - k),
+        (1.0 - y) * (1.0 - k),
+    )
+}
+
+/// Formats a coordinate with at most three decimals.
+fn number(value: f64) -> String {
+    let formatted = format!("{value:.3}");
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    match formatted {
+        "-0" => "0".to_string(),
+        formatted => formatted.to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            char => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        objects::Strictness, parser::parse_content_stream,
+        structures::page::graphics_state::ContentOpsWithState,
+    };
+
+    #[test]
+    fn test_render_svg() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            expected: &'static [&'static str],
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "filled rectangle",
+                input: b"1 0 0 rg 10 20 30 40 re f",
+                expected: &[
+                    "<rect x=\"10\" y=\"40\" width=\"30\" height=\"40\" fill=\"#ff0000\"/>",
+                ],
+            },
+            TestCase {
+                name: "stroked path",
+                input: b"2 w 0 0 1 RG 0 0 m 50 50 l 100 0 l h S",
+                expected: &[
+                    "<path d=\"M0 100 L50 50 L100 100 Z\" fill=\"none\" stroke=\"#0000ff\" stroke-width=\"2\"/>",
+                ],
+            },
+            TestCase {
+                name: "scaled by cm",
+                input: b"2 0 0 2 0 0 cm 0 0 10 10 re B*",
+                expected: &[
+                    "<rect x=\"0\" y=\"80\" width=\"20\" height=\"20\" fill=\"#000000\" fill-rule=\"evenodd\" stroke=\"#000000\" stroke-width=\"1\"/>",
+                ],
+            },
+            TestCase {
+                name: "discarded path",
+                input: b"0 0 10 10 re n 0.5 g 0 0 5 5 re f",
+                expected: &["<rect x=\"0\" y=\"95\" width=\"5\" height=\"5\" fill=\"#808080\"/>"],
+            },
+            TestCase {
+                name: "positioned text",
+                input: b"BT /F1 10 Tf 10 50 Td (a<b) Tj [(c) -1000 (d)] TJ ET",
+                expected: &[
+                    "<text x=\"10\" y=\"50\" font-size=\"10\" fill=\"#000000\">a&lt;b</text>",
+                    "<text x=\"25\" y=\"50\" font-size=\"10\" fill=\"#000000\">c</text>",
+                    "<text x=\"40\" y=\"50\" font-size=\"10\" fill=\"#000000\">d</text>",
+                ],
+            },
+        ];
+
+        let crop_box = Rectangle::new(0.0, 0.0, 100.0, 100.0);
+        for case in &test_cases {
+            let operations = parse_content_stream(case.input, Strictness::default()).unwrap();
+            let svg = render_svg(&crop_box, ContentOpsWithState::new(operations));
+
+            assert!(
+                svg.starts_with(
+                    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"100\""
+                ),
+                "Test '{}' failed: wrong header",
+                case.name
+            );
+            let elements = svg
+                .lines()
+                .filter(|line| !line.starts_with("<svg") && !line.starts_with("</svg"))
+                .collect::<Vec<_>>();
+            assert_eq!(elements, case.expected, "Test '{}' failed", case.name);
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    structures::page::graphics_state::StatefulOp,
+    types::{ContentOp, Matrix, Object, Rectangle},
+};
+
+/// Settings of the plain text extractor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextOptions {
+    /// Smallest `TJ` adjustment, in thousandths of an em, treated as a word break.
+    ///
+    /// `TJ` numbers move the next glyph left, so only negative adjustments
+    /// (moving right) at least this large insert a space.
+    pub word_gap: f64,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self { word_gap: 200.0 }
+    }
+}
+
+/// Collects the text shown by a sequence of content operations.
+///
+/// Strings are decoded as PDF text strings, font encodings are not applied.
+/// Moving to a new line with `T*`, `'`, `"`, `TD` or a vertical `Td` starts
+/// a new output line.
+pub fn extract_text(operations: &[ContentOp], options: &TextOptions) -> String {
+    let mut text = String::new();
+
+    for op in operations {
+        match (op.operator.as_str(), op.operands.as_slice()) {
+            ("Tj", [Object::String(string)]) => text.push_str(&string.to_text_string()),
+            ("TJ", [Object::Array(array)]) => {
+                for element in array.iter() {
+                    match element {
+                        Object::String(string) => text.push_str(&string.to_text_string()),
+                        Object::Numeric(_)
+                            if element
+                                .as_float()
+                                .is_ok_and(|adjustment| -adjustment >= options.word_gap)
+                                && !text.ends_with(char::is_whitespace) =>
+                        {
+                            text.push(' ');
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            ("'", [Object::String(string)]) | ("\"", [_, _, Object::String(string)]) => {
+                new_line(&mut text);
+                text.push_str(&string.to_text_string());
+            }
+            ("T*", _) => new_line(&mut text),
+            ("Td" | "TD", [_, ty]) if ty.as_float().is_ok_and(|ty| ty != 0.0) => {
+                new_line(&mut text)
+            }
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn new_line(text: &mut String) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+/// Text shown by a single text showing operator, with its position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRun {
+    pub text: String,
+    /// Bounds of the run in default user space
+    pub bbox: Rectangle,
+    /// Font resource name selected with `Tf`
+    pub font: String,
+    /// Font size selected with `Tf`
+    pub size: f64,
+}
+
+/// Width of a glyph in text space units, used as font metrics are not read.
+pub(super) const GLYPH_WIDTH: f64 = 0.5;
+/// Extent of glyphs below and above the baseline in text space units.
+const DESCENT: f64 = -0.2;
+const ASCENT: f64 = 0.8;
+
+/// Collects text runs from content operations annotated with their state.
+///
+/// Glyph widths are estimated as half an em, so bounding boxes are
+/// approximate horizontally. Runs of consecutive operators without text
+/// positioning in between continue where the previous run ended.
+pub fn text_runs(operations: impl Iterator<Item = StatefulOp>) -> Vec<TextRun> {
+    let mut runs = Vec::new();
+    let mut line_start = None;
+    let mut advance = 0.0;
+
+    for StatefulOp {
+        op,
+        state,
+        text_matrix,
+    } in operations
+    {
+        let parts = match (op.operator.as_str(), op.operands.as_slice()) {
+            ("Tj" | "'", [Object::String(string)]) | ("\"", [_, _, Object::String(string)]) => {
+                vec![Object::String(string.clone())]
+            }
+            ("TJ", [Object::Array(array)]) => array.to_vec(),
+            _ => continue,
+        };
+
+        if line_start != Some(text_matrix) {
+            line_start = Some(text_matrix);
+            advance = 0.0;
+        }
+
+        let mut text = String::new();
+        let start = advance;
+        for part in &parts {
+            match part {
+                Object::String(string) => {
+                    let part = string.to_text_string();
+                    advance += part.chars().count() as f64 * GLYPH_WIDTH * state.font_size;
+                    text.push_str(&part);
+                }
+                Object::Numeric(_) => {
+                    let adjustment = part.as_float().unwrap_or_default();
+                    advance -= adjustment / 1000.0 * state.font_size;
+                }
+                _ => {}
+            }
+        }
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let matrix = text_matrix.multiply(&state.ctm);
+        runs.push(TextRun {
+            text,
+            bbox: bounding_box(
+                &matrix,
+                start,
+                DESCENT * state.font_size,
+                advance,
+                ASCENT * state.font_size,
+            ),
+            font: state
+                .font
+                .as_ref()
+                .map(|font| font.to_string())
+                .unwrap_or_default(),
+            size: state.font_size,
+        });
+    }
+
+    runs
+}
+
+/// Axis-aligned bounds of a text space rectangle after applying `matrix`.
+fn bounding_box(matrix: &Matrix, left: f64, bottom: f64, right: f64, top: f64) -> Rectangle {
+    let corners = [(left, bottom), (right, bottom), (left, top), (right, top)]
+        .map(|(x, y)| matrix.transform_point(x, y));
+
+    let (mut x1, mut y1) = corners[0];
+    let (mut x2, mut y2) = corners[0];
+    for (x, y) in corners {
+        x1 = x1.min(x);
+        y1 = y1.min(y);
+        x2 = x2.max(x);
+        y2 = y2.max(y);
+    }
+
+    Rectangle::new(x1, y1, x2, y2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        objects::Strictness, parser::parse_content_stream,
+        structures::page::graphics_state::ContentOpsWithState,
+    };
+
+    #[test]
+    fn test_extract_text() {
+        struct TestCase {
+            name: &'static str,
+            input: &'static [u8],
+            word_gap: f64,
+            expected: &'static str,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "word gap in TJ",
+                input: b"BT [(Hello) -250 (World)] TJ ET",
+                word_gap: 200.0,
+                expected: "Hello World",
+            },
+            TestCase {
+                name: "kerning is not a word gap",
+                input: b"BT [(W) 120 (orld) -30 (!)] TJ ET",
+                word_gap: 200.0,
+                expected: "World!",
+            },
+            TestCase {
+                name: "custom threshold",
+                input: b"BT [(Hello) -250 (World)] TJ ET",
+                word_gap: 300.0,
+                expected: "HelloWorld",
+            },
+            TestCase {
+                name: "no double spaces",
+                input: b"BT [(Hello ) -500 (World)] TJ ET",
+                word_gap: 200.0,
+                expected: "Hello World",
+            },
+            TestCase {
+                name: "line breaks",
+                input: b"BT 72 700 Td (one) Tj 0 -14 Td (two) Tj T* (three) Tj (four) ' 20 0 Td (!) Tj ET",
+                word_gap: 200.0,
+                expected: "one\ntwo\nthree\nfour!",
+            },
+        ];
+
+        for case in &test_cases {
+            let operations = parse_content_stream(case.input, Strictness::default()).unwrap();
+            let options = TextOptions {
+                word_gap: case.word_gap,
+            };
+
+            assert_eq!(
+                extract_text(&operations, &options),
+                case.expected,
+                "Test '{}' failed: wrong text",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_text_runs() {
+        let input = b"1 0 0 1 10 20 cm BT /F1 10 Tf 72 700 Td (Hi) Tj [(a) -1000 (b)] TJ \
+                      0 -12 Td (c) Tj ET";
+        let operations = parse_content_stream(input, Strictness::default()).unwrap();
+        let runs = text_runs(ContentOpsWithState::new(operations));
+
+        let expected = [
+            ("Hi", Rectangle::new(82.0, 718.0, 92.0, 728.0)),
+            ("ab", Rectangle::new(92.0, 718.0, 112.0, 728.0)),
+            ("c", Rectangle::new(82.0, 706.0, 87.0, 716.0)),
+        ];
+
+        assert_eq!(runs.len(), expected.len());
+        for (run, (text, bbox)) in runs.iter().zip(expected) {
+            assert_eq!(run.text, text);
+            assert_eq!(run.font, "F1");
+            assert_eq!(run.size, 10.0);
+            assert_eq!(run.bbox, bbox, "wrong bbox for {text:?}");
+        }
+    }
+}
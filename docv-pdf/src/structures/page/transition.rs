@@ -0,0 +1,161 @@
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    objects::Objects,
+    types::{Dictionary, Object},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Effect used when moving to a page in presentation mode (`/Trans`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    /// Transition style (`/S`), `Replace` if absent
+    pub style: TransitionStyle,
+    /// Duration of the effect in seconds (`/D`), 1 if absent
+    pub duration: f64,
+    /// Direction of the lines of `Split` and `Blinds` (`/Dm`)
+    pub dimension: Option<TransitionDimension>,
+    /// Direction of motion in degrees counterclockwise from left to right (`/Di`),
+    /// `None` if absent or if `/Di` is `/None`
+    pub direction: Option<i64>,
+}
+
+/// Visual effect of a page transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransitionStyle {
+    /// Lines sweeping across the screen from the middle or the edges
+    Split,
+    /// Multiple lines sweeping in the same direction
+    Blinds,
+    /// Rectangle growing or shrinking from the center
+    Box,
+    /// Single line sweeping across the screen
+    Wipe,
+    /// Old page dissolving gradually into the new one
+    Dissolve,
+    /// Like `Dissolve`, but the effect sweeps across the screen
+    Glitter,
+    /// New page replacing the old one at once
+    #[default]
+    Replace,
+    /// New page flying in
+    Fly,
+    /// Old page pushed off the screen by the new one
+    Push,
+    /// New page sliding over the old one
+    Cover,
+    /// Old page sliding off, uncovering the new one
+    Uncover,
+    /// Old page fading into the new one
+    Fade,
+}
+
+/// Orientation of the lines of a `Split` or `Blinds` transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionDimension {
+    Horizontal,
+    Vertical,
+}
+
+impl Transition {
+    /// Reads a transition dictionary or a reference to it.
+    pub fn from_object(object: &Object, objects: &mut Objects) -> Result<Self> {
+        let object = object.direct(objects).into_owned();
+        let dictionary = object
+            .as_dictionary()
+            .context(error::InvalidType { field: "Trans" })?;
+
+        Self::from_dictionary(dictionary, objects)
+    }
+
+    fn from_dictionary(dictionary: &Dictionary, objects: &mut Objects) -> Result<Self> {
+        let style = match dictionary.get("S") {
+            Some(object) => {
+                match object
+                    .direct(objects)
+                    .as_name()
+                    .context(error::InvalidType { field: "S" })?
+                    .as_str()
+                {
+                    "Split" => TransitionStyle::Split,
+                    "Blinds" => TransitionStyle::Blinds,
+                    "Box" => TransitionStyle::Box,
+                    "Wipe" => TransitionStyle::Wipe,
+                    "Dissolve" => TransitionStyle::Dissolve,
+                    "Glitter" => TransitionStyle::Glitter,
+                    "R" => TransitionStyle::Replace,
+                    "Fly" => TransitionStyle::Fly,
+                    "Push" => TransitionStyle::Push,
+                    "Cover" => TransitionStyle::Cover,
+                    "Uncover" => TransitionStyle::Uncover,
+                    "Fade" => TransitionStyle::Fade,
+                    style => {
+                        return Err(error::Error::UnknownStyle {
+                            style: style.to_string(),
+                        }
+                        .into());
+                    }
+                }
+            }
+            None => TransitionStyle::default(),
+        };
+
+        let duration = dictionary
+            .get("D")
+            .map(|object| object.direct(objects).as_float())
+            .transpose()
+            .context(error::InvalidType { field: "D" })?
+            .unwrap_or(1.0);
+
+        let dimension = dictionary
+            .get("Dm")
+            .map(|object| {
+                object
+                    .direct(objects)
+                    .as_name()
+                    .map(|name| match name.as_str() {
+                        "V" => TransitionDimension::Vertical,
+                        _ => TransitionDimension::Horizontal,
+                    })
+            })
+            .transpose()
+            .context(error::InvalidType { field: "Dm" })?;
+
+        let direction = match dictionary.get("Di").map(|object| object.direct(objects)) {
+            Some(object) if matches!(object.as_ref(), Object::Name(_)) => None,
+            Some(object) => Some(
+                object
+                    .as_integer()
+                    .context(error::InvalidType { field: "Di" })?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            style,
+            duration,
+            dimension,
+            direction,
+        })
+    }
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidType {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Unknown transition style `{style}`"))]
+        UnknownStyle { style: String },
+    }
+}
@@ -7,8 +7,10 @@ use crate::{
 };
 
 pub mod names;
+pub mod optional_content;
 pub mod pages_tree;
 pub mod version;
+pub mod viewer_preferences;
 
 #[derive(Debug, Snafu)]
 pub struct Error(error::Error);
@@ -22,25 +24,25 @@ pub struct Root {
     pub pages: PagesTreeNode,
     pub _page_labels: Option<Object>,
     pub names: Option<Names>,
-    pub _dests: Option<Object>,
-    pub _viewer_preferences: Option<Object>,
+    pub dests: Option<Object>,
+    pub viewer_preferences: Option<Object>,
     pub page_layout: PageLayout,
     pub page_mode: PageMode,
     pub outlines: Option<IndirectReference>,
     pub threads: Option<IndirectReference>,
-    pub _open_action: Option<Object>,
+    pub open_action: Option<Object>,
     pub _aa: Option<Object>,
     pub _uri: Option<Object>,
-    pub _acro_form: Option<Object>,
+    pub acro_form: Option<Object>,
     pub metadata: Option<IndirectReference>,
-    pub _struct_tree_root: Option<Object>,
-    pub _mark_info: Option<Object>,
-    pub _lang: Option<Object>,
+    pub struct_tree_root: Option<Object>,
+    pub mark_info: Option<Object>,
+    pub lang: Option<Object>,
     pub _spider_info: Option<Object>,
     pub _output_intents: Option<Object>,
     pub _piece_info: Option<Object>,
-    pub _oc_properities: Option<Object>,
-    pub _perms: Option<Object>,
+    pub oc_properties: Option<Object>,
+    pub perms: Option<Object>,
     pub _legal: Option<Object>,
     pub _requirements: Option<Object>,
     pub _collection: Option<Object>,
@@ -87,6 +89,7 @@ impl Root {
                 .as_dictionary()
                 .context(error::InvalidType)?,
             None,
+            objects.options().strictness,
         )
         .context(error::InvalidPages)?;
 
@@ -117,6 +120,16 @@ impl Root {
             .transpose()
             .context(error::InvalidType)?;
 
+        let oc_properties = dictionary.get("OCProperties").cloned();
+        let acro_form = dictionary.get("AcroForm").cloned();
+        let perms = dictionary.get("Perms").cloned();
+        let viewer_preferences = dictionary.get("ViewerPreferences").cloned();
+        let struct_tree_root = dictionary.get("StructTreeRoot").cloned();
+        let mark_info = dictionary.get("MarkInfo").cloned();
+        let open_action = dictionary.get("OpenAction").cloned();
+        let dests = dictionary.get("Dests").cloned();
+        let lang = dictionary.get("Lang").cloned();
+
         let needs_rendering = dictionary
             .get("NeedsRendering")
             .map(|object| object.as_bool())
@@ -171,26 +184,26 @@ impl Root {
             outlines,
             threads,
             metadata,
+            oc_properties,
+            acro_form,
+            perms,
+            viewer_preferences,
+            struct_tree_root,
+            mark_info,
+            open_action,
+            dests,
+            lang,
             needs_rendering,
             names,
             page_layout,
             page_mode,
             _extensions: None,
             _page_labels: None,
-            _dests: None,
-            _viewer_preferences: None,
-            _open_action: None,
             _aa: None,
             _uri: None,
-            _acro_form: None,
-            _struct_tree_root: None,
-            _mark_info: None,
-            _lang: None,
             _spider_info: None,
             _output_intents: None,
             _piece_info: None,
-            _oc_properities: None,
-            _perms: None,
             _legal: None,
             _requirements: None,
             _collection: None,
@@ -22,6 +22,16 @@ pub struct Names {
 }
 
 impl Names {
+    /// Root of the `/Dests` name tree of named destinations.
+    pub fn dests(&self) -> Option<&Object> {
+        self.dests.as_ref()
+    }
+
+    /// Root of the `/EmbeddedFiles` name tree.
+    pub fn embedded_files(&self) -> Option<&Object> {
+        self.embedded_files.as_ref()
+    }
+
     pub fn from_dictionary(dictionary: &Dictionary) -> Result<Self> {
         let dests = dictionary.get("Dests").cloned();
         let ap = dictionary.get("AP").cloned();
@@ -0,0 +1,145 @@
+use snafu::{OptionExt, ResultExt, Snafu};
+
+use crate::{
+    objects::Objects,
+    types::{Dictionary, IndirectReference, Object},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Optional content group (layer) listed in the catalog's `/OCProperties`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    /// Name shown in the viewer's layer list (`/Name`)
+    pub name: String,
+    /// Visibility of the layer in the default configuration (`/D`)
+    pub visible_default: bool,
+    /// Reference to the optional content group dictionary
+    pub id: IndirectReference,
+}
+
+impl Layer {
+    /// Reads every layer of `/OCGs` together with its default visibility.
+    ///
+    /// Visibility starts from `/D /BaseState` (`ON` if missing) and is then
+    /// overridden by the `/ON` and `/OFF` arrays of the default configuration.
+    ///
+    /// # Arguments
+    /// * `properties` - Value of the catalog's `/OCProperties` entry
+    /// * `objects` - Object store used to resolve indirect references
+    pub fn from_properties(properties: &Object, objects: &mut Objects) -> Result<Vec<Self>> {
+        let properties = properties.direct(objects).into_owned();
+        let properties = properties.as_dictionary().context(error::InvalidField {
+            field: "OCProperties",
+        })?;
+
+        let groups = properties
+            .get("OCGs")
+            .context(error::FieldNotFound { field: "OCGs" })?
+            .direct(objects)
+            .as_array()
+            .of(|object| object.as_indirect_ref().cloned())
+            .context(error::InvalidArray { field: "OCGs" })?;
+
+        let config = properties
+            .get("D")
+            .map(|object| object.direct(objects).into_owned())
+            .unwrap_or_else(|| Object::Dictionary(Dictionary::default()));
+        let config = config
+            .as_dictionary()
+            .context(error::InvalidField { field: "D" })?;
+
+        let base_state = config
+            .get("BaseState")
+            .map(|object| object.as_name())
+            .transpose()
+            .context(error::InvalidField { field: "BaseState" })?
+            .is_none_or(|state| state.as_str() != "OFF");
+        let on = reference_list(config, "ON", objects)?;
+        let off = reference_list(config, "OFF", objects)?;
+
+        let mut layers = Vec::with_capacity(groups.len());
+        for id in groups {
+            let group = objects
+                .get_object(&id)
+                .context(error::Object { object: id })?;
+            let group = group
+                .as_dictionary()
+                .context(error::InvalidField { field: "OCGs" })?;
+
+            let name = group
+                .get("Name")
+                .context(error::FieldNotFound { field: "Name" })?
+                .direct(objects)
+                .as_string()
+                .context(error::InvalidField { field: "Name" })?
+                .to_text_string();
+
+            let visible_default = if off.contains(&id) {
+                false
+            } else {
+                on.contains(&id) || base_state
+            };
+
+            layers.push(Layer {
+                name,
+                visible_default,
+                id,
+            });
+        }
+
+        Ok(layers)
+    }
+}
+
+/// Reads an optional array of group references from the configuration dictionary.
+fn reference_list(
+    config: &Dictionary,
+    field: &'static str,
+    objects: &mut Objects,
+) -> Result<Vec<IndirectReference>> {
+    let Some(object) = config.get(field) else {
+        return Ok(Vec::new());
+    };
+
+    let references = object
+        .direct(objects)
+        .as_array()
+        .of(|object| object.as_indirect_ref().cloned())
+        .context(error::InvalidArray { field })?;
+
+    Ok(references)
+}
+
+mod error {
+    use snafu::Snafu;
+
+    use crate::types::IndirectReference;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Required field `{field}` not found"))]
+        FieldNotFound { field: &'static str },
+
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Invalid array in field `{field}`"))]
+        InvalidArray {
+            field: &'static str,
+            source: crate::types::array::Error,
+        },
+
+        #[snafu(display("Failed to get optional content group {object}"))]
+        Object {
+            object: IndirectReference,
+            source: crate::objects::Error,
+        },
+    }
+}
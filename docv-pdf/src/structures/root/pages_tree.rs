@@ -1,6 +1,9 @@
 use snafu::{OptionExt, ResultExt, Snafu};
 
-use crate::types::{Dictionary, IndirectReference, Object, Rectangle};
+use crate::{
+    objects::Strictness,
+    types::{Dictionary, IndirectReference, Object, Rectangle},
+};
 
 #[derive(Debug, Snafu)]
 pub struct Error(error::Error);
@@ -9,7 +12,9 @@ type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug, Clone)]
 pub struct PagesTreeNode {
     pub leaf_count: usize,
-    pub kids: Vec<IndirectReference>,
+    pub kids: Vec<PageTreeKid>,
+    /// Node that lists this one in its `/Kids` (`/Parent`), `None` for the root
+    pub parent: Option<IndirectReference>,
     pub inheritable_attributes: InheritableAttributes,
 }
 
@@ -17,13 +22,14 @@ impl PagesTreeNode {
     pub fn from_dictionary(
         dictionary: &Dictionary,
         inheritable_attributes: Option<InheritableAttributes>,
+        strictness: Strictness,
     ) -> Result<Self> {
-        let kids = dictionary
-            .get("Kids")
-            .context(error::FieldNotFound { field: "Kids" })?
-            .as_array()
-            .of(|obj| obj.as_indirect_ref().cloned())
-            .context(error::InvalidArray { field: "Kids" })?;
+        let kids = read_kids(
+            dictionary
+                .get("Kids")
+                .context(error::FieldNotFound { field: "Kids" })?,
+            strictness,
+        )?;
 
         let count = dictionary
             .get("Count")
@@ -31,20 +37,69 @@ impl PagesTreeNode {
             .as_integer()
             .context(error::InvalidType { field: "Count" })?;
 
+        let parent = dictionary
+            .get("Parent")
+            .map(|object| object.as_indirect_ref().copied())
+            .transpose()
+            .context(error::InvalidType { field: "Parent" })?;
+
         let mut inheritable_attributes = inheritable_attributes.unwrap_or_default();
         inheritable_attributes.read(dictionary)?;
 
         Ok(Self {
             leaf_count: count,
             kids,
+            parent,
             inheritable_attributes,
         })
     }
 }
 
+/// Entry of `/Kids`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageTreeKid {
+    Reference(IndirectReference),
+    /// Node written directly into `/Kids`, which the spec doesn't allow
+    Inline(Box<Dictionary>),
+}
+
+/// Reads a `/Kids` array.
+///
+/// Kids must be indirect references, but some writers inline page
+/// dictionaries. Under [`Strictness::Lenient`] they are accepted with a
+/// warning, as they can still be traversed, they just can't be referred to.
+pub(crate) fn read_kids(object: &Object, strictness: Strictness) -> Result<Vec<PageTreeKid>> {
+    let Object::Array(array) = object else {
+        return Err(error::Error::InvalidKids {
+            object: object.clone(),
+        }
+        .into());
+    };
+
+    array
+        .iter()
+        .map(|kid| match kid {
+            Object::IndirectReference(reference) => Ok(PageTreeKid::Reference(*reference)),
+            Object::Dictionary(_) if strictness == Strictness::Strict => {
+                Err(error::Error::DirectKid.into())
+            }
+            Object::Dictionary(dictionary) => {
+                tracing::warn!("Page tree node has a direct dictionary in `Kids`");
+                Ok(PageTreeKid::Inline(Box::new(dictionary.clone())))
+            }
+            kid => Err(error::Error::InvalidKids {
+                object: kid.clone(),
+            }
+            .into()),
+        })
+        .collect()
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct InheritableAttributes {
     pub resources: Option<Object>,
+    /// `/Resources` of every ancestor that has one, nearest first
+    pub resource_chain: Vec<Object>,
     pub media_box: Option<Rectangle>,
     pub crop_box: Option<Rectangle>,
     pub rotate: Option<u16>,
@@ -70,10 +125,13 @@ impl InheritableAttributes {
             .get("Rotate")
             .map(|object| object.as_integer())
             .transpose()
-            .context(error::InvalidType { field: "Rotate" })?;
+            .context(error::InvalidType { field: "Rotate" })?
+            .map(|value| normalize_rotation(value).context(error::InvalidRotate { value }))
+            .transpose()?;
 
-        if resources.is_some() {
-            self.resources = resources;
+        if let Some(resources) = resources {
+            self.resource_chain.insert(0, resources.clone());
+            self.resources = Some(resources);
         }
 
         if media_box.is_some() {
@@ -92,6 +150,39 @@ impl InheritableAttributes {
     }
 }
 
+/// Shape of the page tree, see [`crate::Document::page_tree_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PageTreeStats {
+    /// Number of `/Pages` levels above the deepest page, 1 for a flat tree
+    pub max_depth: usize,
+    /// Number of intermediate `/Pages` nodes, the root included
+    pub node_count: usize,
+    /// Number of pages
+    pub leaf_count: usize,
+    /// Largest number of direct kids of a single node
+    pub max_kids: usize,
+}
+
+impl PageTreeStats {
+    /// Kids a node may have before random page access gets noticeably slow.
+    pub const MAX_BALANCED_KIDS: usize = 128;
+
+    /// Whether some node has so many direct kids that the tree should be rebalanced.
+    pub fn is_degenerate(&self) -> bool {
+        self.max_kids > Self::MAX_BALANCED_KIDS
+    }
+}
+
+/// Maps a `/Rotate` value to 0, 90, 180 or 270 degrees.
+///
+/// Any multiple of 90 is allowed, negative ones included.
+///
+/// # Returns
+/// `None` if `value` is not a multiple of 90
+pub(crate) fn normalize_rotation(value: i64) -> Option<u16> {
+    (value % 90 == 0).then(|| value.rem_euclid(360) as u16)
+}
+
 mod error {
     use snafu::Snafu;
 
@@ -101,6 +192,12 @@ mod error {
         #[snafu(display("Required field `{field}` not found"))]
         FieldNotFound { field: &'static str },
 
+        #[snafu(display("`Kids` must hold references or dictionaries. Got = {object:?}"))]
+        InvalidKids { object: crate::types::Object },
+
+        #[snafu(display("`Kids` must hold references, not direct dictionaries"))]
+        DirectKid,
+
         #[snafu(display("Invalid object type for field `{field}`"))]
         InvalidType {
             field: &'static str,
@@ -115,5 +212,8 @@ mod error {
 
         #[snafu(display("Unexpected node type. Got = `{got}`. Expected `Page` or `Pages`]"))]
         UnexpectedNodeType { got: String },
+
+        #[snafu(display("`Rotate` must be a multiple of 90. Got = {value}"))]
+        InvalidRotate { value: i64 },
     }
 }
@@ -15,7 +15,7 @@ type Result<T> = std::result::Result<T, Error>;
 ///
 /// The version is typically found in the PDF header and determines
 /// which features are available in the document.
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 pub enum Version {
     /// PDF Version 1.0 (1993)
     Pdf1_0,
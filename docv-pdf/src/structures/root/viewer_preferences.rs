@@ -0,0 +1,143 @@
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    objects::Objects,
+    types::{Dictionary, Object},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// How the viewer should present the document when it's opened (`/ViewerPreferences`).
+///
+/// Missing entries take the defaults from ISO 32000-2, 12.2.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ViewerPreferences {
+    /// Hide the viewer's tool bars (`/HideToolbar`)
+    pub hide_toolbar: bool,
+    /// Hide the viewer's menu bar (`/HideMenubar`)
+    pub hide_menubar: bool,
+    /// Hide scroll bars and navigation controls, leaving only the page (`/HideWindowUI`)
+    pub hide_window_ui: bool,
+    /// Resize the window to fit the first page (`/FitWindow`)
+    pub fit_window: bool,
+    /// Center the window on the screen (`/CenterWindow`)
+    pub center_window: bool,
+    /// Show the document title instead of the file name (`/DisplayDocTitle`)
+    pub display_doc_title: bool,
+    /// Page mode used when leaving full-screen mode (`/NonFullScreenPageMode`)
+    pub non_full_screen_page_mode: NonFullScreenPageMode,
+    /// Predominant reading order of text (`/Direction`)
+    pub direction: Direction,
+}
+
+/// Panel shown next to the pages after leaving full-screen mode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NonFullScreenPageMode {
+    #[default]
+    UseNone,
+    UseOutlines,
+    UseThumbs,
+    UseOC,
+}
+
+/// Reading order, also used to lay out pages side by side.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Left to right
+    #[default]
+    L2R,
+    /// Right to left, including vertical writing systems
+    R2L,
+}
+
+impl ViewerPreferences {
+    /// Reads the viewer preferences dictionary.
+    ///
+    /// # Arguments
+    /// * `object` - Value of the catalog's `/ViewerPreferences` entry
+    /// * `objects` - Object store used to resolve indirect references
+    pub fn from_object(object: &Object, objects: &mut Objects) -> Result<Self> {
+        let object = object.direct(objects).into_owned();
+        let dictionary = object.as_dictionary().context(error::InvalidField {
+            field: "ViewerPreferences",
+        })?;
+
+        let non_full_screen_page_mode = match name_field(dictionary, "NonFullScreenPageMode")? {
+            None | Some("UseNone") => NonFullScreenPageMode::UseNone,
+            Some("UseOutlines") => NonFullScreenPageMode::UseOutlines,
+            Some("UseThumbs") => NonFullScreenPageMode::UseThumbs,
+            Some("UseOC") => NonFullScreenPageMode::UseOC,
+            Some(value) => {
+                return Err(error::Error::UnexpectedValue {
+                    field: "NonFullScreenPageMode",
+                    value: value.to_string(),
+                }
+                .into());
+            }
+        };
+
+        let direction = match name_field(dictionary, "Direction")? {
+            None | Some("L2R") => Direction::L2R,
+            Some("R2L") => Direction::R2L,
+            Some(value) => {
+                return Err(error::Error::UnexpectedValue {
+                    field: "Direction",
+                    value: value.to_string(),
+                }
+                .into());
+            }
+        };
+
+        Ok(Self {
+            hide_toolbar: bool_field(dictionary, "HideToolbar")?,
+            hide_menubar: bool_field(dictionary, "HideMenubar")?,
+            hide_window_ui: bool_field(dictionary, "HideWindowUI")?,
+            fit_window: bool_field(dictionary, "FitWindow")?,
+            center_window: bool_field(dictionary, "CenterWindow")?,
+            display_doc_title: bool_field(dictionary, "DisplayDocTitle")?,
+            non_full_screen_page_mode,
+            direction,
+        })
+    }
+}
+
+/// Reads an optional boolean entry, `false` when it's missing.
+fn bool_field(dictionary: &Dictionary, field: &'static str) -> Result<bool> {
+    let value = dictionary
+        .get(field)
+        .map(|object| object.as_bool())
+        .transpose()
+        .context(error::InvalidField { field })?
+        .unwrap_or(false);
+
+    Ok(value)
+}
+
+fn name_field<'a>(dictionary: &'a Dictionary, field: &'static str) -> Result<Option<&'a str>> {
+    let value = dictionary
+        .get(field)
+        .map(|object| object.as_name().map(|name| name.as_str()))
+        .transpose()
+        .context(error::InvalidField { field })?;
+
+    Ok(value)
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Unexpected value for `{field}`. Got = `{value}`"))]
+        UnexpectedValue { field: &'static str, value: String },
+    }
+}
@@ -0,0 +1,237 @@
+use std::{collections::BTreeSet, ops::Range};
+
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
+
+use crate::{
+    objects::Objects,
+    types::{Dictionary, IndirectReference, Object},
+};
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Maximum depth of nested form fields, deeper hierarchies are treated as malformed.
+const MAX_DEPTH: usize = 64;
+
+/// Signature form field (`/FT /Sig`) of the interactive form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureField {
+    /// Fully qualified field name, partial names (`/T`) joined with `.`
+    pub name: String,
+    /// Signature dictionary (`/V`), `None` for fields that aren't signed yet
+    pub signature: Option<Signature>,
+}
+
+/// Values of a signature dictionary. Nothing is verified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    /// Signed parts of the file, built from the `/ByteRange` offset and length pairs
+    pub byte_range: Vec<Range<usize>>,
+    /// Raw signature value (`/Contents`), usually a DER encoded PKCS#7 blob
+    pub contents: Vec<u8>,
+    /// Encoding of the signature value (`/SubFilter`), e.g. `adbe.pkcs7.detached`
+    pub sub_filter: Option<String>,
+    /// Name of the signer (`/Name`)
+    pub signer_name: Option<String>,
+}
+
+/// Collects every signature field reachable from the `/Fields` of an interactive form.
+///
+/// # Arguments
+/// * `acro_form` - Value of the catalog's `/AcroForm` entry
+/// * `objects` - Object store used to resolve indirect references
+pub fn read_signature_fields(
+    acro_form: &Object,
+    objects: &mut Objects,
+) -> Result<Vec<SignatureField>> {
+    let acro_form = acro_form.direct(objects).into_owned();
+    let acro_form = acro_form
+        .as_dictionary()
+        .context(error::InvalidField { field: "AcroForm" })?;
+
+    let Some(fields) = acro_form.get("Fields") else {
+        return Ok(Vec::new());
+    };
+    let fields = fields
+        .direct(objects)
+        .as_array()
+        .generic()
+        .context(error::InvalidArray { field: "Fields" })?;
+
+    let mut walker = FieldWalker {
+        objects,
+        visited: BTreeSet::new(),
+        signatures: Vec::new(),
+    };
+    for field in fields.iter() {
+        walker.walk(field, None, None, 0)?;
+    }
+
+    Ok(walker.signatures)
+}
+
+struct FieldWalker<'a> {
+    objects: &'a mut Objects,
+    visited: BTreeSet<IndirectReference>,
+    signatures: Vec<SignatureField>,
+}
+
+impl FieldWalker<'_> {
+    /// Visits a field node and returns whether it is a field rather than a widget.
+    ///
+    /// A node without `/T` is a widget annotation of its parent. A field whose
+    /// kids are all widgets is a terminal field.
+    fn walk(
+        &mut self,
+        node: &Object,
+        parent_name: Option<&str>,
+        parent_type: Option<&str>,
+        depth: usize,
+    ) -> Result<bool> {
+        ensure!(depth < MAX_DEPTH, error::TooDeep);
+
+        if let Object::IndirectReference(reference) = node
+            && !self.visited.insert(*reference)
+        {
+            return Ok(false);
+        }
+
+        let node = node.direct(self.objects).into_owned();
+        let node = node
+            .as_dictionary()
+            .context(error::InvalidField { field: "Fields" })?;
+
+        let Some(partial_name) = node.get("T") else {
+            return Ok(false);
+        };
+        let partial_name = partial_name
+            .direct(self.objects)
+            .as_string()
+            .context(error::InvalidField { field: "T" })?
+            .to_text_string();
+        let name = match parent_name {
+            Some(parent_name) => format!("{parent_name}.{partial_name}"),
+            None => partial_name,
+        };
+
+        let field_type = node
+            .get("FT")
+            .map(|object| object.as_name().map(|name| name.to_string()))
+            .transpose()
+            .context(error::InvalidField { field: "FT" })?
+            .or_else(|| parent_type.map(str::to_string));
+
+        let mut has_field_kids = false;
+        if let Some(kids) = node.get("Kids") {
+            let kids = kids
+                .direct(self.objects)
+                .as_array()
+                .generic()
+                .context(error::InvalidArray { field: "Kids" })?;
+
+            for kid in kids.iter() {
+                has_field_kids |= self.walk(kid, Some(&name), field_type.as_deref(), depth + 1)?;
+            }
+        }
+
+        if !has_field_kids && field_type.as_deref() == Some("Sig") {
+            let signature = node
+                .get("V")
+                .map(|value| value.direct(self.objects).into_owned())
+                .filter(|value| !value.is_null())
+                .map(|value| -> Result<Signature> {
+                    let value = value
+                        .as_dictionary()
+                        .context(error::InvalidField { field: "V" })?;
+
+                    read_signature(value, self.objects)
+                })
+                .transpose()?;
+
+            self.signatures.push(SignatureField { name, signature });
+        }
+
+        Ok(true)
+    }
+}
+
+fn read_signature(dictionary: &Dictionary, objects: &mut Objects) -> Result<Signature> {
+    let byte_range = dictionary
+        .get("ByteRange")
+        .map(|object| -> Result<Vec<Range<usize>>> {
+            let values = object
+                .direct(objects)
+                .as_array()
+                .of(|object| object.as_integer::<usize>())
+                .context(error::InvalidArray { field: "ByteRange" })?;
+
+            Ok(values
+                .chunks_exact(2)
+                .map(|pair| pair[0]..pair[0] + pair[1])
+                .collect())
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let contents = dictionary
+        .get("Contents")
+        .context(error::FieldNotFound { field: "Contents" })?
+        .direct(objects)
+        .as_string()
+        .context(error::InvalidField { field: "Contents" })?
+        .as_bytes()
+        .to_vec();
+
+    let sub_filter = dictionary
+        .get("SubFilter")
+        .map(|object| object.as_name().map(|name| name.to_string()))
+        .transpose()
+        .context(error::InvalidField { field: "SubFilter" })?;
+
+    let signer_name = dictionary
+        .get("Name")
+        .map(|object| -> Result<String> {
+            let text = object
+                .direct(objects)
+                .as_string()
+                .context(error::InvalidField { field: "Name" })?
+                .to_text_string();
+
+            Ok(text)
+        })
+        .transpose()?;
+
+    Ok(Signature {
+        byte_range,
+        contents,
+        sub_filter,
+        signer_name,
+    })
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("Required field `{field}` not found"))]
+        FieldNotFound { field: &'static str },
+
+        #[snafu(display("Invalid object type for field `{field}`"))]
+        InvalidField {
+            field: &'static str,
+            source: crate::types::object::Error,
+        },
+
+        #[snafu(display("Invalid array in field `{field}`"))]
+        InvalidArray {
+            field: &'static str,
+            source: crate::types::array::Error,
+        },
+
+        #[snafu(display("Form fields are nested too deeply"))]
+        TooDeep,
+    }
+}
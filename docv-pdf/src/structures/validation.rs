@@ -0,0 +1,115 @@
+use crate::types::IndirectReference;
+
+/// Problem found by [`Document::validate`](crate::Document::validate).
+///
+/// Issues describe inconsistencies a lenient reader can live with, but
+/// which usually mean the file is truncated, hand-edited or corrupt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// Largest trailer `/Size` is not one greater than the highest object number in the xref.
+    SizeMismatch { size: usize, highest_object: usize },
+    /// Cross-reference sections of different revisions declare different `/Size` values.
+    SectionSizesDiffer { sizes: Vec<usize> },
+    /// Object referenced by `/Root` is not a `/Catalog` dictionary.
+    RootNotCatalog { found: Option<String> },
+    /// Page tree `/Count` differs from the number of leaves actually reachable.
+    PageCountMismatch { declared: usize, actual: usize },
+    /// `/Parent` of a page tree node doesn't point to the node listing it in `/Kids`.
+    ParentMismatch {
+        node: IndirectReference,
+        expected: IndirectReference,
+        found: Option<IndirectReference>,
+    },
+    /// Page tree node is listed in `/Kids` by itself or one of its descendants.
+    PageTreeCycle { node: IndirectReference },
+}
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueSeverity {
+    /// The file reads correctly, but other readers may disagree on it
+    Warning,
+    /// Part of the document, like some pages, can't be read as intended
+    Error,
+}
+
+impl ValidationIssue {
+    /// Severity of the issue.
+    ///
+    /// Inconsistent bookkeeping that readers routinely repair, like a wrong
+    /// `/Size` or `/Parent`, is a warning. A catalog of the wrong type or
+    /// pages missing from the tree is an error.
+    pub fn severity(&self) -> IssueSeverity {
+        match self {
+            ValidationIssue::SizeMismatch { .. }
+            | ValidationIssue::SectionSizesDiffer { .. }
+            | ValidationIssue::ParentMismatch { .. } => IssueSeverity::Warning,
+            ValidationIssue::RootNotCatalog { .. }
+            | ValidationIssue::PageCountMismatch { .. }
+            | ValidationIssue::PageTreeCycle { .. } => IssueSeverity::Error,
+        }
+    }
+}
+
+impl std::fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueSeverity::Warning => write!(f, "warning"),
+            IssueSeverity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::SizeMismatch {
+                size,
+                highest_object,
+            } => write!(
+                f,
+                "Trailer /Size is {size}, but the highest object number is {highest_object}"
+            ),
+            ValidationIssue::SectionSizesDiffer { sizes } => {
+                let sizes = sizes
+                    .iter()
+                    .map(|size| size.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "Cross-reference sections disagree on /Size: {sizes}")
+            }
+            ValidationIssue::RootNotCatalog { found: Some(found) } => {
+                write!(f, "/Root has type `{found}` instead of `Catalog`")
+            }
+            ValidationIssue::RootNotCatalog { found: None } => {
+                write!(f, "/Root has no `Catalog` type")
+            }
+            ValidationIssue::PageCountMismatch { declared, actual } => write!(
+                f,
+                "Page tree declares {declared} pages, but {actual} were found"
+            ),
+            ValidationIssue::ParentMismatch {
+                node,
+                expected,
+                found: Some(found),
+            } => write!(
+                f,
+                "Page tree node {node} has /Parent {found}, but is a kid of {expected}"
+            ),
+            ValidationIssue::ParentMismatch {
+                node,
+                expected,
+                found: None,
+            } => write!(
+                f,
+                "Page tree node {node} has no /Parent, but is a kid of {expected}"
+            ),
+            ValidationIssue::PageTreeCycle { node } => {
+                write!(
+                    f,
+                    "Page tree node {node} is listed in its own /Kids subtree"
+                )
+            }
+        }
+    }
+}
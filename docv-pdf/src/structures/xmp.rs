@@ -0,0 +1,330 @@
+//! Lightweight lookups in XMP metadata packets.
+//!
+//! Nothing is validated, properties are found by scanning the text for the
+//! prefixed property name. Both the attribute form
+//! (`pdfaid:part="2"`) and the element form (`<pdfaid:part>2</pdfaid:part>`)
+//! of RDF are recognized.
+
+/// Value of the simple property `name`, e.g. `pdfaid:part`.
+///
+/// # Returns
+/// The trimmed value of the first occurrence, or `None` if the property isn't set
+pub fn property<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(name) {
+        let preceding = rest[..start].chars().next_back();
+        let after = &rest[start + name.len()..];
+        rest = after;
+
+        match preceding {
+            // Element form, the value is the text up to the closing tag.
+            Some('<') => {
+                let Some(open_end) = after.find('>') else {
+                    continue;
+                };
+                if after[..open_end].ends_with('/') {
+                    continue;
+                }
+                let value = &after[open_end + 1..];
+                let Some(end) = value.find('<') else {
+                    continue;
+                };
+
+                return Some(value[..end].trim());
+            }
+            // Attribute form, the value is quoted after `=`.
+            Some(c) if c.is_whitespace() => {
+                let Some(value) = after.trim_start().strip_prefix('=') else {
+                    continue;
+                };
+                let value = value.trim_start();
+                let Some(quote) = value.chars().next().filter(|c| matches!(c, '"' | '\'')) else {
+                    continue;
+                };
+                let value = &value[1..];
+                let Some(end) = value.find(quote) else {
+                    continue;
+                };
+
+                return Some(value[..end].trim());
+            }
+            _ => continue,
+        }
+    }
+
+    None
+}
+
+/// First item of the array property `name`, e.g. the default title of `dc:title`.
+///
+/// `dc:title` and `dc:creator` hold an `rdf:Alt` or `rdf:Seq` of `rdf:li`
+/// items. The item with `xml:lang="x-default"` is preferred, otherwise the
+/// first one is used. A property without items is read as a simple one, see
+/// [`property`]. XML entities and character references are decoded.
+///
+/// # Returns
+/// The trimmed item, or `None` if the property isn't set or is empty
+pub fn first_item(xml: &str, name: &str) -> Option<String> {
+    let item = element_content(xml, name).and_then(|element| {
+        let mut first = None;
+        for (attributes, value) in items(element) {
+            if property(attributes, "xml:lang") == Some("x-default") {
+                return Some(value);
+            }
+            first.get_or_insert(value);
+        }
+
+        first
+    });
+
+    item.or_else(|| property(xml, name))
+        .map(|value| unescape(value.trim()))
+        .filter(|value| !value.is_empty())
+}
+
+/// Content of the first element `name`, between its start and end tags.
+fn element_content<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{name}");
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let tag = &rest[start + open.len()..];
+        rest = tag;
+
+        // Longer names sharing the prefix, like `dc:titles`, are other elements.
+        if !tag.starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace()) {
+            continue;
+        }
+        let end = tag.find('>')?;
+        if tag[..end].ends_with('/') {
+            continue;
+        }
+        let content = &tag[end + 1..];
+
+        return Some(&content[..content.find(&format!("</{name}>"))?]);
+    }
+
+    None
+}
+
+/// Attributes and text of the `rdf:li` items in `element`, in document order.
+fn items(element: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = element;
+
+    std::iter::from_fn(move || {
+        loop {
+            let start = rest.find("<rdf:li")?;
+            let tag = &rest[start + "<rdf:li".len()..];
+            let end = tag.find('>')?;
+            let attributes = &tag[..end];
+            let content = &tag[end + 1..];
+            rest = content;
+
+            if !attributes.is_empty() && !attributes.starts_with(char::is_whitespace) {
+                continue;
+            }
+            if attributes.ends_with('/') {
+                return Some((attributes, ""));
+            }
+
+            return Some((attributes, &content[..content.find('<')?]));
+        }
+    })
+}
+
+/// Replaces the predefined XML entities and character references in `text`.
+///
+/// Anything else starting with `&` is kept as is.
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let character = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                reference => {
+                    let reference = reference.strip_prefix('#')?;
+                    let code = match reference.strip_prefix('x') {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => reference.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+
+            Some((character, end))
+        });
+
+        match decoded {
+            Some((character, end)) => {
+                result.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// PDF/A part and conformance level, e.g. `2B` for PDF/A-2b.
+///
+/// Parts from PDF/A-4 on have no conformance level, so only the part is returned.
+pub fn pdfa_conformance(xml: &str) -> Option<String> {
+    let part = property(xml, "pdfaid:part")?;
+    let conformance = property(xml, "pdfaid:conformance").unwrap_or_default();
+
+    Some(format!("{part}{}", conformance.to_uppercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdfa_conformance() {
+        let test_cases = [
+            (
+                "attributes",
+                r#"<rdf:Description rdf:about="" xmlns:pdfaid="http://www.aiim.org/pdfa/ns/id/"
+                    pdfaid:part="2" pdfaid:conformance="B"/>"#,
+                Some("2B"),
+            ),
+            (
+                "elements",
+                "<rdf:Description rdf:about=''>\n\
+                 <pdfaid:part>1</pdfaid:part>\n\
+                 <pdfaid:conformance>a</pdfaid:conformance>\n\
+                 </rdf:Description>",
+                Some("1A"),
+            ),
+            (
+                "single quotes and spaces",
+                "<rdf:Description pdfaid:part = '3' pdfaid:conformance = 'U'/>",
+                Some("3U"),
+            ),
+            (
+                "part without conformance",
+                "<rdf:Description pdfaid:part=\"4\" pdfaid:rev=\"2020\"/>",
+                Some("4"),
+            ),
+            (
+                "namespace declaration only",
+                "<rdf:Description xmlns:pdfaid=\"http://www.aiim.org/pdfa/ns/id/\"/>",
+                None,
+            ),
+            (
+                "no identification",
+                "<rdf:Description dc:format=\"application/pdf\"/>",
+                None,
+            ),
+        ];
+
+        for (name, xml, expected) in test_cases {
+            assert_eq!(
+                pdfa_conformance(xml).as_deref(),
+                expected,
+                "Test '{name}' failed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_first_item() {
+        let test_cases = [
+            (
+                "language alternative",
+                "dc:title",
+                "<dc:title><rdf:Alt>\n<rdf:li xml:lang=\"x-default\"> Annual report </rdf:li>\n\
+                 <rdf:li xml:lang=\"de\">Jahresbericht</rdf:li></rdf:Alt></dc:title>",
+                Some("Annual report"),
+            ),
+            (
+                "ordered array",
+                "dc:creator",
+                "<dc:creator><rdf:Seq><rdf:li>Jane Doe</rdf:li><rdf:li>John Doe</rdf:li>\
+                 </rdf:Seq></dc:creator>",
+                Some("Jane Doe"),
+            ),
+            (
+                "simple element",
+                "dc:title",
+                "<rdf:Description><dc:title>Plain</dc:title></rdf:Description>",
+                Some("Plain"),
+            ),
+            (
+                "attribute",
+                "dc:title",
+                "<rdf:Description dc:title=\"Attribute\"/>",
+                Some("Attribute"),
+            ),
+            (
+                "empty item",
+                "dc:title",
+                "<dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\"></rdf:li></rdf:Alt></dc:title>",
+                None,
+            ),
+            (
+                "default language after another one",
+                "dc:title",
+                "<dc:title><rdf:Alt><rdf:li xml:lang=\"de\">Jahresbericht</rdf:li>\
+                 <rdf:li xml:lang='x-default'>Annual report</rdf:li></rdf:Alt></dc:title>",
+                Some("Annual report"),
+            ),
+            (
+                "start tag with attributes",
+                "dc:title",
+                "<dc:title xmlns:dc=\"http://purl.org/dc/elements/1.1/\" rdf:parseType=\"Resource\">\
+                 <rdf:Alt><rdf:li xml:lang=\"de\">Bericht</rdf:li></rdf:Alt></dc:title>",
+                Some("Bericht"),
+            ),
+            (
+                "entities in item",
+                "dc:creator",
+                "<dc:creator><rdf:Seq><rdf:li>R&amp;D &lt;Team&gt; &quot;A&quot; &apos;B&apos;\
+                 </rdf:li></rdf:Seq></dc:creator>",
+                Some("R&D <Team> \"A\" 'B'"),
+            ),
+            (
+                "character references and unknown entity",
+                "dc:title",
+                "<rdf:Description><dc:title>Caf&#233; &#x2014; &nbsp;</dc:title></rdf:Description>",
+                Some("Café — &nbsp;"),
+            ),
+            (
+                "longer name with the same prefix",
+                "dc:title",
+                "<dc:titles><rdf:Alt><rdf:li>Wrong</rdf:li></rdf:Alt></dc:titles>\
+                 <dc:title><rdf:Alt><rdf:li>Right</rdf:li></rdf:Alt></dc:title>",
+                Some("Right"),
+            ),
+            (
+                "other property",
+                "dc:title",
+                "<dc:creator><rdf:Seq><rdf:li>Jane Doe</rdf:li></rdf:Seq></dc:creator>",
+                None,
+            ),
+        ];
+
+        for (name, property, xml, expected) in test_cases {
+            assert_eq!(
+                first_item(xml, property).as_deref(),
+                expected,
+                "Test '{name}' failed"
+            );
+        }
+    }
+}
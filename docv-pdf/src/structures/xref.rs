@@ -1,9 +1,16 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
 
 use snafu::{OptionExt, ResultExt, Snafu, ensure};
 
 use crate::{
-    parser::{XrefObject, XrefTableSection, read_startxref, read_trailer, read_version, read_xref},
+    objects::{DecodeBudget, Strictness},
+    parser::{
+        XrefObject, XrefTableSection, is_delimiter, is_whitespace, read_object, read_startxref,
+        read_startxref_lenient, read_trailer, read_version, read_xref,
+    },
     structures::hash::Hash,
     structures::root::version::Version,
     types::{Dictionary, IndirectReference, Stream},
@@ -13,17 +20,51 @@ use crate::{
 pub struct Error(error::Error);
 type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+    /// Whether `startxref` or the section it points to couldn't be parsed,
+    /// so the file has to be scanned for its objects instead.
+    pub(crate) fn is_missing_section(&self) -> bool {
+        matches!(
+            self.0,
+            error::Error::ParseFile {
+                section: "startxref" | "xref",
+                ..
+            }
+        )
+    }
+}
+
+/// Number of cross-reference sections followed when no other limit is given.
+pub const DEFAULT_MAX_XREF_CHAIN: usize = 1024;
+
 #[derive(Debug, Default, Clone)]
 pub struct Xref {
     prev: Option<u64>,
     xref_stm: Option<u64>,
     first_byte: usize,
+    /// Largest `/Size` of all sections read so far.
     size: usize,
+    /// `/Size` of every section read so far, in reading order.
+    section_sizes: Vec<usize>,
     version: Version,
     entries: BTreeMap<IndirectReference, XrefEntry>,
+    /// Whether the newest entry seen for each object number is in use.
+    in_use: BTreeMap<usize, bool>,
+
+    sections_read: usize,
+    /// Budget the cross-reference streams are decoded from.
+    budget: DecodeBudget,
+    /// Whether `startxref` may have its offset on the same line.
+    strictness: Strictness,
+    /// Offsets of all sections read so far, to detect `/Prev` cycles.
+    visited: BTreeSet<u64>,
+    /// Offsets of the `startxref` section and of every `/Prev` section read, newest first.
+    revisions: Vec<u64>,
+    /// Maximum number of sections to read, `None` for [`DEFAULT_MAX_XREF_CHAIN`].
+    max_chain: Option<usize>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum XrefEntry {
     Free {
         #[allow(dead_code)]
@@ -38,16 +79,56 @@ pub enum XrefEntry {
     },
 }
 
+/// Summary of the newest cross-reference section and its trailer.
 #[derive(Debug, Clone)]
 pub struct XrefMetadata {
+    /// Format of the cross-reference section.
+    pub kind: XrefKind,
+    /// Trailer `/Size`: one greater than the highest object number.
+    pub size: usize,
+    /// Offset of the previous cross-reference section (`/Prev`).
+    pub prev: Option<u64>,
+
     pub root_id: IndirectReference,
     pub version: Version,
 
     pub hash: Option<Hash>,
     pub info_id: Option<IndirectReference>,
+    /// Reference to the encryption dictionary (`/Encrypt`), if it's indirect.
+    pub encrypt_id: Option<IndirectReference>,
+    /// Bytes of the trailer: from the `trailer` keyword to the end of its
+    /// dictionary, or the whole cross-reference stream object.
+    pub trailer_range: Range<usize>,
+}
+
+/// Format used to store a cross-reference section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XrefKind {
+    /// Classic `xref` table followed by a `trailer` dictionary.
+    Table,
+    /// Cross-reference stream (PDF 1.5+), the stream dictionary serves as trailer.
+    Stream,
+    /// No usable cross-reference section, entries were rebuilt by scanning
+    /// the file for object definitions, see [`Xref::read_by_scanning`].
+    Scanned,
 }
 
 impl Xref {
+    /// # Arguments
+    /// * `max_chain` - Maximum number of sections to read, `None` for [`DEFAULT_MAX_XREF_CHAIN`]
+    pub(crate) fn new(
+        max_chain: Option<usize>,
+        budget: DecodeBudget,
+        strictness: Strictness,
+    ) -> Self {
+        Self {
+            max_chain,
+            budget,
+            strictness,
+            ..Default::default()
+        }
+    }
+
     pub fn find_entry<'a>(&'a self, ref_id: &IndirectReference) -> Option<&'a XrefEntry> {
         self.entries.get(ref_id)
     }
@@ -56,44 +137,159 @@ impl Xref {
         self.xref_stm.is_some() || self.prev.is_some()
     }
 
+    /// Offsets of the sections of every revision read so far, newest first.
+    ///
+    /// The first one is the `startxref` offset. Sections referenced through
+    /// `/XRefStm` belong to the revision of their trailer and aren't listed.
+    pub fn revision_offsets(&self) -> &[u64] {
+        &self.revisions
+    }
+
+    /// Position of the `%PDF-` header in the file. Offsets in the file count
+    /// from it, bytes before it are ignored.
+    pub fn first_byte(&self) -> usize {
+        self.first_byte
+    }
+
+    /// Position in the file of the section `startxref` points to.
+    ///
+    /// Differs from the `startxref` offset when bytes precede the header.
+    /// `None` if the objects were found by scanning the file.
+    pub fn startxref_position(&self) -> Option<usize> {
+        self.revisions
+            .first()
+            .map(|offset| self.first_byte + *offset as usize)
+    }
+
+    /// Number of entries across all read sections.
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Number of object numbers whose newest entry is in use.
+    pub fn object_count(&self) -> usize {
+        self.in_use.values().filter(|in_use| **in_use).count()
+    }
+
+    /// Number of object numbers whose newest entry is free.
+    pub fn free_count(&self) -> usize {
+        self.in_use.values().filter(|in_use| !**in_use).count()
+    }
+
+    /// Largest `/Size` across the sections read so far.
+    ///
+    /// Sections of different revisions may disagree, the largest one is the
+    /// only value that covers every object number.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// `/Size` of every section read so far, in reading order.
+    pub fn section_sizes(&self) -> &[usize] {
+        &self.section_sizes
+    }
+
+    /// Highest object number with an entry, in use or free.
+    pub fn highest_object(&self) -> Option<usize> {
+        self.in_use.keys().next_back().copied()
+    }
+
+    /// Newest in-use reference of every object number.
+    ///
+    /// Generation numbers only grow, so the highest occupied generation
+    /// of an object number is the one in use.
+    pub fn in_use_references(&self) -> Vec<IndirectReference> {
+        self.in_use
+            .iter()
+            .filter(|(_, in_use)| **in_use)
+            .filter_map(|(id, _)| {
+                let first = IndirectReference { id: *id, gen_id: 0 };
+                let last = IndirectReference {
+                    id: *id,
+                    gen_id: usize::MAX,
+                };
+
+                self.entries
+                    .range(first..=last)
+                    .filter(|(_, entry)| !matches!(entry, XrefEntry::Free { .. }))
+                    .map(|(reference, _)| *reference)
+                    .next_back()
+            })
+            .collect()
+    }
+
+    /// Checks that every object number in `0..size` has an entry.
+    pub fn is_complete(&self) -> bool {
+        (0..self.size).all(|id| self.in_use.contains_key(&id))
+    }
+
     pub fn read_startxref(&mut self, input: &[u8], filesize: usize) -> Result<u64> {
-        let (_, (version, first_byte)) = read_version(input).ok().context(error::ParseFile {
-            section: "version",
-            offset: 0usize,
-        })?;
-        self.version = Version::from_str(version).context(error::InvalidVersion)?;
-        self.first_byte = first_byte;
+        self.read_version(input)?;
 
         let offset = ((filesize as f64).log10().floor() + 1.0) as usize + 23;
         let start = filesize - offset;
 
-        let (_, offset) = read_startxref(&input[start..])
-            .ok()
-            .context(error::ParseFile {
-                section: "startxref",
-                offset: start,
-            })?;
+        let input = &input[start..];
+        let (_, offset) = match read_startxref(input) {
+            Ok(result) => result,
+            strict if self.strictness == Strictness::Strict => {
+                strict.ok().context(error::ParseFile {
+                    section: "startxref",
+                    offset: start,
+                })?
+            }
+            strict => match read_startxref_lenient(input) {
+                Ok(result) => {
+                    tracing::warn!("Offset after `startxref` isn't on its own line");
+                    result
+                }
+                Err(_) => strict.ok().context(error::ParseFile {
+                    section: "startxref",
+                    offset: start,
+                })?,
+            },
+        };
+
+        self.revisions.push(offset);
 
         Ok(offset)
     }
 
     pub fn read_table(&mut self, input: &[u8], offset: u64) -> Result<XrefMetadata> {
+        ensure!(
+            self.visited.insert(offset),
+            error::CyclicXrefChain { offset }
+        );
+
+        let limit = self.max_chain.unwrap_or(DEFAULT_MAX_XREF_CHAIN);
+        ensure!(
+            self.sections_read < limit,
+            error::XrefChainTooLong { limit }
+        );
+
         let start = self.first_byte + offset as usize;
-        let (remained, data) = read_xref(&input[start..]).ok().context(error::ParseFile {
-            section: "xref",
-            offset: start,
-        })?;
+        let (remained, data) = input
+            .get(start..)
+            .and_then(|input| read_xref(input).ok())
+            .context(error::ParseFile {
+                section: "xref",
+                offset: start,
+            })?;
+        let end = input.len() - remained.len();
+        self.sections_read += 1;
 
         match data {
             XrefObject::Table(sections) => {
                 self.parse_xref_table(sections)?;
 
-                self.parse_trailer(remained)
+                self.parse_trailer(input, end)
             }
             XrefObject::Stream(mut stream) => {
-                stream.process_filters().context(error::StreamProcessing)?;
+                self.budget
+                    .process_filters(&mut stream)
+                    .context(error::StreamProcessing)?;
 
-                self.parse_xref_stream(stream)
+                self.parse_xref_stream(input, stream, start..end)
             }
             XrefObject::IndirectStream(indirect_object) => {
                 let mut stream = indirect_object
@@ -101,20 +297,133 @@ impl Xref {
                     .context(error::InvalidStream)?
                     .clone();
 
-                stream.process_filters().context(error::StreamProcessing)?;
+                self.budget
+                    .process_filters(&mut stream)
+                    .context(error::StreamProcessing)?;
+
+                self.parse_xref_stream(input, stream, start..end)
+            }
+        }
+    }
 
-                self.parse_xref_stream(stream)
+    /// Rebuilds the entries by scanning the whole file for `N G obj` markers.
+    ///
+    /// Meant for files without a usable cross-reference section. A later
+    /// definition of the same object number and generation replaces an
+    /// earlier one, as incremental updates append them. Objects stored in
+    /// object streams can't be found this way.
+    ///
+    /// The trailer is the last `trailer` dictionary of the file if it has a
+    /// `/Root`. Otherwise the last object with `/Type /Catalog` is the root
+    /// and there is no `/Info` or `/ID`.
+    pub fn read_by_scanning(&mut self, input: &[u8]) -> Result<XrefMetadata> {
+        self.read_version(input)?;
+
+        let mut definitions = BTreeMap::new();
+        let keywords = input
+            .windows(b"obj".len())
+            .enumerate()
+            .filter(|(_, window)| *window == b"obj");
+        for (keyword, _) in keywords {
+            if let Some((reference, offset)) = object_marker(input, keyword) {
+                definitions.insert(reference, offset);
             }
         }
+        for (reference, offset) in &definitions {
+            self.insert_entry(*reference, XrefEntry::Occupied { offset: *offset });
+        }
+
+        let size = self.highest_object().map_or(0, |id| id + 1);
+        self.size = size;
+
+        let mut trailers = input
+            .windows(b"trailer".len())
+            .enumerate()
+            .rev()
+            .filter(|(_, window)| *window == b"trailer");
+        let trailer = trailers.find_map(|(start, _)| {
+            let (remained, trailer) = read_trailer(&input[start..]).ok()?;
+            let root = trailer.get("Root")?.as_indirect_ref().ok().copied()?;
+
+            Some((root, trailer, start..input.len() - remained.len()))
+        });
+
+        let (root_id, trailer, trailer_range) = match trailer {
+            Some(trailer) => trailer,
+            None => {
+                let mut by_offset = definitions.into_iter().collect::<Vec<_>>();
+                by_offset.sort_by_key(|(_, offset)| *offset);
+
+                let root_id = by_offset
+                    .into_iter()
+                    .rev()
+                    .find(|(_, offset)| {
+                        read_object(&input[*offset..]).is_ok_and(|object| {
+                            object
+                                .as_dictionary()
+                                .ok()
+                                .and_then(|dictionary| dictionary.get_name("Type"))
+                                .is_some_and(|name| name.is_ok_and(|name| name == "Catalog"))
+                        })
+                    })
+                    .map(|(reference, _)| reference)
+                    .context(error::FieldNotFound { field: "Root" })?;
+
+                (root_id, Dictionary::default(), 0..0)
+            }
+        };
+
+        let hash = trailer
+            .get("ID")
+            .map(Hash::from_object)
+            .transpose()
+            .context(error::InvalidHash)?;
+        let info_id = trailer
+            .get("Info")
+            .and_then(|object| object.as_indirect_ref().ok())
+            .copied();
+        let encrypt_id = trailer
+            .get("Encrypt")
+            .and_then(|object| object.as_indirect_ref().ok())
+            .copied();
+
+        Ok(XrefMetadata {
+            kind: XrefKind::Scanned,
+            size,
+            prev: None,
+
+            root_id,
+            version: self.version.clone(),
+
+            hash,
+            info_id,
+            encrypt_id,
+            trailer_range,
+        })
     }
 
-    pub fn read_additional_table(&mut self, input: &[u8]) -> Result<()> {
-        let offset = self
-            .xref_stm
-            .or(self.prev)
-            .context(error::NoXRefAdditionalSources)?;
+    pub fn read_additional_table(&mut self, input: &[u8]) -> Result<XrefMetadata> {
+        let offset = match self.xref_stm.take() {
+            Some(offset) => offset,
+            None => {
+                let offset = self.prev.take().context(error::NoXRefAdditionalSources)?;
+                self.revisions.push(offset);
 
-        self.read_table(input, offset)?;
+                offset
+            }
+        };
+
+        self.read_table(input, offset)
+    }
+
+    /// Reads the header version and the offset of the header in the file.
+    fn read_version(&mut self, input: &[u8]) -> Result<()> {
+        let (_, (version, first_byte)) = read_version(input).ok().context(error::ParseFile {
+            section: "version",
+            offset: 0usize,
+        })?;
+        self.version = Version::from_str(version).context(error::InvalidVersion)?;
+        self.first_byte = first_byte;
 
         Ok(())
     }
@@ -124,6 +433,10 @@ impl Xref {
             return;
         }
 
+        self.in_use
+            .entry(key.id)
+            .or_insert(!matches!(entry, XrefEntry::Free { .. }));
+
         let _ = self.entries.insert(key, entry);
     }
 
@@ -150,11 +463,19 @@ impl Xref {
         Ok(())
     }
 
-    fn parse_trailer(&mut self, input: &[u8]) -> Result<XrefMetadata> {
-        let (_, trailer) = read_trailer(input).ok().context(error::ParseFile {
-            section: "trailer",
-            offset: 0usize,
-        })?;
+    fn parse_trailer(&mut self, input: &[u8], offset: usize) -> Result<XrefMetadata> {
+        let (remained, trailer) =
+            read_trailer(&input[offset..])
+                .ok()
+                .context(error::ParseFile {
+                    section: "trailer",
+                    offset,
+                })?;
+        let end = input.len() - remained.len();
+        let start = input[offset..end]
+            .windows(b"trailer".len())
+            .position(|window| window == b"trailer")
+            .map_or(offset, |position| offset + position);
 
         self.xref_stm = trailer
             .get("XRefStm")
@@ -162,10 +483,16 @@ impl Xref {
             .transpose()
             .context(error::InvalidField { field: "XRefStm" })?;
 
-        self.get_xref_data(&trailer)
+        self.get_xref_data(input, &trailer, XrefKind::Table, start..end)
     }
 
-    fn get_xref_data(&mut self, data: &Dictionary) -> Result<XrefMetadata> {
+    fn get_xref_data(
+        &mut self,
+        input: &[u8],
+        data: &Dictionary,
+        kind: XrefKind,
+        trailer_range: Range<usize>,
+    ) -> Result<XrefMetadata> {
         let size = data
             .get("Size")
             .context(error::FieldNotFound { field: "Size" })?
@@ -184,39 +511,70 @@ impl Xref {
             .transpose()
             .context(error::InvalidHash)?;
 
-        let root_id = data
-            .get("Root")
-            .context(error::FieldNotFound { field: "Root" })?
-            .as_indirect_ref()
-            .cloned()
-            .context(error::InvalidField { field: "Root" })?;
-
         let info_id = data
             .get("Info")
             .map(|object| object.as_indirect_ref().cloned())
             .transpose()
             .context(error::InvalidField { field: "Info" })?;
 
-        // TODO: Support encrypt
+        let encrypt_id = data
+            .get("Encrypt")
+            .and_then(|object| object.as_indirect_ref().ok())
+            .cloned();
 
         self.size = self.size.max(size);
-        self.prev = prev;
+        self.section_sizes.push(size);
+        // A section read through `/XRefStm` has no `/Prev` of its own and must
+        // not drop the one of the trailer that referenced it.
+        if prev.is_some() {
+            self.prev = prev;
+        }
+
+        let root_id = match data.get("Root") {
+            Some(object) => object
+                .as_indirect_ref()
+                .cloned()
+                .context(error::InvalidField { field: "Root" })?,
+            // Some incremental updates leave `/Root` out of their trailer, the
+            // catalog then comes from the previous revision.
+            None => {
+                ensure!(
+                    self.has_more_tables(),
+                    error::FieldNotFound { field: "Root" }
+                );
+
+                self.read_additional_table(input)?.root_id
+            }
+        };
 
         Ok(XrefMetadata {
+            kind,
+            size,
+            prev,
+
             root_id,
             version: self.version.clone(),
 
             hash: file_hash,
             info_id,
+            encrypt_id,
+            trailer_range,
         })
     }
 
-    fn parse_xref_stream(&mut self, stream: Stream) -> Result<XrefMetadata> {
-        let metadata = self.get_xref_data(&stream.dictionary)?;
+    fn parse_xref_stream(
+        &mut self,
+        input: &[u8],
+        stream: Stream,
+        trailer_range: Range<usize>,
+    ) -> Result<XrefMetadata> {
+        let dictionary = stream.dictionary.clone();
 
+        // Entries go first, so they take precedence over older sections
+        // that may be read while looking for `/Root`.
         self.extract_xref_stream_data(stream)?;
 
-        Ok(metadata)
+        self.get_xref_data(input, &dictionary, XrefKind::Stream, trailer_range)
     }
 
     fn extract_xref_stream_data(&mut self, stream: Stream) -> Result<()> {
@@ -250,80 +608,170 @@ impl Xref {
                     .collect::<std::result::Result<Vec<_>, _>>()
             })
             .transpose()
-            .context(error::InvalidField { field: "Index" })?
-            .unwrap_or_else(|| vec![(0, self.size)]);
-
-        let entry_size = w.iter().sum();
-        let current_id = index.iter().flat_map(|(first, last)| *first..=*last);
+            .context(error::InvalidField { field: "Index" })?;
+        // Without `/Index` the stream covers `[0 Size]` of its own `/Size`,
+        // the merged size isn't known yet for the newest section.
+        let index = match index {
+            Some(index) => index,
+            None => {
+                let size = stream
+                    .dictionary
+                    .get("Size")
+                    .context(error::FieldNotFound { field: "Size" })?
+                    .as_integer()
+                    .context(error::InvalidField { field: "Size" })?;
+
+                vec![(0, size)]
+            }
+        };
+
+        let widths = [w[0], w[1], w[2]];
+        let records = XrefStreamRecords::new(&stream.data, widths, index);
+        for record in records {
+            let (key, mut entry) = record?;
+            if let XrefEntry::Occupied { offset } = &mut entry {
+                *offset += self.first_byte;
+            }
 
-        stream
-            .data
-            .chunks_exact(entry_size)
-            .zip(current_id)
-            .try_for_each(|(entry, id)| self.parse_stream_entry(&w, entry, id))?;
+            self.insert_entry(key, entry);
+        }
 
         Ok(())
     }
+}
 
-    fn parse_stream_entry(&mut self, w: &[usize], entry: &[u8], id: usize) -> Result<()> {
-        let mut entry_data = [1, 0, 0];
+/// Object defined by the `obj` keyword at `keyword`, with the offset of its
+/// object number.
+///
+/// The keyword has to be a token of its own, preceded by the object and
+/// generation numbers, which must start a token as well.
+fn object_marker(input: &[u8], keyword: usize) -> Option<(IndirectReference, usize)> {
+    let is_separator = |position: usize| {
+        input
+            .get(position)
+            .is_none_or(|c| is_whitespace(*c) || is_delimiter(*c))
+    };
+    if !is_separator(keyword + b"obj".len()) {
+        return None;
+    }
 
-        w.iter()
-            .zip(entry_data.iter_mut())
-            .fold(0, |pos, (size, data)| {
-                if *size == 0 {
-                    return pos;
-                }
+    // Walks back over whitespace and then digits, returning the digits.
+    let number_before = |end: usize| -> Option<(usize, usize)> {
+        let digits_end = input[..end].iter().rposition(|c| !is_whitespace(*c))? + 1;
+        if digits_end == end {
+            return None;
+        }
+        let digits_start = input[..digits_end]
+            .iter()
+            .rposition(|c| !c.is_ascii_digit())
+            .map_or(0, |position| position + 1);
+        let number = std::str::from_utf8(&input[digits_start..digits_end])
+            .ok()?
+            .parse()
+            .ok()?;
+
+        Some((number, digits_start))
+    };
+
+    let (gen_id, gen_start) = number_before(keyword)?;
+    let (id, start) = number_before(gen_start)?;
+    if start > 0 && !is_separator(start - 1) {
+        return None;
+    }
 
-                *data = entry[pos..(pos + size)]
-                    .iter()
-                    .fold(0usize, |res, byte| res << 8 | (*byte as usize));
-                pos + size
-            });
+    Some((IndirectReference { id, gen_id }, start))
+}
 
-        match entry_data[0] {
-            0 => {
-                let key = IndirectReference {
-                    id,
-                    gen_id: entry_data[2],
-                };
-                let entry = XrefEntry::Free {
-                    next_id: entry_data[1],
-                };
+/// Iterator decoding the records of a cross-reference stream one at a time.
+///
+/// Records are read straight from the decoded stream data, so no list of
+/// entries is built up front. Object numbers are taken from the `/Index`
+/// ranges in order. Offsets of in-use entries are relative to the start of
+/// the file's header, as stored.
+#[derive(Debug, Clone)]
+pub struct XrefStreamRecords<'a> {
+    records: std::slice::ChunksExact<'a, u8>,
+    widths: [usize; 3],
+    ranges: std::vec::IntoIter<(usize, usize)>,
+    current: Range<usize>,
+}
 
-                self.insert_entry(key, entry);
+impl<'a> XrefStreamRecords<'a> {
+    /// # Arguments
+    /// * `data` - Decoded stream data
+    /// * `widths` - Field widths in bytes (`/W`)
+    /// * `ranges` - First object number and record count of every subsection (`/Index`)
+    pub fn new(data: &'a [u8], widths: [usize; 3], ranges: Vec<(usize, usize)>) -> Self {
+        // A zero width record would never advance, `/W [0 0 0]` has no data.
+        let record_size = widths.iter().sum::<usize>().max(1);
+
+        Self {
+            records: data.chunks_exact(record_size),
+            widths,
+            ranges: ranges.into_iter(),
+            current: 0..0,
+        }
+    }
 
-                Ok(())
+    fn next_id(&mut self) -> Option<usize> {
+        loop {
+            if let Some(id) = self.current.next() {
+                return Some(id);
             }
-            1 => {
-                let key = IndirectReference {
-                    id,
-                    gen_id: entry_data[2],
-                };
-                let entry = XrefEntry::Occupied {
-                    offset: self.first_byte + entry_data[1],
-                };
 
-                self.insert_entry(key, entry);
+            let (first, count) = self.ranges.next()?;
+            self.current = first..first.saturating_add(count);
+        }
+    }
+}
 
-                Ok(())
-            }
-            2 => {
-                let key = IndirectReference { id, gen_id: 0 };
-                let entry = XrefEntry::OccupiedCompressed {
-                    stream_id: entry_data[1],
-                    stream_ind: entry_data[2],
-                };
+impl Iterator for XrefStreamRecords<'_> {
+    type Item = Result<(IndirectReference, XrefEntry)>;
 
-                self.insert_entry(key, entry);
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.next_id()?;
+        let record = self.records.next()?;
 
-                Ok(())
+        // The type field defaults to 1 (in use) when its width is zero.
+        let mut fields = [1, 0, 0];
+        let mut position = 0;
+        for (width, field) in self.widths.iter().zip(fields.iter_mut()) {
+            if *width == 0 {
+                continue;
             }
-            _ => Err(error::Error::InvalidXrefStreamEntryType {
-                entry_type: entry_data[0],
-            }
-            .into()),
+
+            *field = record[position..position + width]
+                .iter()
+                .fold(0usize, |res, byte| res << 8 | (*byte as usize));
+            position += width;
         }
+
+        let record = match fields[0] {
+            0 => Ok((
+                IndirectReference {
+                    id,
+                    gen_id: fields[2],
+                },
+                XrefEntry::Free { next_id: fields[1] },
+            )),
+            1 => Ok((
+                IndirectReference {
+                    id,
+                    gen_id: fields[2],
+                },
+                XrefEntry::Occupied { offset: fields[1] },
+            )),
+            2 => Ok((
+                IndirectReference { id, gen_id: 0 },
+                XrefEntry::OccupiedCompressed {
+                    stream_id: fields[1],
+                    stream_ind: fields[2],
+                },
+            )),
+            entry_type => Err(error::Error::InvalidXrefStreamEntryType { entry_type }.into()),
+        };
+
+        Some(record)
     }
 }
 
@@ -344,6 +792,12 @@ mod error {
             source: crate::structures::root::version::Error,
         },
 
+        #[snafu(display("Cross-reference section at offset {offset} was already read"))]
+        CyclicXrefChain { offset: u64 },
+
+        #[snafu(display("Cross-reference chain is longer than {limit} sections"))]
+        XrefChainTooLong { limit: usize },
+
         #[snafu(display("Xref has no XRefStm or Prev instances"))]
         NoXRefAdditionalSources,
 
@@ -382,3 +836,139 @@ mod error {
         InvalidXrefStreamEntryType { entry_type: usize },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xref_stream_records() {
+        // `/W [1 2 1]`, subsections `0 2` and `10 2`.
+        let data = [
+            0, 0x00, 0x00, 0xFF, // 0: free
+            1, 0x01, 0x2C, 0x00, // 1: offset 300
+            2, 0x00, 0x07, 0x03, // 10: object stream 7, index 3
+            1, 0x00, 0x0F, 0x02, // 11: offset 15, generation 2
+        ];
+
+        let records = XrefStreamRecords::new(&data, [1, 2, 1], vec![(0, 2), (10, 2)])
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        let expected = [
+            ((0, 255), XrefEntry::Free { next_id: 0 }),
+            ((1, 0), XrefEntry::Occupied { offset: 300 }),
+            (
+                (10, 0),
+                XrefEntry::OccupiedCompressed {
+                    stream_id: 7,
+                    stream_ind: 3,
+                },
+            ),
+            ((11, 2), XrefEntry::Occupied { offset: 15 }),
+        ]
+        .map(|((id, gen_id), entry)| (IndirectReference { id, gen_id }, entry));
+
+        assert_eq!(records, expected);
+
+        let mut records = XrefStreamRecords::new(&[3, 0, 0, 0], [1, 2, 1], vec![(0, 1)]);
+        assert!(records.next().unwrap().is_err());
+        assert!(records.next().is_none());
+    }
+
+    /// File with objects 1 to 3 and the xref stream 4, using `/W [1 4 2]`.
+    ///
+    /// # Returns
+    /// The file and the offset of every object
+    fn xref_stream_file(index: Option<&str>, ids: &[usize]) -> (Vec<u8>, Vec<usize>) {
+        let mut data = b"%PDF-1.7\n".to_vec();
+        let mut offsets = vec![0];
+        for id in 1..=3 {
+            offsets.push(data.len());
+            data.extend_from_slice(format!("{id} 0 obj\n({id})\nendobj\n").as_bytes());
+        }
+        let xref_offset = data.len();
+        offsets.push(xref_offset);
+
+        let mut records = Vec::new();
+        for id in ids {
+            match id {
+                0 => records.extend_from_slice(&[0, 0, 0, 0, 0, 0xFF, 0xFF]),
+                id => {
+                    records.push(1);
+                    records.extend_from_slice(&(offsets[*id] as u32).to_be_bytes());
+                    records.extend_from_slice(&[0, 0]);
+                }
+            }
+        }
+
+        let index = index.map_or(String::new(), |index| format!("/Index {index}"));
+        data.extend_from_slice(
+            format!(
+                "4 0 obj\n<< /Type /XRef /Size 5 /W [1 4 2] {index} /Root 1 0 R /Length {} >>\nstream\n",
+                records.len()
+            )
+            .as_bytes(),
+        );
+        data.extend_from_slice(&records);
+        data.extend_from_slice(
+            format!("\nendstream\nendobj\nstartxref\n{xref_offset}\n%%EOF\n").as_bytes(),
+        );
+
+        (data, offsets)
+    }
+
+    #[test]
+    fn test_xref_stream_index() {
+        struct TestCase {
+            name: &'static str,
+            index: Option<&'static str>,
+            ids: &'static [usize],
+            expected: &'static [usize],
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "omitted index defaults to [0 Size]",
+                index: None,
+                ids: &[0, 1, 2, 3, 4],
+                expected: &[1, 2, 3, 4],
+            },
+            TestCase {
+                name: "single range",
+                index: Some("[0 5]"),
+                ids: &[0, 1, 2, 3, 4],
+                expected: &[1, 2, 3, 4],
+            },
+            TestCase {
+                name: "multiple ranges",
+                index: Some("[0 2 3 2]"),
+                ids: &[0, 1, 3, 4],
+                expected: &[1, 3, 4],
+            },
+        ];
+
+        for case in &test_cases {
+            let (data, offsets) = xref_stream_file(case.index, case.ids);
+
+            let mut xref = Xref::new(None, DecodeBudget::default(), Strictness::default());
+            let offset = xref.read_startxref(&data, data.len()).unwrap();
+            let metadata = xref.read_table(&data, offset).unwrap();
+            assert_eq!(metadata.kind, XrefKind::Stream);
+
+            for (id, offset) in offsets.iter().enumerate().skip(1) {
+                let entry = xref.find_entry(&IndirectReference { id, gen_id: 0 });
+                let expected = case
+                    .expected
+                    .contains(&id)
+                    .then_some(XrefEntry::Occupied { offset: *offset });
+                assert_eq!(
+                    entry.cloned(),
+                    expected,
+                    "Test '{}' failed: wrong entry for object {id}",
+                    case.name
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Helpers for building small synthetic PDF files in tests.
+#![allow(dead_code)]
+
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Minimal catalog and empty page tree, used as objects 1 and 2 by most fixtures.
+pub const CATALOG: &str = "<< /Type /Catalog /Pages 2 0 R >>";
+pub const EMPTY_PAGES: &str = "<< /Type /Pages /Kids [] /Count 0 >>";
+
+#[derive(Debug, Clone, Copy)]
+enum Entry {
+    Occupied(usize),
+    Compressed { stream_id: usize, index: usize },
+    Free,
+}
+
+/// Builds a PDF file with correct xref offsets.
+///
+/// Objects are appended in call order. Every call to [`PdfBuilder::section`]
+/// writes an xref table for the objects added since the previous section,
+/// followed by a trailer that links back to the previous section via `/Prev`.
+#[derive(Debug)]
+pub struct PdfBuilder {
+    data: Vec<u8>,
+    pending: BTreeMap<usize, Entry>,
+    last_xref: Option<usize>,
+    size: usize,
+}
+
+impl PdfBuilder {
+    pub fn new() -> Self {
+        Self::with_header("%PDF-1.7\n")
+    }
+
+    pub fn with_header(header: &str) -> Self {
+        Self {
+            data: header.as_bytes().to_vec(),
+            pending: BTreeMap::from([(0, Entry::Free)]),
+            last_xref: None,
+            size: 1,
+        }
+    }
+
+    /// Offset at which the next written byte will be placed.
+    pub fn offset(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Appends `id 0 obj <body> endobj` and registers it in the next xref section.
+    pub fn object(self, id: usize, body: &str) -> Self {
+        self.object_bytes(id, body.as_bytes())
+    }
+
+    pub fn object_bytes(mut self, id: usize, body: &[u8]) -> Self {
+        self.pending.insert(id, Entry::Occupied(self.data.len()));
+        self.size = self.size.max(id + 1);
+
+        self.data
+            .extend_from_slice(format!("{id} 0 obj\n").as_bytes());
+        self.data.extend_from_slice(body);
+        self.data.extend_from_slice(b"\nendobj\n");
+        self
+    }
+
+    /// Appends `data` verbatim and registers it as object `id`, for malformed objects.
+    pub fn object_raw(mut self, id: usize, data: &[u8]) -> Self {
+        self.pending.insert(id, Entry::Occupied(self.data.len()));
+        self.size = self.size.max(id + 1);
+
+        self.data.extend_from_slice(data);
+        self
+    }
+
+    /// Appends a stream object with a correct `/Length`.
+    pub fn stream(self, id: usize, dictionary: &str, data: &[u8]) -> Self {
+        let mut body = format!("<< /Length {} {dictionary} >>\nstream\n", data.len()).into_bytes();
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\nendstream");
+
+        self.object_bytes(id, &body)
+    }
+
+    /// Appends an uncompressed object stream `id` holding `objects`.
+    ///
+    /// The contained objects are registered as compressed entries, so the
+    /// next section has to be written with [`PdfBuilder::stream_section`].
+    pub fn object_stream(mut self, id: usize, objects: &[(usize, &str)]) -> Self {
+        let mut header = String::new();
+        let mut body = String::new();
+        for (index, (object_id, object)) in objects.iter().enumerate() {
+            header.push_str(&format!("{object_id} {} ", body.len()));
+            body.push_str(object);
+            body.push('\n');
+
+            self.pending.insert(
+                *object_id,
+                Entry::Compressed {
+                    stream_id: id,
+                    index,
+                },
+            );
+            self.size = self.size.max(object_id + 1);
+        }
+
+        let dictionary = format!("/Type /ObjStm /N {} /First {}", objects.len(), header.len());
+        self.stream(id, &dictionary, format!("{header}{body}").as_bytes())
+    }
+
+    /// Marks `id` as free in the next xref section.
+    pub fn free(mut self, id: usize) -> Self {
+        self.pending.insert(id, Entry::Free);
+        self.size = self.size.max(id + 1);
+        self
+    }
+
+    /// Appends raw bytes without registering anything in the xref.
+    pub fn raw(mut self, bytes: &[u8]) -> Self {
+        self.data.extend_from_slice(bytes);
+        self
+    }
+
+    /// Writes an xref table, trailer and `startxref` for pending entries.
+    ///
+    /// `/Size` and `/Prev` are filled in automatically unless `trailer`
+    /// already contains them.
+    pub fn section(mut self, trailer: &str) -> Self {
+        let xref_offset = self.data.len();
+        let pending = std::mem::take(&mut self.pending);
+
+        let mut xref = String::from("xref\n");
+        let entries = pending.into_iter().collect::<Vec<_>>();
+        for chunk in entries.chunk_by(|(a, _), (b, _)| a + 1 == *b) {
+            xref.push_str(&format!("{} {}\n", chunk[0].0, chunk.len()));
+
+            for (id, entry) in chunk {
+                match entry {
+                    Entry::Occupied(offset) => xref.push_str(&format!("{offset:010} 00000 n \n")),
+                    Entry::Compressed { .. } => panic!("compressed entries need an xref stream"),
+                    Entry::Free if *id == 0 => xref.push_str("0000000000 65535 f \n"),
+                    Entry::Free => xref.push_str("0000000000 00001 f \n"),
+                }
+            }
+        }
+
+        let mut dictionary = String::new();
+        if !trailer.contains("/Size") {
+            dictionary.push_str(&format!("/Size {} ", self.size));
+        }
+        if let Some(prev) = self.last_xref
+            && !trailer.contains("/Prev")
+        {
+            dictionary.push_str(&format!("/Prev {prev} "));
+        }
+        dictionary.push_str(trailer);
+
+        xref.push_str(&format!(
+            "trailer\n<< {dictionary} >>\nstartxref\n{xref_offset}\n%%EOF\n"
+        ));
+
+        self.data.extend_from_slice(xref.as_bytes());
+        self.last_xref = Some(xref_offset);
+        self
+    }
+
+    /// Writes a cross-reference stream object `id` for pending entries.
+    ///
+    /// The stream is left uncompressed and uses `/W [1 4 2]`. `/Size`,
+    /// `/Prev` and `/Index` are filled in automatically.
+    pub fn stream_section(mut self, id: usize, trailer: &str) -> Self {
+        let xref_offset = self.data.len();
+        self.pending.insert(id, Entry::Occupied(xref_offset));
+        self.size = self.size.max(id + 1);
+
+        let pending = std::mem::take(&mut self.pending);
+        let entries = pending.into_iter().collect::<Vec<_>>();
+
+        let mut index = Vec::new();
+        let mut data = Vec::new();
+        for chunk in entries.chunk_by(|(a, _), (b, _)| a + 1 == *b) {
+            index.push(format!("{} {}", chunk[0].0, chunk.len()));
+
+            for (id, entry) in chunk {
+                let (kind, field, generation) = match entry {
+                    Entry::Occupied(offset) => (1u8, *offset as u32, 0u16),
+                    Entry::Compressed { stream_id, index } => (2, *stream_id as u32, *index as u16),
+                    Entry::Free if *id == 0 => (0, 0, 65535),
+                    Entry::Free => (0, 0, 1),
+                };
+                data.push(kind);
+                data.extend_from_slice(&field.to_be_bytes());
+                data.extend_from_slice(&generation.to_be_bytes());
+            }
+        }
+
+        let mut dictionary = format!("/Type /XRef /W [1 4 2] /Index [{}] ", index.join(" "));
+        if !trailer.contains("/Size") {
+            dictionary.push_str(&format!("/Size {} ", self.size));
+        }
+        if let Some(prev) = self.last_xref
+            && !trailer.contains("/Prev")
+        {
+            dictionary.push_str(&format!("/Prev {prev} "));
+        }
+        dictionary.push_str(trailer);
+
+        let mut body = format!(
+            "{id} 0 obj\n<< /Length {} {dictionary} >>\nstream\n",
+            data.len()
+        )
+        .into_bytes();
+        body.extend_from_slice(&data);
+        body.extend_from_slice(
+            format!("\nendstream\nendobj\nstartxref\n{xref_offset}\n%%EOF\n").as_bytes(),
+        );
+
+        self.data.extend_from_slice(&body);
+        self.last_xref = Some(xref_offset);
+        self
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Writes the file into the temp directory and returns its path.
+    pub fn write(self, name: &str) -> PathBuf {
+        write_temp(name, &self.build())
+    }
+}
+
+/// Writes `data` into a uniquely named file inside the temp directory.
+pub fn write_temp(name: &str, data: &[u8]) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let mut path = std::env::temp_dir();
+    path.push(format!(
+        "docv-test-{}-{}-{name}.pdf",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    std::fs::write(&path, data).expect("failed to write test file");
+    path
+}
+
+/// Single-section document containing only a catalog and an empty page tree.
+pub fn minimal_document() -> PdfBuilder {
+    PdfBuilder::new().object(1, CATALOG).object(2, EMPTY_PAGES)
+}
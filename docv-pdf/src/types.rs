@@ -1,6 +1,8 @@
 pub mod array;
+pub mod content;
 pub mod dictionary;
 pub mod indirect_object;
+pub mod matrix;
 pub mod name;
 pub mod numeric;
 pub mod object;
@@ -9,10 +11,12 @@ pub mod string;
 
 pub use array::Array;
 pub use array::rectangle::Rectangle;
-pub use dictionary::Dictionary;
+pub use content::ContentOp;
+pub use dictionary::{Dictionary, DictionaryBuilder, DuplicateKeyPolicy};
 pub use indirect_object::{IndirectObject, IndirectReference};
+pub use matrix::Matrix;
 pub use name::Name;
 pub use numeric::Numeric;
 pub use object::Object;
-pub use stream::Stream;
+pub use stream::{ImageFormat, Stream};
 pub use string::PdfString;
@@ -350,11 +350,11 @@ mod tests {
         ]));
 
         let string_refs: Vec<PdfString> =
-            ArrayBuilder::new(&string_array).of(|obj| obj.as_string().map(|s| s.clone()))?;
+            ArrayBuilder::new(&string_array).of(|obj| obj.as_string().cloned())?;
 
         assert_eq!(string_refs.len(), 2);
-        assert_eq!(string_refs[0], PdfString::Literal(String::from("hello")));
-        assert_eq!(string_refs[1], PdfString::Literal(String::from("world")));
+        assert_eq!(string_refs[0], PdfString::Literal("hello".into()));
+        assert_eq!(string_refs[1], PdfString::Literal("world".into()));
 
         // Test 9: Array with name values
         let name_array = Object::Array(Array::from(vec![
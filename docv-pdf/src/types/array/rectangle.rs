@@ -20,6 +20,14 @@ pub struct Rectangle {
 }
 
 impl Rectangle {
+    /// US Letter page, 8.5 by 11 inches.
+    pub const LETTER: Rectangle = Rectangle {
+        left: 0.0,
+        bottom: 0.0,
+        right: 612.0,
+        top: 792.0,
+    };
+
     /// Creates a new rectangle from four coordinates.
     ///
     /// The coordinates are automatically normalized to ensure
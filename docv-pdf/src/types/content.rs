@@ -0,0 +1,27 @@
+use smol_str::SmolStr;
+
+use crate::types::Object;
+
+/// Represents a single operation of a PDF content stream.
+///
+/// Content streams are sequences of operands followed by an operator
+/// in postfix notation, e.g. `0 0 100 100 re` or `/F1 12 Tf`.
+///
+/// # Inline images
+/// Inline images (`BI ... ID ... EI`) are reported as a single operation with
+/// the `BI` operator and one [`Object::Stream`] operand holding the image
+/// parameters and its raw data.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContentOp {
+    pub operator: SmolStr,
+    pub operands: Vec<Object>,
+}
+
+impl ContentOp {
+    pub fn new(operator: impl Into<SmolStr>, operands: Vec<Object>) -> Self {
+        Self {
+            operator: operator.into(),
+            operands,
+        }
+    }
+}
@@ -2,7 +2,10 @@ use std::collections::BTreeMap;
 
 use smol_str::SmolStr;
 
-use crate::types::object::Object;
+use crate::types::{
+    Array, Stream,
+    object::{self, Object},
+};
 
 /// Represents a PDF dictionary object containing key-value pairs.
 ///
@@ -26,6 +29,115 @@ pub struct Dictionary {
     records: BTreeMap<SmolStr, Object>,
 }
 
+/// Builder for constructing a [`Dictionary`] entry by entry.
+///
+/// # Example
+/// ```
+/// use docv_pdf::{Dictionary, Object};
+///
+/// let page = Dictionary::builder()
+///     .entry("Type", Object::name("Page"))
+///     .entry("Rotate", Object::integer(90))
+///     .build();
+///
+/// assert_eq!(page.len(), 2);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct DictionaryBuilder {
+    records: BTreeMap<SmolStr, Object>,
+}
+
+/// How a parsed dictionary that repeats a key is read.
+///
+/// PDF leaves the meaning of a repeated key undefined and readers disagree
+/// on it. A validator can pick [`DuplicateKeyPolicy::Error`], so that a
+/// second `/Type` can't make the file look different to different readers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// The first value of the key is kept, later ones are ignored
+    KeepFirst,
+    /// Every value replaces the previous one, as in Acrobat
+    #[default]
+    KeepLast,
+    /// A repeated key makes the dictionary fail to parse
+    Error,
+}
+
+impl Dictionary {
+    pub fn builder() -> DictionaryBuilder {
+        DictionaryBuilder::default()
+    }
+
+    /// Inserts an entry, returning the previous value stored under `key`.
+    pub fn insert(&mut self, key: impl Into<SmolStr>, value: Object) -> Option<Object> {
+        self.records.insert(key.into(), value)
+    }
+
+    /// Removes an entry, returning the value that was stored under `key`.
+    pub fn remove(&mut self, key: &str) -> Option<Object> {
+        self.records.remove(key)
+    }
+
+    /// Name stored under `key`.
+    ///
+    /// The typed getters return `None` if `key` is absent and an error if the
+    /// value has another type. Indirect references are not resolved.
+    pub fn get_name(&self, key: &str) -> Option<Result<&str, object::Error>> {
+        self.get(key)
+            .map(|object| object.as_name().map(|name| name.as_str()))
+    }
+
+    /// Integer stored under `key`, see [`Dictionary::get_name`].
+    pub fn get_integer<T>(&self, key: &str) -> Option<Result<T, object::Error>>
+    where
+        T: TryFrom<i64>,
+    {
+        self.get(key).map(|object| object.as_integer())
+    }
+
+    /// Number stored under `key`, see [`Dictionary::get_name`].
+    pub fn get_float(&self, key: &str) -> Option<Result<f64, object::Error>> {
+        self.get(key).map(|object| object.as_float())
+    }
+
+    /// Boolean stored under `key`, see [`Dictionary::get_name`].
+    pub fn get_bool(&self, key: &str) -> Option<Result<bool, object::Error>> {
+        self.get(key).map(|object| object.as_bool())
+    }
+
+    /// Dictionary stored under `key`, see [`Dictionary::get_name`].
+    pub fn get_dictionary(&self, key: &str) -> Option<Result<&Dictionary, object::Error>> {
+        self.get(key).map(|object| object.as_dictionary())
+    }
+
+    /// Array stored under `key`, see [`Dictionary::get_name`].
+    pub fn get_array(&self, key: &str) -> Option<Result<&Array, object::Error>> {
+        self.get(key).map(|object| match object {
+            Object::Array(array) => Ok(array),
+            object => Err(object::Error::unexpected_type("Array", object)),
+        })
+    }
+
+    /// Stream stored under `key`, see [`Dictionary::get_name`].
+    pub fn get_stream(&self, key: &str) -> Option<Result<&Stream, object::Error>> {
+        self.get(key).map(|object| object.as_stream())
+    }
+}
+
+impl DictionaryBuilder {
+    /// Adds an entry, replacing any previous value stored under `key`.
+    pub fn entry(mut self, key: impl Into<SmolStr>, value: Object) -> Self {
+        self.records.insert(key.into(), value);
+        self
+    }
+
+    pub fn build(self) -> Dictionary {
+        Dictionary {
+            records: self.records,
+        }
+    }
+}
+
 impl<K: std::convert::Into<SmolStr>> From<Vec<(K, Object)>> for Dictionary {
     fn from(value: Vec<(K, Object)>) -> Self {
         let value = value.into_iter().map(|(key, val)| (key.into(), val));
@@ -53,3 +165,157 @@ impl std::ops::Deref for Dictionary {
         &self.records
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Array, IndirectObject, IndirectReference, Numeric, PdfString};
+
+    #[test]
+    fn test_dictionary_builder() {
+        let built = Dictionary::builder()
+            .entry("Type", Object::name("Page"))
+            .entry(
+                "Parent",
+                Object::IndirectReference(IndirectReference { id: 2, gen_id: 0 }),
+            )
+            .entry(
+                "MediaBox",
+                Object::Array(Array::from([
+                    Object::integer(0),
+                    Object::integer(0),
+                    Object::real(595.5),
+                    Object::integer(842),
+                ])),
+            )
+            .entry("Rotate", Object::integer(180))
+            .entry("Rotate", Object::integer(90))
+            .entry("TemplateInstantiated", Object::string("Form"))
+            .build();
+
+        let expected = Dictionary::from([
+            ("Type", Object::Name("Page".into())),
+            (
+                "Parent",
+                Object::IndirectReference(IndirectReference { id: 2, gen_id: 0 }),
+            ),
+            (
+                "MediaBox",
+                Object::Array(Array::from([
+                    Object::Numeric(Numeric::Integer(0)),
+                    Object::Numeric(Numeric::Integer(0)),
+                    Object::Numeric(Numeric::Real(595.5)),
+                    Object::Numeric(Numeric::Integer(842)),
+                ])),
+            ),
+            ("Rotate", Object::Numeric(Numeric::Integer(90))),
+            (
+                "TemplateInstantiated",
+                Object::String(PdfString::Literal("Form".into())),
+            ),
+        ]);
+
+        assert_eq!(built, expected);
+        assert_eq!(Dictionary::builder().build(), Dictionary::default());
+    }
+
+    #[test]
+    fn test_as_dictionary_mut() {
+        let info = Dictionary::from([
+            ("Title", Object::string("Draft")),
+            ("Producer", Object::string("docv")),
+        ]);
+        let mut object =
+            Object::IndirectDefinition(IndirectObject::new(3, 0, Object::Dictionary(info)));
+        let original = object.clone();
+
+        let dictionary = object.as_dictionary_mut().unwrap();
+        dictionary.insert("Title", Object::string("Final"));
+        dictionary.remove("Producer");
+
+        let dictionary = object.as_dictionary().unwrap();
+        assert_eq!(dictionary.get("Title"), Some(&Object::string("Final")));
+        assert_eq!(dictionary.get("Producer"), None);
+
+        // Clones made before the edit keep the old value.
+        let dictionary = original.as_dictionary().unwrap();
+        assert_eq!(dictionary.get("Title"), Some(&Object::string("Draft")));
+
+        assert!(Object::integer(1).as_dictionary_mut().is_err());
+    }
+
+    #[test]
+    fn test_empty_key() {
+        let mut dictionary = Dictionary::from([("A", Object::integer(1))]);
+        assert_eq!(dictionary.insert("", Object::name("Value")), None);
+        assert_eq!(
+            dictionary.insert("", Object::name("")),
+            Some(Object::name("Value"))
+        );
+
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary.get(""), Some(&Object::name("")));
+        assert_eq!(dictionary.get_name("").unwrap().unwrap(), "");
+        assert_eq!(dictionary.get("A"), Some(&Object::integer(1)));
+
+        assert_eq!(dictionary.remove(""), Some(Object::name("")));
+        assert_eq!(dictionary.get(""), None);
+    }
+
+    #[test]
+    fn test_typed_getters() {
+        let dictionary = Dictionary::from([
+            ("Type", Object::name("Page")),
+            ("Rotate", Object::integer(90)),
+            ("UserUnit", Object::real(1.5)),
+            ("Hidden", Object::Boolean(true)),
+            ("Resources", Object::Dictionary(Dictionary::default())),
+            ("Kids", Object::Array(Array::from([Object::integer(1)]))),
+            (
+                "Contents",
+                Object::Stream(Stream::new(Dictionary::default(), b"q Q".to_vec())),
+            ),
+        ]);
+
+        // Present with the correct type
+        assert_eq!(dictionary.get_name("Type").unwrap().unwrap(), "Page");
+        assert_eq!(
+            dictionary.get_integer::<u16>("Rotate").unwrap().unwrap(),
+            90
+        );
+        assert_eq!(dictionary.get_float("UserUnit").unwrap().unwrap(), 1.5);
+        assert_eq!(dictionary.get_float("Rotate").unwrap().unwrap(), 90.0);
+        assert!(dictionary.get_bool("Hidden").unwrap().unwrap());
+        assert!(
+            dictionary
+                .get_dictionary("Resources")
+                .unwrap()
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(dictionary.get_array("Kids").unwrap().unwrap().len(), 1);
+        assert_eq!(
+            dictionary.get_stream("Contents").unwrap().unwrap().data,
+            b"q Q"
+        );
+
+        // Present with a wrong type
+        assert!(dictionary.get_name("Rotate").unwrap().is_err());
+        assert!(dictionary.get_integer::<i64>("Type").unwrap().is_err());
+        assert!(dictionary.get_integer::<u8>("Rotate").unwrap().is_ok());
+        assert!(dictionary.get_float("Hidden").unwrap().is_err());
+        assert!(dictionary.get_bool("Type").unwrap().is_err());
+        assert!(dictionary.get_dictionary("Kids").unwrap().is_err());
+        assert!(dictionary.get_array("Resources").unwrap().is_err());
+        assert!(dictionary.get_stream("Resources").unwrap().is_err());
+
+        // Absent
+        assert!(dictionary.get_name("Missing").is_none());
+        assert!(dictionary.get_integer::<i64>("Missing").is_none());
+        assert!(dictionary.get_float("Missing").is_none());
+        assert!(dictionary.get_bool("Missing").is_none());
+        assert!(dictionary.get_dictionary("Missing").is_none());
+        assert!(dictionary.get_array("Missing").is_none());
+        assert!(dictionary.get_stream("Missing").is_none());
+    }
+}
@@ -43,6 +43,14 @@ impl IndirectObject {
             object: object.into(),
         }
     }
+
+    /// Mutable access to the contained object.
+    ///
+    /// The object is shared between clones, so it is copied first if any
+    /// other clone still refers to it.
+    pub fn object_mut(&mut self) -> &mut Object {
+        Arc::make_mut(&mut self.object)
+    }
 }
 
 impl std::ops::Deref for IndirectObject {
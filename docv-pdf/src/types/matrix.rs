@@ -0,0 +1,79 @@
+/// A PDF transformation matrix `[a b c d e f]`.
+///
+/// Represents the 3x3 matrix
+/// ```text
+/// | a b 0 |
+/// | c d 0 |
+/// | e f 1 |
+/// ```
+/// which maps a point `(x, y)` to `(a*x + c*y + e, b*x + d*y + f)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Matrix {
+    pub const IDENTITY: Matrix = Matrix::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    pub const fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    /// Matrix that moves points by `(tx, ty)`.
+    pub const fn translation(tx: f64, ty: f64) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, tx, ty)
+    }
+
+    /// Returns `self × other`, the transformation applying `self` first and then `other`.
+    pub fn multiply(&self, other: &Matrix) -> Matrix {
+        Matrix {
+            a: self.a * other.a + self.b * other.c,
+            b: self.a * other.b + self.b * other.d,
+            c: self.c * other.a + self.d * other.c,
+            d: self.c * other.b + self.d * other.d,
+            e: self.e * other.a + self.f * other.c + other.e,
+            f: self.e * other.b + self.f * other.d + other.f,
+        }
+    }
+
+    /// Applies the matrix to the point `(x, y)`.
+    pub fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiply() {
+        let scale = Matrix::new(2.0, 0.0, 0.0, 3.0, 0.0, 0.0);
+        let translate = Matrix::translation(10.0, 20.0);
+
+        assert_eq!(
+            scale.multiply(&translate),
+            Matrix::new(2.0, 0.0, 0.0, 3.0, 10.0, 20.0)
+        );
+        assert_eq!(
+            translate.multiply(&scale),
+            Matrix::new(2.0, 0.0, 0.0, 3.0, 20.0, 60.0)
+        );
+        assert_eq!(scale.multiply(&Matrix::IDENTITY), scale);
+        assert_eq!(translate.transform_point(1.0, 1.0), (11.0, 21.0));
+    }
+}
@@ -1,3 +1,5 @@
+use snafu::Snafu;
+
 /// Represents numeric values in a PDF document according to PDF 2.0 specification.
 ///
 /// PDF supports two types of numeric values:
@@ -34,3 +36,216 @@ pub enum Numeric {
     /// have implementation-defined range and precision limits.
     Real(f64),
 }
+
+#[derive(Debug, Snafu)]
+pub struct Error(error::Error);
+type Result<T> = std::result::Result<T, Error>;
+
+/// Largest magnitude below which every integer is exactly representable as `f64`.
+const MAX_EXACT_F64: i64 = 1 << f64::MANTISSA_DIGITS;
+
+impl Numeric {
+    /// Converts the value into the integer type `T`.
+    ///
+    /// Reals are accepted only if they have no fractional part, e.g. `612.0`.
+    ///
+    /// # Errors
+    /// Returns an error naming the value and `T` if the value is out of range,
+    /// or if a real has a fractional part.
+    pub fn try_as<T>(&self) -> Result<T>
+    where
+        T: TryFrom<i64>,
+    {
+        let value = match *self {
+            Numeric::Integer(value) => value,
+            Numeric::Real(value) => {
+                if value.fract() != 0.0 || !value.is_finite() {
+                    return Err(error::Error::NotInteger { value }.into());
+                }
+                if value < i64::MIN as f64 || value >= i64::MAX as f64 {
+                    return Err(error::Error::RealOutOfRange {
+                        value,
+                        target: std::any::type_name::<T>(),
+                    }
+                    .into());
+                }
+                value as i64
+            }
+        };
+
+        T::try_from(value).map_err(|_| {
+            error::Error::OutOfRange {
+                value,
+                target: std::any::type_name::<T>(),
+            }
+            .into()
+        })
+    }
+
+    /// Converts the value into `i64`, see [`Numeric::try_as`].
+    pub fn try_as_i64(&self) -> Result<i64> {
+        self.try_as()
+    }
+
+    /// Converts the value into `i32`, see [`Numeric::try_as`].
+    pub fn try_as_i32(&self) -> Result<i32> {
+        self.try_as()
+    }
+
+    /// Converts the value into `u32`, see [`Numeric::try_as`].
+    pub fn try_as_u32(&self) -> Result<u32> {
+        self.try_as()
+    }
+
+    /// Converts the value into `u16`, see [`Numeric::try_as`].
+    pub fn try_as_u16(&self) -> Result<u16> {
+        self.try_as()
+    }
+
+    /// Converts the value into `usize`, see [`Numeric::try_as`].
+    pub fn try_as_usize(&self) -> Result<usize> {
+        self.try_as()
+    }
+
+    /// Converts the value into `f64`.
+    ///
+    /// # Errors
+    /// Returns an error if the value is an integer that `f64` can't represent exactly.
+    pub fn try_as_f64(&self) -> Result<f64> {
+        match *self {
+            Numeric::Integer(value) if !(-MAX_EXACT_F64..=MAX_EXACT_F64).contains(&value) => {
+                Err(error::Error::PrecisionLoss { value }.into())
+            }
+            Numeric::Integer(value) => Ok(value as f64),
+            Numeric::Real(value) => Ok(value),
+        }
+    }
+}
+
+mod error {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(super)), context(suffix(false)))]
+    pub(super) enum Error {
+        #[snafu(display("{value} out of range for {target}"))]
+        OutOfRange { value: i64, target: &'static str },
+
+        #[snafu(display("{value} out of range for {target}"))]
+        RealOutOfRange { value: f64, target: &'static str },
+
+        #[snafu(display("{value} is not an integer"))]
+        NotInteger { value: f64 },
+
+        #[snafu(display("{value} can't be represented exactly as f64"))]
+        PrecisionLoss { value: i64 },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_as_integer() {
+        struct TestCase {
+            name: &'static str,
+            value: Numeric,
+            convert: fn(&Numeric) -> Result<i64>,
+            expected: std::result::Result<i64, &'static str>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "u16 in range",
+                value: Numeric::Integer(270),
+                convert: |n| n.try_as_u16().map(i64::from),
+                expected: Ok(270),
+            },
+            TestCase {
+                name: "u16 too large",
+                value: Numeric::Integer(70000),
+                convert: |n| n.try_as_u16().map(i64::from),
+                expected: Err("70000 out of range for u16"),
+            },
+            TestCase {
+                name: "u32 negative",
+                value: Numeric::Integer(-1),
+                convert: |n| n.try_as_u32().map(i64::from),
+                expected: Err("-1 out of range for u32"),
+            },
+            TestCase {
+                name: "i32 too large",
+                value: Numeric::Integer(1 << 40),
+                convert: |n| n.try_as_i32().map(i64::from),
+                expected: Err("1099511627776 out of range for i32"),
+            },
+            TestCase {
+                name: "usize negative",
+                value: Numeric::Integer(-5),
+                convert: |n| n.try_as_usize().map(|v| v as i64),
+                expected: Err("-5 out of range for usize"),
+            },
+            TestCase {
+                name: "whole real",
+                value: Numeric::Real(612.0),
+                convert: Numeric::try_as_i64,
+                expected: Ok(612),
+            },
+            TestCase {
+                name: "fractional real",
+                value: Numeric::Real(0.5),
+                convert: Numeric::try_as_i64,
+                expected: Err("0.5 is not an integer"),
+            },
+            TestCase {
+                name: "real too large",
+                value: Numeric::Real(1e30),
+                convert: Numeric::try_as_i64,
+                expected: Err("1000000000000000000000000000000 out of range for i64"),
+            },
+            TestCase {
+                name: "whole real too large for u16",
+                value: Numeric::Real(70000.0),
+                convert: |n| n.try_as_u16().map(i64::from),
+                expected: Err("70000 out of range for u16"),
+            },
+        ];
+
+        for case in test_cases {
+            let result = (case.convert)(&case.value).map_err(|e| e.to_string());
+            assert_eq!(
+                result,
+                case.expected.map_err(str::to_string),
+                "Test '{}' failed",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_try_as_f64() {
+        let test_cases = [
+            ("integer", Numeric::Integer(-42), Ok(-42.0)),
+            ("real", Numeric::Real(1.5), Ok(1.5)),
+            (
+                "largest exact",
+                Numeric::Integer(1 << 53),
+                Ok(9007199254740992.0),
+            ),
+            (
+                "precision loss",
+                Numeric::Integer((1 << 53) + 1),
+                Err("9007199254740993 can't be represented exactly as f64"),
+            ),
+        ];
+
+        for (name, value, expected) in test_cases {
+            assert_eq!(
+                value.try_as_f64().map_err(|e| e.to_string()),
+                expected.map_err(str::to_string),
+                "Test '{name}' failed"
+            );
+        }
+    }
+}
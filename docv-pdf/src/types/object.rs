@@ -1,7 +1,8 @@
 use core::str;
 use std::borrow::Cow;
 
-use snafu::{OptionExt, Snafu};
+use smol_str::SmolStr;
+use snafu::{ResultExt, Snafu};
 
 use crate::{
     objects::Objects,
@@ -15,6 +16,17 @@ use crate::{
 pub struct Error(error::Error);
 type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+    /// Error for an object of another type than `expected`.
+    pub(crate) fn unexpected_type(expected: &'static str, got: &Object) -> Self {
+        error::Error::UnexpectedObjectType {
+            expected,
+            got: got.clone(),
+        }
+        .into()
+    }
+}
+
 /// Represents all fundamental object types defined in the PDF 2.0 specification.
 ///
 /// PDF documents are built from a hierarchy of objects that can be:
@@ -71,6 +83,26 @@ pub enum Object {
 }
 
 impl Object {
+    /// Creates an integer object.
+    pub fn integer(value: i64) -> Self {
+        Object::Numeric(Numeric::Integer(value))
+    }
+
+    /// Creates a real number object.
+    pub fn real(value: f64) -> Self {
+        Object::Numeric(Numeric::Real(value))
+    }
+
+    /// Creates a name object, `value` is given without the leading `/`.
+    pub fn name(value: impl Into<SmolStr>) -> Self {
+        Object::Name(Name::from(value))
+    }
+
+    /// Creates a literal string object.
+    pub fn string(value: impl Into<String>) -> Self {
+        Object::String(PdfString::Literal(value.into().into_bytes()))
+    }
+
     /// Checks if the object is a null object.
     ///
     /// # Returns
@@ -79,12 +111,89 @@ impl Object {
         matches!(self, Object::Null)
     }
 
+    /// Checks if the object is a dictionary, see [`Object::as_dictionary`].
+    ///
+    /// Like the accessor, an indirect definition of a dictionary counts too.
+    pub fn is_dictionary(&self) -> bool {
+        match self {
+            Object::Dictionary(_) => true,
+            Object::IndirectDefinition(data) => matches!(&**data, Object::Dictionary(_)),
+            _ => false,
+        }
+    }
+
+    /// Checks if the object is an array.
+    pub fn is_array(&self) -> bool {
+        matches!(self, Object::Array(_))
+    }
+
+    /// Checks if the object is a stream, see [`Object::as_stream`].
+    ///
+    /// Like the accessor, an indirect definition of a stream counts too.
+    pub fn is_stream(&self) -> bool {
+        match self {
+            Object::Stream(_) => true,
+            Object::IndirectDefinition(data) => matches!(&**data, Object::Stream(_)),
+            _ => false,
+        }
+    }
+
+    /// Checks if the object is a name.
+    pub fn is_name(&self) -> bool {
+        matches!(self, Object::Name(_))
+    }
+
+    /// Checks if the object is a literal or hexadecimal string.
+    pub fn is_string(&self) -> bool {
+        matches!(self, Object::String(_))
+    }
+
+    /// Checks if the object is an integer or a real number.
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Object::Numeric(_))
+    }
+
+    /// Checks if the object is an indirect reference (`1 0 R`).
+    pub fn is_reference(&self) -> bool {
+        matches!(self, Object::IndirectReference(_))
+    }
+
+    /// Checks if the object is an indirect object definition (`1 0 obj ... endobj`).
+    pub fn is_indirect_definition(&self) -> bool {
+        matches!(self, Object::IndirectDefinition(_))
+    }
+
+    /// Indirect references contained in the object, in order of appearance.
+    ///
+    /// Arrays, dictionaries, stream dictionaries and indirect definitions are
+    /// searched recursively. Referenced objects aren't loaded.
+    pub fn references(&self) -> Vec<IndirectReference> {
+        let mut references = Vec::new();
+        let mut stack = vec![self];
+
+        while let Some(object) = stack.pop() {
+            match object {
+                Object::IndirectReference(reference) => references.push(*reference),
+                Object::Array(array) => stack.extend(array.iter().rev()),
+                Object::Dictionary(dictionary) => stack.extend(dictionary.values().rev()),
+                Object::Stream(stream) => stack.extend(stream.dictionary.values().rev()),
+                Object::IndirectDefinition(definition) => stack.push(definition),
+                _ => {}
+            }
+        }
+
+        references
+    }
+
     /// Returns the direct object by resolving indirect references.
     ///
     /// This method follows indirect references to return the actual object they point to.
     /// If the object is not an indirect reference, it returns the object itself.
-    /// If an indirect reference cannot be resolved (e.g., the referenced object doesn't exist),
-    /// it returns the original indirect reference unchanged.
+    /// An object that is itself a reference, as in `1 0 obj 2 0 R endobj`, is
+    /// followed as well, up to [`Object::MAX_REFERENCE_HOPS`] references.
+    /// If a reference in the chain cannot be resolved (e.g., the referenced object
+    /// doesn't exist), the chain is cyclic or too long, it returns the original
+    /// indirect reference unchanged.
     ///
     /// # Arguments
     /// * `objects` - The object store used to resolve indirect references
@@ -95,14 +204,38 @@ impl Object {
     ///   - It's not an indirect reference, or
     ///   - The indirect reference cannot be resolved
     pub fn direct<'a>(&'a self, objects: &mut Objects) -> Cow<'a, Object> {
-        match self {
-            Object::IndirectReference(obj_ref) => objects
-                .get_object(obj_ref)
-                .map_or(Cow::Borrowed(self), Cow::Owned),
-            _ => Cow::Borrowed(self),
+        let Object::IndirectReference(mut reference) = *self else {
+            return Cow::Borrowed(self);
+        };
+
+        let mut visited = Vec::new();
+        while visited.len() < Self::MAX_REFERENCE_HOPS {
+            if visited.contains(&reference) {
+                tracing::warn!("Reference {reference} is part of a cycle");
+                return Cow::Borrowed(self);
+            }
+            visited.push(reference);
+
+            let Ok(object) = objects.get_object(&reference) else {
+                return Cow::Borrowed(self);
+            };
+            let inner = match &object {
+                Object::IndirectDefinition(definition) => &**definition,
+                object => object,
+            };
+            match inner {
+                Object::IndirectReference(next) => reference = *next,
+                _ => return Cow::Owned(object),
+            }
         }
+
+        tracing::warn!("Reference chain starting at {self:?} is too long");
+        Cow::Borrowed(self)
     }
 
+    /// Number of references [`Object::direct`] follows before giving up.
+    pub const MAX_REFERENCE_HOPS: usize = 32;
+
     /// Attempts to convert the object to an integer of type `T`.
     ///
     /// Only succeeds if the object is a `Numeric::Integer` and the value
@@ -117,17 +250,15 @@ impl Object {
     ///
     /// # Errors
     /// Returns `Error::UnexpectedObjectType` if the object is not a numeric integer.
-    /// Returns `Error::TypeConvertion` if the integer value cannot be converted to type `T`.
+    /// Returns `Error::TypeConvertion` naming the value if it's out of range for type `T`.
     pub fn as_integer<T>(&self) -> Result<T>
     where
         T: TryFrom<i64>,
     {
         match self {
-            Object::Numeric(Numeric::Integer(data)) => Ok(TryInto::try_into(*data)
-                .ok()
-                .with_context(|| error::TypeConvertion {
-                    object: self.clone(),
-                })?),
+            Object::Numeric(number @ Numeric::Integer(_)) => {
+                Ok(number.try_as().context(error::TypeConvertion)?)
+            }
             _ => Err(error::Error::UnexpectedObjectType {
                 expected: "Integer",
                 got: self.clone(),
@@ -257,6 +388,20 @@ impl Object {
         }
     }
 
+    /// Attempts to get the bytes of a string object.
+    ///
+    /// Works the same for literal and hexadecimal strings, see [`PdfString::as_bytes`].
+    ///
+    /// # Returns
+    /// - `Ok(&[u8])` containing the string bytes if successful
+    /// - `Err(Error)` if the object is not a string
+    ///
+    /// # Errors
+    /// Returns `Error::UnexpectedObjectType` if the object is not a string.
+    pub fn as_bytes(&self) -> Result<&[u8]> {
+        Ok(self.as_string()?.as_bytes())
+    }
+
     /// Attempts to convert the object to a dictionary.
     ///
     /// Succeeds if the object is either:
@@ -295,6 +440,30 @@ impl Object {
         }
     }
 
+    /// Mutable access to the dictionary of a dictionary object.
+    ///
+    /// Works like [`Object::as_dictionary`], an indirect definition is
+    /// unwrapped and its dictionary is edited in place.
+    ///
+    /// # Errors
+    /// Returns `Error::UnexpectedObjectType` if the object is not a dictionary
+    /// or an indirect definition containing a dictionary.
+    pub fn as_dictionary_mut(&mut self) -> Result<&mut Dictionary> {
+        let object = match self {
+            Object::IndirectDefinition(data) => data.object_mut(),
+            object => object,
+        };
+
+        match object {
+            Object::Dictionary(data) => Ok(data),
+            object => Err(error::Error::UnexpectedObjectType {
+                expected: "Dictionary",
+                got: object.clone(),
+            }
+            .into()),
+        }
+    }
+
     /// Attempts to convert the object to an indirect reference.
     ///
     /// Only succeeds if the object is an `Object::IndirectReference`.
@@ -370,7 +539,111 @@ mod error {
         #[snafu(display("Unexpected object type. Expected = {expected}. Got = {got:?}"))]
         UnexpectedObjectType { expected: &'static str, got: Object },
 
-        #[snafu(display("Can't convert into Rust type. Object = {object:?}"))]
-        TypeConvertion { object: Object },
+        #[snafu(display("Can't convert into Rust type"))]
+        TypeConvertion {
+            source: crate::types::numeric::Error,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Dictionary, IndirectObject, IndirectReference, Stream};
+
+    #[test]
+    fn test_is_predicates() {
+        type Predicate = fn(&Object) -> bool;
+
+        let predicates: [(&str, Predicate); 9] = [
+            ("null", Object::is_null),
+            ("dictionary", Object::is_dictionary),
+            ("array", Object::is_array),
+            ("stream", Object::is_stream),
+            ("name", Object::is_name),
+            ("string", Object::is_string),
+            ("numeric", Object::is_numeric),
+            ("reference", Object::is_reference),
+            ("indirect definition", Object::is_indirect_definition),
+        ];
+
+        let stream = Object::Stream(Stream::new(Dictionary::default(), Vec::new()));
+        let test_cases: [(Object, &[&str]); 11] = [
+            (Object::Null, &["null"]),
+            (Object::Dictionary(Dictionary::default()), &["dictionary"]),
+            (Object::Array(Vec::new().into()), &["array"]),
+            (stream.clone(), &["stream"]),
+            (Object::name("Type"), &["name"]),
+            (Object::string("text"), &["string"]),
+            (Object::integer(1), &["numeric"]),
+            (Object::real(1.5), &["numeric"]),
+            (
+                Object::IndirectReference(IndirectReference { id: 1, gen_id: 0 }),
+                &["reference"],
+            ),
+            (
+                Object::IndirectDefinition(IndirectObject::new(1, 0, stream)),
+                &["stream", "indirect definition"],
+            ),
+            (
+                Object::IndirectDefinition(IndirectObject::new(2, 0, Object::integer(3))),
+                &["indirect definition"],
+            ),
+        ];
+
+        for (object, expected) in test_cases {
+            for (name, predicate) in predicates {
+                assert_eq!(
+                    predicate(&object),
+                    expected.contains(&name),
+                    "Test '{name}' failed for {object:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_references() {
+        let reference = |id| IndirectReference { id, gen_id: 0 };
+        let object = Object::IndirectDefinition(IndirectObject::new(
+            1,
+            0,
+            Object::Dictionary(Dictionary::from([
+                ("A", Object::IndirectReference(reference(2))),
+                (
+                    "B",
+                    Object::Array(
+                        vec![
+                            Object::integer(1),
+                            Object::IndirectReference(reference(3)),
+                            Object::Array(vec![Object::IndirectReference(reference(4))].into()),
+                        ]
+                        .into(),
+                    ),
+                ),
+                (
+                    "C",
+                    Object::Stream(Stream::new(
+                        Dictionary::from([("D", Object::IndirectReference(reference(2)))]),
+                        b"5 0 R".to_vec(),
+                    )),
+                ),
+            ])),
+        ));
+
+        assert_eq!(
+            object.references(),
+            [reference(2), reference(3), reference(4), reference(2)]
+        );
+        assert!(Object::name("Type").references().is_empty());
+    }
+
+    #[test]
+    fn test_as_integer_out_of_range() {
+        let error = Object::integer(70000).as_integer::<u16>().unwrap_err();
+        let source = std::error::Error::source(&error).map(ToString::to_string);
+
+        assert_eq!(source.as_deref(), Some("70000 out of range for u16"));
+        assert_eq!(Object::integer(270).as_integer::<u16>().unwrap(), 270);
     }
 }
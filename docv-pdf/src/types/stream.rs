@@ -1,14 +1,24 @@
 use std::io::Read;
 
 use flate2::read::ZlibDecoder;
-use snafu::{OptionExt, ResultExt, Snafu};
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
 
-use crate::types::{Dictionary, Object};
+use crate::{
+    parser::read_objects,
+    types::{Dictionary, IndirectReference, Object},
+};
 
 #[derive(Debug, Snafu)]
 pub struct Error(error::Error);
 type Result<T> = std::result::Result<T, Error>;
 
+/// Largest `/DL` trusted as an allocation hint, the decoder grows the buffer past it if needed.
+const MAX_DECODED_LENGTH_HINT: usize = 64 * 1024 * 1024;
+
+/// Highest compression ratio `FlateDecode` can reach, a `/DL` above this
+/// many times the encoded length can't be right.
+const MAX_FLATE_RATIO: usize = 1032;
+
 /// Represents a PDF stream object containing both a dictionary and binary data.
 ///
 /// PDF streams are used to store large amounts of data, such as:
@@ -25,7 +35,8 @@ type Result<T> = std::result::Result<T, Error>;
 /// # Filter Support
 /// Currently supports:
 /// - No filtering (raw data)
-/// - FlateDecode (zlib/deflate compression)
+/// - FlateDecode (zlib/deflate compression) with TIFF and PNG predictors
+/// - DCTDecode and JBIG2Decode passthrough (encoded image data is kept as is)
 /// - Filter pipelines (multiple filters applied in sequence)
 ///
 /// # Example
@@ -54,11 +65,47 @@ enum StreamFilterType {
     None,
     /// FlateDecode compression (zlib/deflate algorithm)
     FlateDecode,
+    /// DCTDecode (JPEG) image data, passed through undecoded
+    DctDecode,
+    /// JBIG2Decode bi-level image data, passed through undecoded
+    Jbig2Decode,
+    /// Crypt filter selected by `/Name` in `/DecodeParms`, decrypted before decoding
+    Crypt,
     /// Multiple filters applied in sequence
     PipeLine(Vec<StreamFilterType>),
 }
 
+impl StreamFilterType {
+    /// Whether the filter reads entries of `/DecodeParms`.
+    fn takes_parms(&self) -> bool {
+        matches!(self, Self::FlateDecode | Self::Jbig2Decode | Self::Crypt)
+    }
+}
+
+/// Format of image data that is left encoded after filter processing.
+///
+/// Image codecs such as JPEG and JBIG2 are not decoded by this crate.
+/// Streams using them keep the encoded bytes, and the format tells the caller
+/// which external decoder the data has to be routed to.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ImageFormat {
+    /// JPEG data (`/DCTDecode`)
+    Jpeg,
+    /// JBIG2 data (`/JBIG2Decode`)
+    Jbig2 {
+        /// Stream with segments shared between several images (`/JBIG2Globals`)
+        globals: Option<IndirectReference>,
+    },
+}
+
 impl Stream {
+    /// Creates a stream, setting `/Length` to the size of `data`.
+    pub fn new(mut dictionary: Dictionary, data: Vec<u8>) -> Self {
+        dictionary.insert("Length", Object::integer(data.len() as i64));
+
+        Self { dictionary, data }
+    }
+
     /// Processes all filters applied to the stream data and decompresses/decodes it.
     ///
     /// This method reads the filter information from the stream's dictionary,
@@ -66,7 +113,8 @@ impl Stream {
     /// data with the processed result.
     ///
     /// # Steps
-    /// 1. Extracts the content length from the dictionary
+    /// 1. Extracts the content length from the dictionary, `/DL` is preferred
+    ///    as the size of the decoded data if present
     /// 2. Parses the filter specification (single filter or pipeline)
     /// 3. Applies filters in reverse order (as per PDF specification)
     /// 4. Replaces the internal data with the processed result
@@ -82,21 +130,360 @@ impl Stream {
     /// - An unsupported filter is specified
     /// - Decompression fails (corrupted data, etc.)
     pub fn process_filters(&mut self) -> Result<()> {
+        self.process_filters_with_limit(None)
+    }
+
+    /// Same as [`Stream::process_filters`], failing once the decoded data
+    /// grows past `limit` bytes.
+    ///
+    /// Decompression stops as soon as the limit is exceeded, so a compression
+    /// bomb is rejected without being inflated in full.
+    pub fn process_filters_with_limit(&mut self, limit: Option<usize>) -> Result<()> {
+        self.data = self.decoded_data(limit)?;
+
+        Ok(())
+    }
+
+    /// Decodes the stream data without modifying the stream.
+    fn decoded_data(&self, limit: Option<usize>) -> Result<Vec<u8>> {
         let content_length = self
             .dictionary
             .get("Length")
             .context(error::NoStreamLength)?
             .as_integer()
             .context(error::UnexpectedDictionaryValue)?;
+        // `/DL` is the decoded length, a better allocation hint than the encoded `/Length`.
+        // It's the size after the whole pipeline, so only the last filter
+        // uses it, and it's never trusted beyond what could be decoded.
+        let decoded_length = self
+            .dictionary
+            .get_integer::<usize>("DL")
+            .and_then(|length| length.ok())
+            .map(|length| {
+                length
+                    .min(MAX_DECODED_LENGTH_HINT)
+                    .min(self.data.len().saturating_mul(MAX_FLATE_RATIO))
+                    .min(limit.unwrap_or(usize::MAX))
+            });
 
-        let filter = match self.dictionary.get("Filter") {
-            Some(object) => process_filter(object)?,
-            None => StreamFilterType::default(),
+        let filters = match self.dictionary.get("Filter") {
+            Some(object) => match process_filter(object)? {
+                StreamFilterType::PipeLine(filters) => filters,
+                filter => vec![filter],
+            },
+            None => Vec::new(),
         };
 
-        self.data = apply_filter(&self.data, &filter, content_length)?;
+        let mut data = self.data.clone();
+        for (index, filter) in filters.iter().enumerate() {
+            let parms = self.decode_parms(&filters, index)?;
+            let capacity = match decoded_length {
+                Some(length) if index + 1 == filters.len() => length,
+                _ => content_length,
+            };
+            data = apply_filter(&data, filter, parms, capacity, limit)?;
+        }
 
-        Ok(())
+        if let Some(limit) = limit {
+            ensure!(data.len() <= limit, error::DecompressedLimit { limit });
+        }
+
+        Ok(data)
+    }
+
+    /// Decode parameters of the filter at `index` of the `/Filter` pipeline.
+    ///
+    /// `/DecodeParms` is normally an array parallel to `/Filter`. Some
+    /// writers store a single dictionary for a filter array, it is applied to
+    /// the first filter that takes parameters.
+    fn decode_parms(
+        &self,
+        filters: &[StreamFilterType],
+        index: usize,
+    ) -> Result<Option<&Dictionary>> {
+        let parms = match self.dictionary.get("DecodeParms") {
+            Some(Object::Array(array)) => array.get(index),
+            Some(parms) => {
+                let target = filters
+                    .iter()
+                    .position(StreamFilterType::takes_parms)
+                    .unwrap_or(0);
+                (target == index).then_some(parms)
+            }
+            None => None,
+        };
+
+        let parms = parms
+            .filter(|object| !object.is_null())
+            .map(|object| object.as_dictionary())
+            .transpose()
+            .context(error::InvalidDecodeParms)?;
+
+        Ok(parms)
+    }
+
+    /// Decodes the stream and parses its content as a sequence of objects.
+    ///
+    /// For object streams (`/Type /ObjStm`) the header of object numbers and
+    /// offsets before `/First` is skipped, so only the contained objects are
+    /// returned.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<Object>)` with the objects in stream order
+    /// - `Err(Error)` if decoding fails or the content isn't a sequence of objects
+    pub fn parse_contents_as_objects(&self) -> Result<Vec<Object>> {
+        let data = self.decoded_data(None)?;
+
+        let is_object_stream = self
+            .dictionary
+            .get("Type")
+            .and_then(|object| object.as_name().ok())
+            .is_some_and(|name| name.as_str() == "ObjStm");
+        let first = if is_object_stream {
+            self.dictionary
+                .get("First")
+                .context(error::NoObjectStreamFirst)?
+                .as_integer::<usize>()
+                .context(error::UnexpectedDictionaryValue)?
+        } else {
+            0
+        };
+
+        let content = data
+            .get(first..)
+            .context(error::ObjectStreamFirstOutOfRange {
+                first,
+                length: data.len(),
+            })?;
+        let objects = read_objects(content).ok().context(error::ParseContents)?;
+
+        Ok(objects)
+    }
+
+    /// Expected size of the data after [`Stream::process_filters`], without decoding it.
+    ///
+    /// For images the size follows from `/Width`, `/Height`, the number of
+    /// color components and `/BitsPerComponent`, for cross-reference streams
+    /// from the field widths `/W` and the number of entries. Any other stream,
+    /// or an image whose color space has to be loaded from another object,
+    /// reports `/Length`.
+    ///
+    /// # Returns
+    /// `None` if the size can't be computed and `/Length` isn't a direct integer
+    pub fn uncompressed_len(&self) -> Option<usize> {
+        let dictionary = &self.dictionary;
+        let integer = |key: &str| dictionary.get_integer::<usize>(key)?.ok();
+
+        let computed = match dictionary.get_name("Type").and_then(|name| name.ok()) {
+            Some("XRef") => {
+                let width = dictionary
+                    .get_array("W")?
+                    .ok()?
+                    .iter()
+                    .map(|object| object.as_integer::<usize>().ok())
+                    .sum::<Option<usize>>();
+                let entries = match dictionary.get_array("Index") {
+                    Some(index) => index
+                        .ok()?
+                        .iter()
+                        .skip(1)
+                        .step_by(2)
+                        .map(|object| object.as_integer::<usize>().ok())
+                        .sum::<Option<usize>>(),
+                    None => integer("Size"),
+                };
+
+                width.zip(entries).map(|(width, entries)| width * entries)
+            }
+            _ if dictionary.get_name("Subtype").and_then(|name| name.ok()) == Some("Image") => {
+                let is_mask = dictionary
+                    .get_bool("ImageMask")
+                    .and_then(|mask| mask.ok())
+                    .unwrap_or(false);
+                let (components, bits) = if is_mask {
+                    (Some(1), Some(1))
+                } else {
+                    (
+                        dictionary.get("ColorSpace").and_then(color_components),
+                        integer("BitsPerComponent"),
+                    )
+                };
+
+                match (integer("Width"), integer("Height"), components, bits) {
+                    (Some(width), Some(height), Some(components), Some(bits)) => {
+                        Some((width * components * bits).div_ceil(8) * height)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        computed.or_else(|| integer("Length"))
+    }
+
+    /// Name of the crypt filter the stream uses in place of the document default.
+    ///
+    /// A `/Crypt` entry in `/Filter` overrides `/StmF` of the encryption
+    /// dictionary for this stream. `Identity` means the data is stored
+    /// unencrypted and must not be decrypted, even in an encrypted document.
+    /// Used by [`crate::CryptFilters::stream_method`].
+    ///
+    /// # Returns
+    /// - `Ok(Some(name))` if `/Filter` has a `/Crypt` filter
+    /// - `Ok(None)` if it doesn't, the document default applies
+    /// - `Err(Error)` if the filter or decode parameters are malformed
+    pub fn crypt_filter(&self) -> Result<Option<String>> {
+        let filters = match self.dictionary.get("Filter") {
+            Some(object) => match process_filter(object)? {
+                StreamFilterType::PipeLine(filters) => filters,
+                filter => vec![filter],
+            },
+            None => return Ok(None),
+        };
+        let Some(index) = filters
+            .iter()
+            .position(|filter| matches!(filter, StreamFilterType::Crypt))
+        else {
+            return Ok(None);
+        };
+
+        let name = self
+            .decode_parms(&filters, index)?
+            .and_then(|parms| parms.get_name("Name"))
+            .transpose()
+            .context(error::InvalidDecodeParms)?
+            .unwrap_or("Identity");
+
+        Ok(Some(name.to_string()))
+    }
+
+    /// Drops the `/Crypt` filter and its decode parameters, once the
+    /// security handler has decrypted the data.
+    pub(crate) fn remove_crypt_filter(&mut self) {
+        let is_crypt =
+            |object: &Object| object.as_name().is_ok_and(|name| name.as_str() == "Crypt");
+
+        match self.dictionary.get("Filter") {
+            Some(Object::Array(filters)) => {
+                let Some(index) = filters.iter().position(is_crypt) else {
+                    return;
+                };
+                let mut filters = filters.to_vec();
+                filters.remove(index);
+                self.dictionary
+                    .insert("Filter", Object::Array(filters.into()));
+
+                if let Some(Object::Array(parms)) = self.dictionary.get("DecodeParms")
+                    && index < parms.len()
+                {
+                    let mut parms = parms.to_vec();
+                    parms.remove(index);
+                    self.dictionary
+                        .insert("DecodeParms", Object::Array(parms.into()));
+                }
+            }
+            Some(filter) if is_crypt(filter) => {
+                self.dictionary.remove("Filter");
+                self.dictionary.remove("DecodeParms");
+            }
+            _ => {}
+        }
+    }
+
+    /// Names of the filters in `/Filter`, in the order they are listed.
+    ///
+    /// Unlike [`Stream::process_filters`] this accepts any filter name, and
+    /// the abbreviations used in inline images, like `/Fl`, are expanded to
+    /// their long form.
+    ///
+    /// # Returns
+    /// - `Ok(names)`, empty if the stream isn't filtered
+    /// - `Err(Error)` if `/Filter` isn't a name, an array of names or `null`
+    pub fn filter_names(&self) -> Result<Vec<String>> {
+        let names = match self.dictionary.get("Filter") {
+            None | Some(Object::Null) => Vec::new(),
+            Some(Object::Name(name)) => vec![name.as_str()],
+            Some(filter @ Object::Array(array)) => array
+                .iter()
+                .map(|object| object.as_name().map(|name| name.as_str()))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .ok()
+                .context(error::InvalidStreamFiltersObject {
+                    object: filter.clone(),
+                })?,
+            Some(filter) => {
+                return Err(error::Error::InvalidStreamFiltersObject {
+                    object: filter.clone(),
+                }
+                .into());
+            }
+        };
+
+        Ok(names
+            .into_iter()
+            .map(|name| long_filter_name(name).to_string())
+            .collect())
+    }
+
+    /// Reports the format of encoded image data left in the stream.
+    ///
+    /// Image filters are always the last ones in a pipeline, so only the
+    /// final filter is inspected. For JBIG2 the `/JBIG2Globals` reference is
+    /// read from the matching `/DecodeParms` entry.
+    ///
+    /// # Returns
+    /// - `Ok(Some(ImageFormat))` if the data stays encoded after [`Stream::process_filters`]
+    /// - `Ok(None)` if the stream has no image filter
+    /// - `Err(Error)` if the filter or decode parameters are malformed
+    pub fn image_format(&self) -> Result<Option<ImageFormat>> {
+        let filters = match self.dictionary.get("Filter") {
+            Some(object) => match process_filter(object)? {
+                StreamFilterType::PipeLine(filters) => filters,
+                filter => vec![filter],
+            },
+            None => return Ok(None),
+        };
+        let Some((index, filter)) = filters.iter().enumerate().next_back() else {
+            return Ok(None);
+        };
+
+        match filter {
+            StreamFilterType::DctDecode => Ok(Some(ImageFormat::Jpeg)),
+            StreamFilterType::Jbig2Decode => {
+                let globals = self
+                    .decode_parms(&filters, index)?
+                    .and_then(|parms| parms.get("JBIG2Globals"))
+                    .map(|object| object.as_indirect_ref().cloned())
+                    .transpose()
+                    .context(error::InvalidDecodeParms)?;
+
+                Ok(Some(ImageFormat::Jbig2 { globals }))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Number of color components of a color space that doesn't refer to other objects.
+fn color_components(color_space: &Object) -> Option<usize> {
+    let (family, parameters) = match color_space {
+        Object::Array(array) => {
+            let (family, parameters) = array.split_first()?;
+            (family.as_name().ok()?, parameters)
+        }
+        object => (object.as_name().ok()?, &[][..]),
+    };
+
+    match family.as_str() {
+        "DeviceGray" | "G" | "CalGray" | "Indexed" | "I" | "Separation" => Some(1),
+        "DeviceRGB" | "RGB" | "CalRGB" | "Lab" => Some(3),
+        "DeviceCMYK" | "CMYK" => Some(4),
+        "DeviceN" => match parameters.first()? {
+            Object::Array(names) => Some(names.len()),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
@@ -105,6 +492,7 @@ impl Stream {
 /// PDF filters can be specified as:
 /// - A single name object (e.g., `/FlateDecode`)
 /// - An array of names for filter pipelines (e.g., `[/ASCII85Decode /FlateDecode]`)
+/// - `null` or an empty array, meaning no filter
 ///
 /// # Arguments
 /// * `filter` - PDF object containing the filter specification
@@ -115,17 +503,22 @@ impl Stream {
 ///
 /// # Errors
 /// Returns an error if:
-/// - The object is not a name or array
+/// - The object is not a name, array or `null`
 /// - An unsupported filter name is encountered
 fn process_filter(filter: &Object) -> Result<StreamFilterType> {
     match filter {
         Object::Name(name) => match name.as_str() {
             "FlateDecode" => Ok(StreamFilterType::FlateDecode),
+            "DCTDecode" => Ok(StreamFilterType::DctDecode),
+            "JBIG2Decode" => Ok(StreamFilterType::Jbig2Decode),
+            "Crypt" => Ok(StreamFilterType::Crypt),
             _ => Err(error::Error::InvalidStreamFilter {
                 name: name.to_string(),
             }
             .into()),
         },
+        Object::Null => Ok(StreamFilterType::None),
+        Object::Array(pipeline) if pipeline.is_empty() => Ok(StreamFilterType::None),
         Object::Array(pipeline) => Ok(StreamFilterType::PipeLine(
             pipeline
                 .iter()
@@ -139,6 +532,20 @@ fn process_filter(filter: &Object) -> Result<StreamFilterType> {
     }
 }
 
+/// Long form of a filter name abbreviated in an inline image, other names are kept.
+fn long_filter_name(name: &str) -> &str {
+    match name {
+        "AHx" => "ASCIIHexDecode",
+        "A85" => "ASCII85Decode",
+        "LZW" => "LZWDecode",
+        "Fl" => "FlateDecode",
+        "RL" => "RunLengthDecode",
+        "CCF" => "CCITTFaxDecode",
+        "DCT" => "DCTDecode",
+        name => name,
+    }
+}
+
 /// Applies a filter (or filter pipeline) to stream data.
 ///
 /// This function handles the actual decoding/decompression of stream data
@@ -148,7 +555,8 @@ fn process_filter(filter: &Object) -> Result<StreamFilterType> {
 /// # Arguments
 /// * `data` - The raw stream data to process
 /// * `filter` - The filter type to apply
-/// * `content_length` - Expected length of decompressed data (for allocation)
+/// * `parms` - Decode parameters of `filter`, ignored for pipelines
+/// * `content_length` - Expected length of decompressed data (for allocation), `/DL` or `/Length`
 ///
 /// # Returns
 /// - `Ok(Vec<u8>)` containing the processed data
@@ -158,70 +566,225 @@ fn process_filter(filter: &Object) -> Result<StreamFilterType> {
 /// Returns an error if:
 /// - FlateDecode decompression fails (corrupted data, etc.)
 /// - An unsupported filter type is specified
-fn apply_filter(data: &[u8], filter: &StreamFilterType, content_length: usize) -> Result<Vec<u8>> {
+fn apply_filter(
+    data: &[u8],
+    filter: &StreamFilterType,
+    parms: Option<&Dictionary>,
+    content_length: usize,
+    limit: Option<usize>,
+) -> Result<Vec<u8>> {
     match filter {
         StreamFilterType::None => Ok(data.to_vec()),
         StreamFilterType::FlateDecode => {
-            let mut decoder = ZlibDecoder::new(data);
+            // One byte past the limit is enough to tell that it's exceeded.
+            let bound = limit.map_or(u64::MAX, |limit| limit as u64 + 1);
+            let mut decoder = ZlibDecoder::new(data).take(bound);
             let mut data = Vec::with_capacity(content_length);
 
             decoder
                 .read_to_end(&mut data)
                 .context(error::Decompression)?;
+            if let Some(limit) = limit {
+                ensure!(data.len() <= limit, error::DecompressedLimit { limit });
+            }
+
+            match parms {
+                Some(parms) => apply_predictor(data, parms),
+                None => Ok(data),
+            }
+        }
+        StreamFilterType::DctDecode | StreamFilterType::Jbig2Decode => Ok(data.to_vec()),
+        // Decryption is done on the raw data by the security handler, see
+        // `Stream::crypt_filter`, which drops the filter afterwards. Any other
+        // filter than `Identity` left here means the data is still encrypted.
+        StreamFilterType::Crypt => {
+            let name = parms
+                .and_then(|parms| parms.get_name("Name"))
+                .transpose()
+                .context(error::InvalidDecodeParms)?
+                .unwrap_or("Identity");
+            ensure!(
+                name == "Identity",
+                error::UnsupportedCryptFilter {
+                    name: name.to_string()
+                }
+            );
 
-            Ok(data)
+            Ok(data.to_vec())
         }
         StreamFilterType::PipeLine(filters) => {
             filters.iter().try_fold(data.to_vec(), |data, filter| {
-                apply_filter(&data, filter, content_length)
+                apply_filter(&data, filter, None, content_length, limit)
             })
         }
     }
 }
 
-impl std::fmt::Display for Stream {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let filter = match self.dictionary.get("Filter") {
-            Some(object) => {
-                let filter = process_filter(object);
+/// Reverses the predictor selected by `/Predictor` in `parms`.
+///
+/// Rows hold `/Columns` samples of `/Colors` components with
+/// `/BitsPerComponent` bits each. For PNG predictors every row starts with a
+/// tag byte naming the PNG filter used for it, so predictors 10 to 15 are
+/// decoded the same way. `/Predictor 1` or a missing entry leaves the data
+/// unchanged.
+fn apply_predictor(mut data: Vec<u8>, parms: &Dictionary) -> Result<Vec<u8>> {
+    let parameter = |key: &str, default: usize| -> Result<usize> {
+        let value = parms
+            .get_integer::<usize>(key)
+            .transpose()
+            .context(error::InvalidDecodeParms)?
+            .unwrap_or(default);
 
-                if filter.is_err() {
-                    return writeln!(f, "--- Data compressed ---");
-                }
+        Ok(value)
+    };
 
-                filter.unwrap()
+    let predictor = parms
+        .get_integer::<i64>("Predictor")
+        .transpose()
+        .context(error::InvalidDecodeParms)?
+        .unwrap_or(1);
+    let colors = parameter("Colors", 1)?.max(1);
+    let bits = parameter("BitsPerComponent", 8)?;
+    let columns = parameter("Columns", 1)?.max(1);
+    ensure!(
+        matches!(bits, 1 | 2 | 4 | 8 | 16),
+        error::UnsupportedBitsPerComponent { bits }
+    );
+
+    // Sub-byte samples are packed, but PNG filters still work on whole bytes.
+    let pixel_bytes = (colors * bits).div_ceil(8);
+    let row_bytes = (columns * colors * bits).div_ceil(8);
+
+    match predictor {
+        1 => return Ok(data),
+        2 => {
+            for row in data.chunks_mut(row_bytes) {
+                undo_tiff_predictor(row, columns * colors, colors, bits);
             }
-            None => StreamFilterType::default(),
-        };
-        let content_length = self.dictionary.get("Length");
-        if content_length.is_none() {
-            return writeln!(f, "--- Data compressed ---");
+            return Ok(data);
         }
-        let content_length = content_length.unwrap().as_integer();
-        if content_length.is_err() {
-            return writeln!(f, "--- Data compressed ---");
+        10..=15 => {}
+        _ => return Err(error::Error::UnsupportedPredictor { predictor }.into()),
+    }
+
+    let mut result = Vec::with_capacity(data.len());
+    let mut previous = vec![0u8; row_bytes];
+    for row in data.chunks(row_bytes + 1) {
+        let (tag, row) = row.split_first().context(error::InvalidPredictorRow)?;
+
+        let mut current = row.to_vec();
+        for i in 0..current.len() {
+            let left = if i >= pixel_bytes {
+                current[i - pixel_bytes]
+            } else {
+                0
+            };
+            let up = previous[i];
+            let up_left = if i >= pixel_bytes {
+                previous[i - pixel_bytes]
+            } else {
+                0
+            };
+
+            let prediction = match tag {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return Err(error::Error::InvalidPredictorTag { tag: *tag }.into()),
+            };
+            current[i] = current[i].wrapping_add(prediction);
         }
-        let content_length = content_length.unwrap();
 
-        match filter {
-            StreamFilterType::None => {
-                let text = String::from_utf8_lossy(self.data.as_ref());
-                writeln!(f, "{}", text)
+        result.extend_from_slice(&current);
+        previous[..current.len()].copy_from_slice(&current);
+    }
+
+    Ok(result)
+}
+
+/// TIFF predictor 2: every component is the difference to the same component of the sample on its left.
+///
+/// # Arguments
+/// * `row` - One row of packed components, modified in place
+/// * `count` - Number of components in the row, without the padding bits
+/// * `colors` - Components per sample
+/// * `bits` - Bits per component, one of 1, 2, 4, 8 or 16
+fn undo_tiff_predictor(row: &mut [u8], count: usize, colors: usize, bits: usize) {
+    match bits {
+        8 => {
+            for i in colors..row.len().min(count) {
+                row[i] = row[i].wrapping_add(row[i - colors]);
+            }
+        }
+        16 => {
+            for i in colors..(row.len() / 2).min(count) {
+                let left = u16::from_be_bytes([row[2 * (i - colors)], row[2 * (i - colors) + 1]]);
+                let value = u16::from_be_bytes([row[2 * i], row[2 * i + 1]]).wrapping_add(left);
+                row[2 * i..2 * i + 2].copy_from_slice(&value.to_be_bytes());
+            }
+        }
+        _ => {
+            let mask = (1u8 << bits) - 1;
+            let shift = |i: usize| 8 - bits - (i * bits) % 8;
+            let get = |row: &[u8], i: usize| (row[i * bits / 8] >> shift(i)) & mask;
+
+            for i in colors..(row.len() * 8 / bits).min(count) {
+                let value = get(row, i).wrapping_add(get(row, i - colors)) & mask;
+                let byte = &mut row[i * bits / 8];
+                *byte = (*byte & !(mask << shift(i))) | (value << shift(i));
             }
+        }
+    }
+}
+
+/// Paeth predictor of the PNG specification.
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = left as i16 + up as i16 - up_left as i16;
+    let distance = |value: u8| (estimate - value as i16).abs();
+
+    if distance(left) <= distance(up) && distance(left) <= distance(up_left) {
+        left
+    } else if distance(up) <= distance(up_left) {
+        up
+    } else {
+        up_left
+    }
+}
+
+impl std::fmt::Display for Stream {
+    /// Writes decoded data as text if it looks textual, otherwise a short hex summary.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Ok(data) = self.decoded_data(None) else {
+            return writeln!(f, "--- Data compressed ---");
+        };
+
+        match str::from_utf8(&data) {
+            Ok(text) if is_printable(text) => writeln!(f, "{}", text),
             _ => {
-                let data = apply_filter(&self.data, &filter, content_length);
-                if data.is_err() {
-                    return writeln!(f, "--- Data compressed ---");
+                write!(f, "--- Binary data, {} bytes:", data.len())?;
+                for byte in data.iter().take(BINARY_PREVIEW_LEN) {
+                    write!(f, " {byte:02x}")?;
                 }
-                let data = data.unwrap();
-
-                let text = String::from_utf8_lossy(&data);
-                writeln!(f, "{}", text)
+                if data.len() > BINARY_PREVIEW_LEN {
+                    write!(f, " ...")?;
+                }
+                writeln!(f, " ---")
             }
         }
     }
 }
 
+/// Number of leading bytes shown when displaying binary stream data.
+const BINARY_PREVIEW_LEN: usize = 16;
+
+/// Checks that `text` has no control characters other than line breaks and tabs.
+fn is_printable(text: &str) -> bool {
+    text.chars()
+        .all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t' | '\x0C'))
+}
+
 mod error {
     use snafu::Snafu;
 
@@ -244,6 +807,36 @@ mod error {
 
         #[snafu(display("Error during decompression"))]
         Decompression { source: std::io::Error },
+
+        #[snafu(display("Stream decodes to more than {limit} bytes"))]
+        DecompressedLimit { limit: usize },
+
+        #[snafu(display("Stream is encrypted with crypt filter `{name}`, decrypt it first"))]
+        UnsupportedCryptFilter { name: String },
+
+        #[snafu(display("Object stream has no `First` entry"))]
+        NoObjectStreamFirst,
+
+        #[snafu(display("Object stream `First` {first} is past the end of its {length} bytes"))]
+        ObjectStreamFirstOutOfRange { first: usize, length: usize },
+
+        #[snafu(display("Stream content is not a sequence of objects"))]
+        ParseContents,
+
+        #[snafu(display("Invalid decode parameters"))]
+        InvalidDecodeParms { source: crate::types::object::Error },
+
+        #[snafu(display("Unsupported predictor {predictor}"))]
+        UnsupportedPredictor { predictor: i64 },
+
+        #[snafu(display("Unsupported predictor bits per component {bits}"))]
+        UnsupportedBitsPerComponent { bits: usize },
+
+        #[snafu(display("Empty row in predicted data"))]
+        InvalidPredictorRow,
+
+        #[snafu(display("Invalid PNG predictor tag {tag}"))]
+        InvalidPredictorTag { tag: u8 },
     }
 }
 
@@ -255,7 +848,7 @@ mod tests {
 
     use flate2::{Compression, write::ZlibEncoder};
 
-    use crate::types::{Dictionary, Numeric, Object, PdfString};
+    use crate::types::{Dictionary, IndirectReference, Numeric, Object, PdfString};
 
     #[test]
     fn test_process_filter() {
@@ -276,6 +869,21 @@ mod tests {
                 expected_ok: false,
                 description: "Invalid single filter",
             },
+            TestCase {
+                input: Object::Name("DCTDecode".into()),
+                expected_ok: true,
+                description: "Valid passthrough image filter",
+            },
+            TestCase {
+                input: Object::Name("JBIG2Decode".into()),
+                expected_ok: true,
+                description: "Valid passthrough bi-level image filter",
+            },
+            TestCase {
+                input: Object::Name("Crypt".into()),
+                expected_ok: true,
+                description: "Valid crypt filter",
+            },
             TestCase {
                 input: Object::Array(
                     vec![
@@ -298,6 +906,16 @@ mod tests {
                 expected_ok: false,
                 description: "Pipeline with invalid filter",
             },
+            TestCase {
+                input: Object::Null,
+                expected_ok: true,
+                description: "Null filter",
+            },
+            TestCase {
+                input: Object::Array(vec![].into()),
+                expected_ok: true,
+                description: "Empty filter array",
+            },
             TestCase {
                 input: Object::Numeric(Numeric::Integer(42)),
                 expected_ok: false,
@@ -458,7 +1076,7 @@ mod tests {
         ];
 
         for case in cases {
-            let result = apply_filter(&case.data, &case.filter, case.content_length);
+            let result = apply_filter(&case.data, &case.filter, None, case.content_length, None);
 
             if case.successful {
                 assert!(result.is_ok(), "Case '{}' should succeed", case.description);
@@ -503,7 +1121,7 @@ mod tests {
                     ("Type".to_string(), Object::Name("XObject".into())),
                     (
                         "Field".to_string(),
-                        Object::String(PdfString::Literal(String::from("123456"))),
+                        Object::String(PdfString::Literal("123456".into())),
                     ),
                     ("Length".to_string(), Object::Numeric(Numeric::Integer(4))),
                 ]),
@@ -512,6 +1130,26 @@ mod tests {
                 successful: true,
                 description: "Valid stream with no filter and custom field",
             },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Filter".to_string(), Object::Null),
+                    ("Length".to_string(), Object::Numeric(Numeric::Integer(4))),
+                ]),
+                data: b"test".to_vec(),
+                expected_data: Some(b"test".to_vec()),
+                successful: true,
+                description: "Null filter leaves data unchanged",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Filter".to_string(), Object::Array(vec![].into())),
+                    ("Length".to_string(), Object::Numeric(Numeric::Integer(4))),
+                ]),
+                data: b"test".to_vec(),
+                expected_data: Some(b"test".to_vec()),
+                successful: true,
+                description: "Empty filter array leaves data unchanged",
+            },
             TestCase {
                 dictionary: Dictionary::from([
                     ("Type".to_string(), Object::Name("XObject".into())),
@@ -527,6 +1165,126 @@ mod tests {
                 successful: true,
                 description: "Valid stream with FlateDecode filter",
             },
+            TestCase {
+                dictionary: Dictionary::from([
+                    (
+                        "Filter".to_string(),
+                        Object::Array(vec![Object::Name("FlateDecode".into())].into()),
+                    ),
+                    (
+                        "DecodeParms".to_string(),
+                        Object::Dictionary(Dictionary::from([
+                            ("Predictor", Object::integer(12)),
+                            ("Columns", Object::integer(5)),
+                        ])),
+                    ),
+                    ("Length".to_string(), Object::Numeric(Numeric::Integer(10))),
+                ]),
+                data: {
+                    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                    encoder
+                        .write_all(b"\x02hello\x02\x00\x00\x00\x00\x01")
+                        .unwrap();
+                    encoder.finish().unwrap()
+                },
+                expected_data: Some(b"hellohellp".to_vec()),
+                successful: true,
+                description: "Single DecodeParms dictionary for a filter array",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Filter".to_string(), Object::Name("FlateDecode".into())),
+                    (
+                        "DecodeParms".to_string(),
+                        Object::Array(
+                            vec![Object::Dictionary(Dictionary::from([(
+                                "Predictor",
+                                Object::integer(12),
+                            )]))]
+                            .into(),
+                        ),
+                    ),
+                    ("Length".to_string(), Object::Numeric(Numeric::Integer(2))),
+                ]),
+                data: {
+                    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(b"\x02a\x02\x01").unwrap();
+                    encoder.finish().unwrap()
+                },
+                expected_data: Some(b"ab".to_vec()),
+                successful: true,
+                description: "DecodeParms array for a single filter",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Filter".to_string(), Object::name("Crypt")),
+                    (
+                        "DecodeParms".to_string(),
+                        Object::Dictionary(Dictionary::from([("Name", Object::name("Identity"))])),
+                    ),
+                    ("Length".to_string(), Object::integer(4)),
+                ]),
+                data: b"test".to_vec(),
+                expected_data: Some(b"test".to_vec()),
+                successful: true,
+                description: "Identity crypt filter leaves data unchanged",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Filter".to_string(), Object::name("Crypt")),
+                    ("Length".to_string(), Object::integer(4)),
+                ]),
+                data: b"test".to_vec(),
+                expected_data: Some(b"test".to_vec()),
+                successful: true,
+                description: "Crypt filter without DecodeParms defaults to Identity",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    (
+                        "Filter".to_string(),
+                        Object::Array(
+                            vec![Object::name("Crypt"), Object::name("FlateDecode")].into(),
+                        ),
+                    ),
+                    (
+                        "DecodeParms".to_string(),
+                        Object::Array(
+                            vec![
+                                Object::Dictionary(Dictionary::from([(
+                                    "Name",
+                                    Object::name("Identity"),
+                                )])),
+                                Object::Null,
+                            ]
+                            .into(),
+                        ),
+                    ),
+                    ("Length".to_string(), Object::integer(5)),
+                ]),
+                data: {
+                    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(b"hello").unwrap();
+                    encoder.finish().unwrap()
+                },
+                expected_data: Some(b"hello".to_vec()),
+                successful: true,
+                description: "Identity crypt filter followed by FlateDecode",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Filter".to_string(), Object::name("Crypt")),
+                    (
+                        "DecodeParms".to_string(),
+                        Object::Dictionary(Dictionary::from([("Name", Object::name("StdCF"))])),
+                    ),
+                    ("Length".to_string(), Object::integer(4)),
+                ]),
+                data: b"test".to_vec(),
+                expected_data: None,
+                successful: false,
+                description: "Named crypt filter fails before decryption",
+            },
             TestCase {
                 dictionary: Dictionary::from([
                     ("Type".to_string(), Object::Name("XObject".into())),
@@ -571,4 +1329,626 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_decoded_length_hint() {
+        let content = vec![7u8; 4096];
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        // Only a hint that's used as is pins the capacity, the others just have to decode.
+        let test_cases = [
+            ("exact hint", Some(4096), None, Some(4096)),
+            ("short hint grows", Some(100), None, None),
+            ("negative hint falls back to length", Some(-1), None, None),
+            ("no hint", None, None, None),
+            (
+                "hint above the limit",
+                Some(1 << 30),
+                Some(4096),
+                Some(4096),
+            ),
+            (
+                "hint above the compression ratio",
+                Some(1 << 40),
+                None,
+                Some(compressed.len() * MAX_FLATE_RATIO),
+            ),
+        ];
+
+        for (name, decoded_length, limit, expected_capacity) in test_cases {
+            let mut dictionary = Dictionary::from([("Filter", Object::name("FlateDecode"))]);
+            if let Some(decoded_length) = decoded_length {
+                dictionary.insert("DL", Object::integer(decoded_length));
+            }
+            let mut stream = Stream::new(dictionary, compressed.clone());
+
+            stream.process_filters_with_limit(limit).unwrap();
+
+            assert_eq!(stream.data, content, "Test '{name}' failed");
+            if let Some(capacity) = expected_capacity {
+                assert_eq!(stream.data.capacity(), capacity, "Test '{name}' failed");
+            }
+        }
+
+        // `/DL` describes the output of the whole pipeline, not of its first filter.
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&compressed).unwrap();
+        let twice = encoder.finish().unwrap();
+        let dictionary = Dictionary::from([
+            (
+                "Filter",
+                Object::Array([Object::name("FlateDecode"), Object::name("FlateDecode")].into()),
+            ),
+            ("DL", Object::integer(4096)),
+        ]);
+        let mut stream = Stream::new(dictionary, twice);
+
+        stream.process_filters().unwrap();
+
+        assert_eq!(stream.data, content);
+        assert_eq!(stream.data.capacity(), 4096);
+    }
+
+    #[test]
+    fn test_decompressed_limit() {
+        const BOMB_LEN: usize = 16 * 1024 * 1024;
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&vec![0; BOMB_LEN]).unwrap();
+        let bomb = encoder.finish().unwrap();
+        assert!(bomb.len() < 64 * 1024);
+
+        struct TestCase {
+            name: &'static str,
+            filter: Option<&'static str>,
+            data: Vec<u8>,
+            limit: Option<usize>,
+            expected_len: Option<usize>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "no limit",
+                filter: Some("FlateDecode"),
+                data: bomb.clone(),
+                limit: None,
+                expected_len: Some(BOMB_LEN),
+            },
+            TestCase {
+                name: "bomb within limit",
+                filter: Some("FlateDecode"),
+                data: bomb.clone(),
+                limit: Some(BOMB_LEN),
+                expected_len: Some(BOMB_LEN),
+            },
+            TestCase {
+                name: "bomb over limit",
+                filter: Some("FlateDecode"),
+                data: bomb,
+                limit: Some(4096),
+                expected_len: None,
+            },
+            TestCase {
+                name: "unfiltered data over limit",
+                filter: None,
+                data: vec![0; 10],
+                limit: Some(4),
+                expected_len: None,
+            },
+        ];
+
+        for case in test_cases {
+            let mut dictionary = Dictionary::default();
+            if let Some(filter) = case.filter {
+                dictionary.insert("Filter", Object::name(filter));
+            }
+            let mut stream = Stream::new(dictionary, case.data);
+            let result = stream.process_filters_with_limit(case.limit);
+
+            match case.expected_len {
+                Some(len) => {
+                    assert!(result.is_ok(), "Test '{}' failed: {result:?}", case.name);
+                    assert_eq!(stream.data.len(), len, "Test '{}' failed", case.name);
+                }
+                None => {
+                    let error = format!("{:?}", result.expect_err(case.name));
+                    assert!(
+                        error.contains("DecompressedLimit"),
+                        "Test '{}' failed: got {error}",
+                        case.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_uncompressed_len() {
+        struct TestCase {
+            name: &'static str,
+            dictionary: Dictionary,
+            expected: Option<usize>,
+        }
+
+        let image = |color_space: Object, bits: i64| {
+            Dictionary::from([
+                ("Subtype", Object::name("Image")),
+                ("Width", Object::integer(10)),
+                ("Height", Object::integer(4)),
+                ("ColorSpace", color_space),
+                ("BitsPerComponent", Object::integer(bits)),
+                ("Filter", Object::name("FlateDecode")),
+                ("Length", Object::integer(35)),
+            ])
+        };
+
+        let test_cases = [
+            TestCase {
+                name: "RGB image",
+                dictionary: image(Object::name("DeviceRGB"), 8),
+                expected: Some(120),
+            },
+            TestCase {
+                name: "16-bit CMYK image",
+                dictionary: image(Object::name("DeviceCMYK"), 16),
+                expected: Some(320),
+            },
+            TestCase {
+                name: "1-bit gray image with padded rows",
+                dictionary: image(Object::name("DeviceGray"), 1),
+                expected: Some(8),
+            },
+            TestCase {
+                name: "indexed image",
+                dictionary: image(
+                    Object::Array(
+                        [
+                            Object::name("Indexed"),
+                            Object::name("DeviceRGB"),
+                            Object::integer(1),
+                            Object::string("abcdef"),
+                        ]
+                        .into(),
+                    ),
+                    4,
+                ),
+                expected: Some(20),
+            },
+            TestCase {
+                name: "image with ICC profile",
+                dictionary: image(
+                    Object::Array(
+                        [
+                            Object::name("ICCBased"),
+                            Object::IndirectReference(IndirectReference { id: 5, gen_id: 0 }),
+                        ]
+                        .into(),
+                    ),
+                    8,
+                ),
+                expected: Some(35),
+            },
+            TestCase {
+                name: "image mask",
+                dictionary: Dictionary::from([
+                    ("Subtype", Object::name("Image")),
+                    ("ImageMask", Object::Boolean(true)),
+                    ("Width", Object::integer(9)),
+                    ("Height", Object::integer(3)),
+                ]),
+                expected: Some(6),
+            },
+            TestCase {
+                name: "cross-reference stream",
+                dictionary: Dictionary::from([
+                    ("Type", Object::name("XRef")),
+                    ("Size", Object::integer(12)),
+                    ("W", Object::Array([1, 2, 1].map(Object::integer).into())),
+                    ("Length", Object::integer(20)),
+                ]),
+                expected: Some(48),
+            },
+            TestCase {
+                name: "cross-reference stream with index",
+                dictionary: Dictionary::from([
+                    ("Type", Object::name("XRef")),
+                    ("Size", Object::integer(12)),
+                    (
+                        "Index",
+                        Object::Array([0, 1, 7, 3].map(Object::integer).into()),
+                    ),
+                    ("W", Object::Array([1, 3, 0].map(Object::integer).into())),
+                ]),
+                expected: Some(16),
+            },
+            TestCase {
+                name: "raw stream",
+                dictionary: Dictionary::from([("Length", Object::integer(15))]),
+                expected: Some(15),
+            },
+            TestCase {
+                name: "indirect length",
+                dictionary: Dictionary::from([(
+                    "Length",
+                    Object::IndirectReference(IndirectReference { id: 9, gen_id: 0 }),
+                )]),
+                expected: None,
+            },
+        ];
+
+        for case in test_cases {
+            let stream = Stream {
+                dictionary: case.dictionary,
+                data: Vec::new(),
+            };
+            assert_eq!(
+                stream.uncompressed_len(),
+                case.expected,
+                "Test '{}' failed",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_predictor() {
+        struct TestCase {
+            name: &'static str,
+            parms: &'static [(&'static str, i64)],
+            data: &'static [u8],
+            expected: Option<&'static [u8]>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "PNG single column",
+                parms: &[("Predictor", 12)],
+                data: b"\x02a\x02\x01",
+                expected: Some(b"ab"),
+            },
+            TestCase {
+                name: "PNG 8-bit RGB",
+                parms: &[("Predictor", 15), ("Colors", 3), ("Columns", 2)],
+                data: &[1, 10, 20, 30, 5, 5, 5, 2, 1, 1, 1, 1, 1, 1],
+                expected: Some(&[10, 20, 30, 15, 25, 35, 11, 21, 31, 16, 26, 36]),
+            },
+            TestCase {
+                name: "PNG 8-bit RGB paeth",
+                parms: &[("Predictor", 14), ("Colors", 3), ("Columns", 2)],
+                data: &[0, 10, 20, 30, 15, 25, 35, 4, 1, 1, 1, 1, 1, 1],
+                expected: Some(&[10, 20, 30, 15, 25, 35, 11, 21, 31, 16, 26, 36]),
+            },
+            TestCase {
+                name: "PNG 16-bit gray",
+                parms: &[("Predictor", 11), ("BitsPerComponent", 16), ("Columns", 2)],
+                data: &[1, 0x01, 0x00, 0x00, 0x80],
+                expected: Some(&[0x01, 0x00, 0x01, 0x80]),
+            },
+            TestCase {
+                name: "TIFF 16-bit gray",
+                parms: &[("Predictor", 2), ("BitsPerComponent", 16), ("Columns", 3)],
+                data: &[0x01, 0x00, 0x00, 0x80, 0xff, 0x00],
+                expected: Some(&[0x01, 0x00, 0x01, 0x80, 0x00, 0x80]),
+            },
+            TestCase {
+                name: "TIFF 8-bit RGB",
+                parms: &[("Predictor", 2), ("Colors", 3), ("Columns", 2)],
+                data: &[10, 20, 30, 5, 5, 5, 11, 21, 31, 5, 5, 5],
+                expected: Some(&[10, 20, 30, 15, 25, 35, 11, 21, 31, 16, 26, 36]),
+            },
+            TestCase {
+                name: "TIFF 4-bit gray with padding",
+                parms: &[("Predictor", 2), ("BitsPerComponent", 4), ("Columns", 3)],
+                data: &[0x32, 0xf0, 0x11, 0x10],
+                expected: Some(&[0x35, 0x40, 0x12, 0x30]),
+            },
+            TestCase {
+                name: "unsupported bits per component",
+                parms: &[("Predictor", 12), ("BitsPerComponent", 3)],
+                data: &[0, 0],
+                expected: None,
+            },
+        ];
+
+        for case in &test_cases {
+            let parms = Dictionary::from(
+                case.parms
+                    .iter()
+                    .map(|(key, value)| (*key, Object::integer(*value)))
+                    .collect::<Vec<_>>(),
+            );
+            let result = apply_predictor(case.data.to_vec(), &parms).ok();
+            assert_eq!(
+                result.as_deref(),
+                case.expected,
+                "Test '{}' failed",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_image_format() {
+        struct TestCase {
+            dictionary: Dictionary,
+            data: Vec<u8>,
+            expected_format: Option<ImageFormat>,
+            expected_data: Vec<u8>,
+            description: &'static str,
+        }
+
+        let compressed = |data: &[u8]| {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        };
+
+        let cases = vec![
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Subtype", Object::Name("Image".into())),
+                    ("Filter", Object::Name("JBIG2Decode".into())),
+                    ("Length", Object::Numeric(Numeric::Integer(4))),
+                    (
+                        "DecodeParms",
+                        Object::Dictionary(Dictionary::from([(
+                            "JBIG2Globals",
+                            Object::IndirectReference(IndirectReference { id: 7, gen_id: 0 }),
+                        )])),
+                    ),
+                ]),
+                data: b"\x97JB2".to_vec(),
+                expected_format: Some(ImageFormat::Jbig2 {
+                    globals: Some(IndirectReference { id: 7, gen_id: 0 }),
+                }),
+                expected_data: b"\x97JB2".to_vec(),
+                description: "JBIG2 image with globals",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Subtype", Object::Name("Image".into())),
+                    ("Filter", Object::Name("JBIG2Decode".into())),
+                    ("Length", Object::Numeric(Numeric::Integer(4))),
+                ]),
+                data: b"\x97JB2".to_vec(),
+                expected_format: Some(ImageFormat::Jbig2 { globals: None }),
+                expected_data: b"\x97JB2".to_vec(),
+                description: "JBIG2 image without globals",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Subtype", Object::Name("Image".into())),
+                    (
+                        "Filter",
+                        Object::Array(
+                            vec![
+                                Object::Name("FlateDecode".into()),
+                                Object::Name("JBIG2Decode".into()),
+                            ]
+                            .into(),
+                        ),
+                    ),
+                    ("Length", Object::Numeric(Numeric::Integer(4))),
+                    (
+                        "DecodeParms",
+                        Object::Array(
+                            vec![
+                                Object::Null,
+                                Object::Dictionary(Dictionary::from([(
+                                    "JBIG2Globals",
+                                    Object::IndirectReference(IndirectReference {
+                                        id: 12,
+                                        gen_id: 0,
+                                    }),
+                                )])),
+                            ]
+                            .into(),
+                        ),
+                    ),
+                ]),
+                data: compressed(b"\x97JB2"),
+                expected_format: Some(ImageFormat::Jbig2 {
+                    globals: Some(IndirectReference { id: 12, gen_id: 0 }),
+                }),
+                expected_data: b"\x97JB2".to_vec(),
+                description: "JBIG2 image at the end of a pipeline",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Subtype", Object::Name("Image".into())),
+                    ("Filter", Object::Name("DCTDecode".into())),
+                    ("Length", Object::Numeric(Numeric::Integer(4))),
+                ]),
+                data: b"\xff\xd8\xff\xe0".to_vec(),
+                expected_format: Some(ImageFormat::Jpeg),
+                expected_data: b"\xff\xd8\xff\xe0".to_vec(),
+                description: "JPEG image",
+            },
+            TestCase {
+                dictionary: Dictionary::from([
+                    ("Filter", Object::Name("FlateDecode".into())),
+                    ("Length", Object::Numeric(Numeric::Integer(5))),
+                ]),
+                data: compressed(b"hello"),
+                expected_format: None,
+                expected_data: b"hello".to_vec(),
+                description: "Not an image filter",
+            },
+        ];
+
+        for case in cases {
+            let mut stream = Stream {
+                dictionary: case.dictionary,
+                data: case.data,
+            };
+
+            let format = stream.image_format();
+            assert!(format.is_ok(), "Case '{}' should succeed", case.description);
+            assert_eq!(
+                format.unwrap(),
+                case.expected_format,
+                "Case '{}' format mismatch",
+                case.description
+            );
+
+            let result = stream.process_filters();
+            assert!(result.is_ok(), "Case '{}' should load", case.description);
+            assert_eq!(
+                stream.data, case.expected_data,
+                "Case '{}' data mismatch",
+                case.description
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_new() {
+        let stream = Stream::new(
+            Dictionary::builder()
+                .entry("Filter", Object::name("FlateDecode"))
+                .entry("Length", Object::integer(100))
+                .build(),
+            b"hello".to_vec(),
+        );
+
+        assert_eq!(
+            stream,
+            Stream {
+                dictionary: Dictionary::from([
+                    ("Filter", Object::Name("FlateDecode".into())),
+                    ("Length", Object::Numeric(Numeric::Integer(5))),
+                ]),
+                data: b"hello".to_vec(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_stream_display() {
+        struct TestCase {
+            name: &'static str,
+            input: Stream,
+            expected: String,
+        }
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"BT /F1 12 Tf (Hi) Tj ET").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let test_cases = [
+            TestCase {
+                name: "raw content stream",
+                input: Stream::new(Dictionary::default(), b"0 0 m 10 10 l S".to_vec()),
+                expected: "0 0 m 10 10 l S\n".to_string(),
+            },
+            TestCase {
+                name: "compressed content stream",
+                input: Stream::new(
+                    Dictionary::from([("Filter", Object::name("FlateDecode"))]),
+                    compressed,
+                ),
+                expected: "BT /F1 12 Tf (Hi) Tj ET\n".to_string(),
+            },
+            TestCase {
+                name: "binary image stream",
+                input: Stream::new(
+                    Dictionary::from([
+                        ("Subtype", Object::name("Image")),
+                        ("Filter", Object::name("DCTDecode")),
+                    ]),
+                    (0..20).map(|i| 0xF0 + i % 16).collect(),
+                ),
+                expected: "--- Binary data, 20 bytes: f0 f1 f2 f3 f4 f5 f6 f7 f8 f9 fa fb fc fd fe ff ... ---\n"
+                    .to_string(),
+            },
+            TestCase {
+                name: "valid UTF-8 with control characters",
+                input: Stream::new(Dictionary::default(), b"\x00\x01A".to_vec()),
+                expected: "--- Binary data, 3 bytes: 00 01 41 ---\n".to_string(),
+            },
+            TestCase {
+                name: "undecodable data",
+                input: Stream::new(
+                    Dictionary::from([("Filter", Object::name("FlateDecode"))]),
+                    b"not zlib".to_vec(),
+                ),
+                expected: "--- Data compressed ---\n".to_string(),
+            },
+        ];
+
+        for case in &test_cases {
+            assert_eq!(
+                case.input.to_string(),
+                case.expected,
+                "Test '{}' failed: wrong output",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_contents_as_objects() {
+        let header = "10 0 11 27 ";
+        let body = "<< /Type /Font /F1 12 >>\n[1 0 R (two)]";
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(format!("{header}{body}").as_bytes())
+            .unwrap();
+
+        let object_stream = Stream::new(
+            Dictionary::from([
+                ("Type", Object::name("ObjStm")),
+                ("N", Object::integer(2)),
+                ("First", Object::integer(header.len() as i64)),
+                ("Filter", Object::name("FlateDecode")),
+            ]),
+            encoder.finish().unwrap(),
+        );
+
+        assert_eq!(
+            object_stream.parse_contents_as_objects().unwrap(),
+            vec![
+                Object::Dictionary(Dictionary::from([
+                    ("Type", Object::name("Font")),
+                    ("F1", Object::integer(12)),
+                ])),
+                Object::Array(
+                    [
+                        Object::IndirectReference(IndirectReference { id: 1, gen_id: 0 }),
+                        Object::String(PdfString::Literal("two".into())),
+                    ]
+                    .into()
+                ),
+            ]
+        );
+
+        let plain = Stream::new(Dictionary::default(), b"1 /Two % comment\n(3)".to_vec());
+        assert_eq!(
+            plain.parse_contents_as_objects().unwrap(),
+            vec![
+                Object::integer(1),
+                Object::name("Two"),
+                Object::String(PdfString::Literal("3".into())),
+            ]
+        );
+
+        let invalid = Stream::new(Dictionary::default(), b"1 0 obj".to_vec());
+        assert!(invalid.parse_contents_as_objects().is_err());
+
+        let first_out_of_range = Stream::new(
+            Dictionary::from([
+                ("Type", Object::name("ObjStm")),
+                ("N", Object::integer(1)),
+                ("First", Object::integer(100)),
+            ]),
+            b"10 0 (ten)".to_vec(),
+        );
+        let error = first_out_of_range.parse_contents_as_objects().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Object stream `First` 100 is past the end of its 10 bytes"
+        );
+    }
 }
@@ -45,9 +45,10 @@ type Result<T> = std::result::Result<T, Error>;
 pub enum PdfString {
     /// A literal string enclosed in parentheses with support for escape sequences.
     ///
-    /// PDF literal strings can contain arbitrary characters with certain characters
-    /// requiring escape sequences. The content is stored after processing escapes.
-    Literal(std::string::String),
+    /// PDF literal strings can contain arbitrary bytes with certain characters
+    /// requiring escape sequences. The content is stored as the bytes left
+    /// after processing escapes, without decoding them as text.
+    Literal(Vec<u8>),
     /// A hexadecimal string representing binary data enclosed in angle brackets.
     ///
     /// Hexadecimal strings store raw byte data as pairs of hexadecimal digits.
@@ -86,53 +87,74 @@ pub struct Date {
 impl PdfString {
     /// Attempts to convert the PDF string to a UTF-8 string slice.
     ///
-    /// For literal strings, returns the content as-is after processing escape sequences.
-    /// For hexadecimal strings, attempts to decode the bytes as UTF-8.
+    /// The bytes of both literal and hexadecimal strings are decoded as UTF-8.
+    /// Use [`PdfString::to_text_string`] for strings in other text encodings.
     ///
     /// # Returns
     /// - `Ok(&str)` containing the string content if successful
-    /// - `Err(Error)` if the hexadecimal data cannot be decoded as UTF-8
+    /// - `Err(Error)` if the data cannot be decoded as UTF-8
     ///
     /// # Errors
-    /// Returns `Error::EncodingStr` if the hexadecimal string contains invalid UTF-8 data.
+    /// Returns `Error::EncodingStr` if the string contains invalid UTF-8 data.
     ///
     /// # Note
     /// Removes Byte Order Mark (BOM) `\u{FEFF}` from the beginning of strings if present.
     pub fn as_str(&self) -> Result<&str> {
-        match self {
-            PdfString::Literal(data) => {
-                let mut data = data.as_str();
-
-                data = data.trim_start_matches("\u{FEFF}");
-
-                Ok(data)
-            }
-            PdfString::Hexadecimal(data) => {
-                let mut data = str::from_utf8(data).with_context(|_| error::EncodingStr {
-                    data: data.to_vec(),
-                })?;
-
-                data = data.trim_start_matches("\u{FEFF}");
+        let data = self.as_bytes();
+        let data = str::from_utf8(data).with_context(|_| error::EncodingStr {
+            data: data.to_vec(),
+        })?;
 
-                Ok(data)
-            }
-        }
+        Ok(data.trim_start_matches("\u{FEFF}"))
     }
 
     /// Returns the raw byte representation of the PDF string.
     ///
-    /// For literal strings, returns the UTF-8 bytes of the string content.
+    /// For literal strings, returns the bytes left after processing escape sequences.
     /// For hexadecimal strings, returns the decoded byte data.
     ///
     /// # Returns
     /// `&[u8]` slice containing the raw byte data
     pub fn as_bytes(&self) -> &[u8] {
         match self {
-            PdfString::Literal(data) => data.as_bytes(),
-            PdfString::Hexadecimal(data) => data.as_slice(),
+            PdfString::Literal(data) | PdfString::Hexadecimal(data) => data.as_slice(),
         }
     }
 
+    /// Decodes the PDF string as a text string (ISO 32000-2, 7.9.2.2).
+    ///
+    /// Strings are decoded as UTF-16BE or UTF-8 when they start with the
+    /// matching Byte Order Mark. Otherwise hexadecimal strings are decoded as
+    /// `PDFDocEncoding`, and so are literal strings that aren't valid UTF-8.
+    /// Undecodable characters are replaced with `U+FFFD`, so this never fails.
+    ///
+    /// # Returns
+    /// `String` with the decoded text, without a leading Byte Order Mark
+    pub fn to_text_string(&self) -> std::string::String {
+        let data = self.as_bytes();
+
+        if let Some(data) = data.strip_prefix(&[0xFE, 0xFF]) {
+            let data = data
+                .chunks(2)
+                .map(|chunk| u16::from_be_bytes([chunk[0], *chunk.get(1).unwrap_or(&0)]))
+                .collect::<Vec<u16>>();
+
+            return std::string::String::from_utf16_lossy(&data);
+        }
+
+        if let Some(data) = data.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+            return std::string::String::from_utf8_lossy(data).to_string();
+        }
+
+        if let PdfString::Literal(data) = self
+            && let Ok(text) = str::from_utf8(data)
+        {
+            return text.to_string();
+        }
+
+        data.iter().map(|byte| pdf_doc_char(*byte)).collect()
+    }
+
     /// Attempts to parse the PDF string as a date value.
     ///
     /// PDF dates follow the format: `(D:YYYYMMDDHHmmSSOHH'mm')`
@@ -145,9 +167,9 @@ impl PdfString {
     /// Returns `Error::ParseTo` if the string does not match the expected PDF date format
     /// or contains invalid date components.
     pub fn to_date(&self) -> Result<Date> {
-        let input = self.as_str()?;
-        let (_, date) = read_date(input).ok().with_context(|| error::ParseTo {
-            data: input.to_string(),
+        let input = self.to_text_string();
+        let (_, date) = read_date(&input).ok().with_context(|| error::ParseTo {
+            data: input.clone(),
             target: "date",
         })?;
 
@@ -155,9 +177,33 @@ impl PdfString {
     }
 }
 
+/// Maps a `PDFDocEncoding` byte to its character (ISO 32000-2, Annex D.2).
+///
+/// The encoding matches Latin-1 except for the ranges handled below.
+fn pdf_doc_char(byte: u8) -> char {
+    const LOW: [char; 8] = [
+        '\u{02D8}', '\u{02C7}', '\u{02C6}', '\u{02D9}', '\u{02DD}', '\u{02DB}', '\u{02DA}',
+        '\u{02DC}',
+    ];
+    const HIGH: [char; 33] = [
+        '\u{2022}', '\u{2020}', '\u{2021}', '\u{2026}', '\u{2014}', '\u{2013}', '\u{0192}',
+        '\u{2044}', '\u{2039}', '\u{203A}', '\u{2212}', '\u{2030}', '\u{201E}', '\u{201C}',
+        '\u{201D}', '\u{2018}', '\u{2019}', '\u{201A}', '\u{2122}', '\u{FB01}', '\u{FB02}',
+        '\u{0141}', '\u{0152}', '\u{0160}', '\u{0178}', '\u{017D}', '\u{0131}', '\u{0142}',
+        '\u{0153}', '\u{0161}', '\u{017E}', '\u{FFFD}', '\u{20AC}',
+    ];
+
+    match byte {
+        0x18..=0x1F => LOW[(byte - 0x18) as usize],
+        0x80..=0xA0 => HIGH[(byte - 0x80) as usize],
+        0xAD => '\u{FFFD}',
+        _ => byte as char,
+    }
+}
+
 impl<T: std::convert::Into<String>> From<T> for PdfString {
     fn from(value: T) -> Self {
-        Self::Literal(value.into())
+        Self::Literal(value.into().into_bytes())
     }
 }
 
@@ -183,3 +229,125 @@ mod error {
         ParseTo { data: String, target: &'static str },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Object;
+
+    #[test]
+    fn test_to_text_string() {
+        struct TestCase {
+            name: &'static str,
+            input: PdfString,
+            expected: &'static str,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "literal string",
+                input: PdfString::Literal("Hello".into()),
+                expected: "Hello",
+            },
+            TestCase {
+                name: "literal string with BOM",
+                input: PdfString::Literal("\u{FEFF}Hello".into()),
+                expected: "Hello",
+            },
+            TestCase {
+                name: "literal UTF-16BE",
+                input: PdfString::Literal(vec![0xFE, 0xFF, 0x4F, 0x60, 0x59, 0x7D]),
+                expected: "你好",
+            },
+            TestCase {
+                name: "literal PDFDocEncoding",
+                input: PdfString::Literal(vec![0x47, 0x72, 0xFC, 0xDF, 0x65]),
+                expected: "Grüße",
+            },
+            TestCase {
+                name: "hexadecimal UTF-16BE",
+                input: PdfString::Hexadecimal(vec![0xFE, 0xFF, 0x4F, 0x60, 0x59, 0x7D]),
+                expected: "你好",
+            },
+            TestCase {
+                name: "hexadecimal UTF-8",
+                input: PdfString::Hexadecimal("\u{FEFF}Grüße".as_bytes().to_vec()),
+                expected: "Grüße",
+            },
+            TestCase {
+                name: "hexadecimal PDFDocEncoding",
+                input: PdfString::Hexadecimal(vec![0x84, 0x20, 0x47, 0x72, 0xFC, 0xDF, 0x65, 0xA0]),
+                expected: "— Grüße€",
+            },
+            TestCase {
+                name: "hexadecimal UTF-16BE odd length",
+                input: PdfString::Hexadecimal(vec![0xFE, 0xFF, 0x00, 0x41, 0x00]),
+                expected: "A\0",
+            },
+        ];
+
+        for case in &test_cases {
+            assert_eq!(
+                case.input.to_text_string(),
+                case.expected,
+                "Test '{}' failed: wrong text",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        struct TestCase {
+            name: &'static str,
+            input: Object,
+            expected: Option<&'static [u8]>,
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "literal string",
+                input: Object::String(PdfString::Literal("ID-1".into())),
+                expected: Some(b"ID-1"),
+            },
+            TestCase {
+                name: "literal string with non-UTF-8 bytes",
+                input: Object::String(PdfString::Literal(vec![0xFF, 0x00])),
+                expected: Some(&[0xFF, 0x00]),
+            },
+            TestCase {
+                name: "hexadecimal string",
+                input: Object::String(PdfString::Hexadecimal(vec![0x00, 0xFF, 0x10])),
+                expected: Some(&[0x00, 0xFF, 0x10]),
+            },
+            TestCase {
+                name: "empty hexadecimal string",
+                input: Object::String(PdfString::Hexadecimal(vec![])),
+                expected: Some(b""),
+            },
+            TestCase {
+                name: "not a string",
+                input: Object::name("ID"),
+                expected: None,
+            },
+        ];
+
+        for case in &test_cases {
+            if let Object::String(string) = &case.input {
+                assert_eq!(
+                    Some(string.as_bytes()),
+                    case.expected,
+                    "Test '{}' failed: wrong string bytes",
+                    case.name
+                );
+            }
+
+            assert_eq!(
+                case.input.as_bytes().ok(),
+                case.expected,
+                "Test '{}' failed: wrong object bytes",
+                case.name
+            );
+        }
+    }
+}
@@ -0,0 +1,472 @@
+//! Serialization of objects back into PDF syntax.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use flate2::{Compression, write::ZlibEncoder};
+
+use crate::{
+    structures::root::version::Version,
+    types::{Dictionary, IndirectReference, Name, Numeric, Object, PdfString, Stream},
+};
+
+/// Writes a PDF file with a classic cross-reference table.
+///
+/// Objects are written in call order and their offsets are recorded, so
+/// [`PdfWriter::finish`] can emit a matching `xref` table and trailer.
+#[derive(Debug)]
+pub struct PdfWriter<W: Write> {
+    output: W,
+    offset: usize,
+    /// Offset and generation of every written object, by object number.
+    entries: BTreeMap<usize, (usize, usize)>,
+    /// Object stream number and index of every packed object, by object number.
+    compressed: BTreeMap<usize, (usize, usize)>,
+    /// Whether the output continues an existing file, see [`PdfWriter::append`].
+    incremental: bool,
+}
+
+impl<W: Write> PdfWriter<W> {
+    /// Starts a file by writing the header for `version`.
+    ///
+    /// The header comment with high-bit bytes marks the file as binary for
+    /// transfer tools.
+    pub fn new(output: W, version: &Version) -> io::Result<Self> {
+        let mut writer = Self {
+            output,
+            offset: 0,
+            entries: BTreeMap::new(),
+            compressed: BTreeMap::new(),
+            incremental: false,
+        };
+        writer.write_bytes(format!("%PDF-{version}\n").as_bytes())?;
+        writer.write_bytes(b"%\xE2\xE3\xCF\xD3\n")?;
+
+        Ok(writer)
+    }
+
+    /// Continues a file for an incremental update.
+    ///
+    /// `output` already holds the original file, `offset` bytes counted from
+    /// its `%PDF-` header. The cross-reference section written by
+    /// [`PdfWriter::finish`] then only lists the newly written objects and
+    /// the trailer keeps its `/Prev`.
+    pub fn append(output: W, offset: usize) -> Self {
+        Self {
+            output,
+            offset,
+            entries: BTreeMap::new(),
+            compressed: BTreeMap::new(),
+            incremental: true,
+        }
+    }
+
+    /// Writes `object` as the indirect object `reference`.
+    pub fn write_object(
+        &mut self,
+        reference: IndirectReference,
+        object: &Object,
+    ) -> io::Result<()> {
+        self.entries
+            .insert(reference.id, (self.offset, reference.gen_id));
+
+        let mut data = format!("{} {} obj\n", reference.id, reference.gen_id).into_bytes();
+        serialize_object(object, &mut data);
+        data.extend_from_slice(b"\nendobj\n");
+
+        self.write_bytes(&data)
+    }
+
+    /// Copies `data`, the complete indirect object `reference` taken from another file.
+    ///
+    /// `data` has to start with `N G obj` and end with `endobj`.
+    pub fn write_raw(&mut self, reference: IndirectReference, data: &[u8]) -> io::Result<()> {
+        self.entries
+            .insert(reference.id, (self.offset, reference.gen_id));
+
+        self.write_bytes(data)?;
+        self.write_bytes(b"\n")
+    }
+
+    /// Writes `objects` packed into the Flate-compressed object stream `reference`.
+    ///
+    /// The packed objects must not be streams and must have generation 0.
+    /// They can only be listed by [`PdfWriter::finish_with_xref_stream`].
+    pub fn write_object_stream(
+        &mut self,
+        reference: IndirectReference,
+        objects: &[(IndirectReference, Object)],
+    ) -> io::Result<()> {
+        let mut header = Vec::new();
+        let mut body = Vec::new();
+        for (index, (packed, object)) in objects.iter().enumerate() {
+            self.compressed.insert(packed.id, (reference.id, index));
+
+            header.extend_from_slice(format!("{} {} ", packed.id, body.len()).as_bytes());
+            serialize_object(object, &mut body);
+            body.push(b'\n');
+        }
+
+        let first = header.len();
+        header.extend_from_slice(&body);
+
+        let stream = Stream {
+            dictionary: Dictionary::from([
+                ("Type", Object::name("ObjStm")),
+                ("N", Object::integer(objects.len() as i64)),
+                ("First", Object::integer(first as i64)),
+                ("Filter", Object::name("FlateDecode")),
+            ]),
+            data: compress(&header)?,
+        };
+
+        self.write_object(reference, &Object::Stream(stream))
+    }
+
+    /// Writes the cross-reference table, `trailer` and `startxref`.
+    ///
+    /// `/Size` is set from the highest written object number, `/Prev` is
+    /// removed as the table covers every object. For incremental updates
+    /// only the written objects are listed, `/Prev` is kept and `/Size`
+    /// never shrinks below the one given in `trailer`.
+    ///
+    /// # Returns
+    /// The underlying output
+    pub fn finish(mut self, mut trailer: Dictionary) -> io::Result<W> {
+        let xref_offset = self.offset;
+        let mut size = self.entries.keys().next_back().map_or(1, |id| id + 1);
+
+        let mut xref = "xref\n".to_string();
+        if self.incremental {
+            let ids = self.entries.keys().copied().collect::<Vec<_>>();
+            for run in ids.chunk_by(|a, b| a + 1 == *b) {
+                xref.push_str(&format!("{} {}\n", run[0], run.len()));
+                for id in run {
+                    let (offset, gen_id) = self.entries[id];
+                    xref.push_str(&format!("{offset:010} {gen_id:05} n \n"));
+                }
+            }
+
+            let previous = trailer
+                .get("Size")
+                .and_then(|size| size.as_integer::<usize>().ok())
+                .unwrap_or(0);
+            size = size.max(previous);
+        } else {
+            xref.push_str(&format!("0 {size}\n"));
+            for id in 0..size {
+                match self.entries.get(&id) {
+                    Some((offset, gen_id)) => {
+                        xref.push_str(&format!("{offset:010} {gen_id:05} n \n"))
+                    }
+                    None if id == 0 => xref.push_str("0000000000 65535 f \n"),
+                    None => xref.push_str("0000000000 00001 f \n"),
+                }
+            }
+
+            trailer.remove("Prev");
+        }
+        self.write_bytes(xref.as_bytes())?;
+
+        trailer.insert("Size", Object::integer(size as i64));
+
+        let mut data = b"trailer\n".to_vec();
+        serialize_dictionary(&trailer, &mut data);
+        data.extend_from_slice(format!("\nstartxref\n{xref_offset}\n%%EOF\n").as_bytes());
+        self.write_bytes(&data)?;
+
+        self.output.flush()?;
+        Ok(self.output)
+    }
+
+    /// Writes a cross-reference stream with the entries of `trailer`, then `startxref`.
+    ///
+    /// Unlike [`PdfWriter::finish`], objects packed by
+    /// [`PdfWriter::write_object_stream`] are listed too. The stream takes
+    /// the object number after the highest written one, `/Size` and `/Prev`
+    /// are handled as for a complete file.
+    ///
+    /// # Returns
+    /// The underlying output
+    pub fn finish_with_xref_stream(mut self, mut trailer: Dictionary) -> io::Result<W> {
+        let last = self.entries.keys().chain(self.compressed.keys()).max();
+        let id = last.map_or(1, |id| id + 1);
+        let size = id + 1;
+
+        let xref_offset = self.offset;
+        self.entries.insert(id, (xref_offset, 0));
+
+        let rows = (0..size)
+            .map(|id| {
+                if let Some((offset, gen_id)) = self.entries.get(&id) {
+                    (1, *offset, *gen_id)
+                } else if let Some((stream_id, index)) = self.compressed.get(&id) {
+                    (2, *stream_id, *index)
+                } else if id == 0 {
+                    (0, 0, 65535)
+                } else {
+                    (0, 0, 1)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let width = |value: usize| (usize::BITS - value.leading_zeros()).div_ceil(8).max(1);
+        let second = rows.iter().map(|row| width(row.1)).max().unwrap_or(1);
+        let third = rows.iter().map(|row| width(row.2)).max().unwrap_or(1);
+
+        let mut data = Vec::new();
+        for (kind, field, number) in rows {
+            data.push(kind);
+            data.extend_from_slice(&field.to_be_bytes()[(usize::BITS / 8 - second) as usize..]);
+            data.extend_from_slice(&number.to_be_bytes()[(usize::BITS / 8 - third) as usize..]);
+        }
+
+        trailer.remove("Prev");
+        trailer.insert("Type", Object::name("XRef"));
+        trailer.insert("Size", Object::integer(size as i64));
+        trailer.insert(
+            "W",
+            Object::Array(
+                [
+                    Object::integer(1),
+                    Object::integer(second as i64),
+                    Object::integer(third as i64),
+                ]
+                .into(),
+            ),
+        );
+        trailer.insert("Filter", Object::name("FlateDecode"));
+
+        let stream = Stream {
+            dictionary: trailer,
+            data: compress(&data)?,
+        };
+
+        let mut data = format!("{id} 0 obj\n").into_bytes();
+        serialize_object(&Object::Stream(stream), &mut data);
+        data.extend_from_slice(format!("\nendobj\nstartxref\n{xref_offset}\n%%EOF\n").as_bytes());
+        self.write_bytes(&data)?;
+
+        self.output.flush()?;
+        Ok(self.output)
+    }
+
+    fn write_bytes(&mut self, data: &[u8]) -> io::Result<()> {
+        self.output.write_all(data)?;
+        self.offset += data.len();
+
+        Ok(())
+    }
+}
+
+fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(data)?;
+
+    encoder.finish()
+}
+
+/// Appends the PDF syntax of `object` to `output`.
+///
+/// Indirect definitions are written as their inner object, stream
+/// `/Length` is always set to the length of the stored data.
+pub fn serialize_object(object: &Object, output: &mut Vec<u8>) {
+    match object {
+        Object::Boolean(value) => output.extend_from_slice(value.to_string().as_bytes()),
+        Object::Numeric(value) => serialize_numeric(value, output),
+        Object::String(value) => serialize_string(value, output),
+        Object::Name(value) => serialize_name(value, output),
+        Object::Null => output.extend_from_slice(b"null"),
+        Object::Array(array) => {
+            output.push(b'[');
+            for (index, element) in array.iter().enumerate() {
+                if index > 0 {
+                    output.push(b' ');
+                }
+                serialize_object(element, output);
+            }
+            output.push(b']');
+        }
+        Object::Dictionary(dictionary) => serialize_dictionary(dictionary, output),
+        Object::Stream(stream) => serialize_stream(stream, output),
+        Object::IndirectDefinition(definition) => serialize_object(definition, output),
+        Object::IndirectReference(reference) => {
+            output.extend_from_slice(reference.to_string().as_bytes())
+        }
+    }
+}
+
+fn serialize_numeric(value: &Numeric, output: &mut Vec<u8>) {
+    let text = match value {
+        Numeric::Integer(value) => value.to_string(),
+        // Reals have no exponent form in PDF, which `Display` never uses.
+        Numeric::Real(value) if value.is_finite() => {
+            let text = value.to_string();
+            if text.contains('.') {
+                text
+            } else {
+                format!("{text}.0")
+            }
+        }
+        Numeric::Real(_) => "0.0".to_string(),
+    };
+
+    output.extend_from_slice(text.as_bytes());
+}
+
+fn serialize_string(value: &PdfString, output: &mut Vec<u8>) {
+    match value {
+        PdfString::Literal(text) => {
+            output.push(b'(');
+            for &byte in text {
+                match byte {
+                    b'(' | b')' | b'\\' => output.extend_from_slice(&[b'\\', byte]),
+                    b'\r' => output.extend_from_slice(b"\\r"),
+                    b'\n' => output.extend_from_slice(b"\\n"),
+                    _ => output.push(byte),
+                }
+            }
+            output.push(b')');
+        }
+        PdfString::Hexadecimal(data) => {
+            output.push(b'<');
+            for byte in data {
+                output.extend_from_slice(format!("{byte:02X}").as_bytes());
+            }
+            output.push(b'>');
+        }
+    }
+}
+
+/// Writes a name, escaping delimiters, whitespace and `#` as `#xx`.
+fn serialize_name(value: &Name, output: &mut Vec<u8>) {
+    output.push(b'/');
+    for byte in value.bytes() {
+        let escape = !(0x21..=0x7E).contains(&byte)
+            || matches!(
+                byte,
+                b'#' | b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+            );
+
+        if escape {
+            output.extend_from_slice(format!("#{byte:02X}").as_bytes());
+        } else {
+            output.push(byte);
+        }
+    }
+}
+
+fn serialize_dictionary(dictionary: &Dictionary, output: &mut Vec<u8>) {
+    output.extend_from_slice(b"<<");
+    for (key, value) in dictionary.iter() {
+        output.push(b' ');
+        serialize_name(&Name::from(key.clone()), output);
+        output.push(b' ');
+        serialize_object(value, output);
+    }
+    output.extend_from_slice(b" >>");
+}
+
+fn serialize_stream(stream: &Stream, output: &mut Vec<u8>) {
+    let mut dictionary = stream.dictionary.clone();
+    dictionary.insert("Length", Object::integer(stream.data.len() as i64));
+
+    serialize_dictionary(&dictionary, output);
+    output.extend_from_slice(b"\nstream\n");
+    output.extend_from_slice(&stream.data);
+    output.extend_from_slice(b"\nendstream");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::read_object;
+
+    #[test]
+    fn test_serialize_object() {
+        struct TestCase {
+            name: &'static str,
+            object: Object,
+            expected: &'static [u8],
+        }
+
+        let test_cases = [
+            TestCase {
+                name: "integer and real",
+                object: Object::Array([Object::integer(-3), Object::real(2.0)].into()),
+                expected: b"[-3 2.0]",
+            },
+            TestCase {
+                name: "literal string with delimiters",
+                object: Object::string("a(b)\\c\n"),
+                expected: b"(a\\(b\\)\\\\c\\n)",
+            },
+            TestCase {
+                name: "literal string with binary bytes",
+                object: Object::String(PdfString::Literal(vec![0xFF, 0x00, b')'])),
+                expected: b"(\xFF\x00\\))",
+            },
+            TestCase {
+                name: "hexadecimal string",
+                object: Object::String(PdfString::Hexadecimal(vec![0x00, 0xAB])),
+                expected: b"<00AB>",
+            },
+            TestCase {
+                name: "name with special characters",
+                object: Object::name("A B#"),
+                expected: b"/A#20B#23",
+            },
+            TestCase {
+                name: "dictionary with reference",
+                object: Object::Dictionary(Dictionary::from([
+                    (
+                        "Pages",
+                        Object::IndirectReference(IndirectReference { id: 2, gen_id: 0 }),
+                    ),
+                    ("Type", Object::name("Catalog")),
+                ])),
+                expected: b"<< /Pages 2 0 R /Type /Catalog >>",
+            },
+            TestCase {
+                name: "dictionary with empty keys and names",
+                object: Object::Dictionary(Dictionary::from([
+                    ("", Object::name("Value")),
+                    ("Empty", Object::name("")),
+                ])),
+                expected: b"<< / /Value /Empty / >>",
+            },
+            TestCase {
+                name: "stream with stale length",
+                object: Object::Stream(Stream {
+                    dictionary: Dictionary::from([("Length", Object::integer(100))]),
+                    data: b"q Q".to_vec(),
+                }),
+                expected: b"<< /Length 3 >>\nstream\nq Q\nendstream",
+            },
+        ];
+
+        for case in test_cases {
+            let mut output = Vec::new();
+            serialize_object(&case.object, &mut output);
+            assert_eq!(
+                output,
+                case.expected,
+                "Test '{}' failed: got {:?}",
+                case.name,
+                String::from_utf8_lossy(&output)
+            );
+
+            // Stream lengths are rewritten on purpose.
+            if !case.object.is_stream() {
+                let reparsed = read_object(&output).unwrap();
+                assert_eq!(
+                    reparsed, case.object,
+                    "Test '{}' failed: object changed after reparsing",
+                    case.name
+                );
+            }
+        }
+    }
+}
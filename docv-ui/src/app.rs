@@ -267,14 +267,8 @@ impl App {
             _ => None,
         })];
 
-        if self.document.is_some() {
-            subscriptions.push(
-                self.document
-                    .as_ref()
-                    .unwrap()
-                    .subscription()
-                    .map(Message::Document),
-            );
+        if let Some(document) = self.document.as_ref() {
+            subscriptions.push(document.subscription().map(Message::Document));
         }
 
         match self.action_area {
@@ -45,11 +45,7 @@ impl Document {
 
         let filename = path.file_name().unwrap().to_string_lossy().to_string();
 
-        let title = file
-            .info()
-            .title
-            .clone()
-            .unwrap_or_else(|| filename.clone());
+        let title = file.title().unwrap_or_else(|| filename.clone());
 
         let pages = file
             .pages()
@@ -172,6 +168,7 @@ impl Document {
     pub fn view_info(&self) -> Element<'_, Message> {
         let file = self.file.lock();
         let info = file.info();
+        let field = |value: Option<String>| value.unwrap_or_else(|| "Unavailable".to_string());
 
         container(
             column![
@@ -183,15 +180,9 @@ impl Document {
                 .style(container::rounded_box),
                 container(
                     column![
-                        text!("Title: {}", info.title.as_deref().unwrap_or("Unavailable")),
-                        text!(
-                            "Subject: {}",
-                            info.subject.as_deref().unwrap_or("Unavailable")
-                        ),
-                        text!(
-                            "Keywords: {}",
-                            info.keywords.as_deref().unwrap_or("Unavailable")
-                        ),
+                        text!("Title: {}", field(file.title())),
+                        text!("Subject: {}", field(file.subject())),
+                        text!("Keywords: {}", field(file.keywords())),
                     ]
                     .padding(10)
                     .spacing(10)
@@ -199,18 +190,9 @@ impl Document {
                 .style(container::rounded_box),
                 container(
                     column![
-                        text!(
-                            "Author: {}",
-                            info.author.as_deref().unwrap_or("Unavailable")
-                        ),
-                        text!(
-                            "Creator: {}",
-                            info.creator.as_deref().unwrap_or("Unavailable")
-                        ),
-                        text!(
-                            "Producer: {}",
-                            info.producer.as_deref().unwrap_or("Unavailable")
-                        ),
+                        text!("Author: {}", field(file.author())),
+                        text!("Creator: {}", field(file.creator())),
+                        text!("Producer: {}", field(file.producer())),
                     ]
                     .padding(10)
                     .spacing(10)
@@ -220,11 +202,11 @@ impl Document {
                     column![
                         text(format!(
                             "Creation date: {}",
-                            info.creation_date.unwrap_or_default()
+                            info.and_then(|info| info.creation_date).unwrap_or_default()
                         )),
                         text(format!(
                             "Modified date: {}",
-                            info.mod_date.unwrap_or_default()
+                            info.and_then(|info| info.mod_date).unwrap_or_default()
                         )),
                     ]
                     .padding(10)
@@ -234,7 +216,13 @@ impl Document {
                 container(
                     column![
                         text(format!("Version: {}", file.version())),
-                        text(format!("Trapped: {}", info.trapped)),
+                        text(format!(
+                            "Trapped: {}",
+                            info.map_or_else(
+                                || "Unknown".to_string(),
+                                |info| info.trapped.to_string()
+                            )
+                        )),
                         text(format!(
                             "File size: {:.2} Mib",
                             file.filesize() as f64 / ((1024 * 1024) as f64)